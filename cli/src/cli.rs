@@ -3,7 +3,8 @@ use std::path::PathBuf;
 
 use crate::{
     cli_meta_generation_feature::CliMetaGenerationFeature,
-    cli_meta_generation_format::CliMetaGenerationFormat, cli_template_type::CliTemplateType,
+    cli_meta_generation_format::CliMetaGenerationFormat, cli_metadata_format::CliMetadataFormat,
+    cli_nav_title_source::CliNavTitleSource, cli_template_type::CliTemplateType,
     consts::POSSIBLE_TEMPLATES,
 };
 
@@ -61,6 +62,37 @@ pub(crate) enum Commands {
             num_args = 1..,
         )]
         markdown_projects: Option<Vec<String>>,
+        #[arg(
+            long = "meta",
+            help = "Override metadata for this conversion, in the form key=value. Can be used multiple times.",
+            long_help = r#"Override metadata for this conversion, in the form key=value. Can be used multiple times.
+Overrides take precedence over the shared, profile, template and markdown project metadata. Use dotted keys like series.name=Dreams to set fields in nested tables."#
+        )]
+        metadata_overrides: Vec<String>,
+        #[arg(
+            short,
+            long,
+            help = "Watch the project for changes and convert the affected templates again.",
+            long_help = r#"Watch the project for changes and convert the affected templates again.
+The markdown project directories, their resources, the template directory and the manifest are watched. Only the conversions affected by a change are run again. Failed conversions are reported, but do not stop watching."#
+        )]
+        watch: bool,
+        #[arg(
+            short,
+            long,
+            help = "The number of conversions to run in parallel. Defaults to 1.",
+            long_help = r#"The number of conversions to run in parallel. Defaults to 1.
+Conversions of markdown projects sharing an output directory are always run one after another. If more than one job is used, all conversions are run even if one fails, and the status of every conversion is printed in queue order."#
+        )]
+        jobs: Option<usize>,
+        #[arg(
+            long,
+            help = "Print the conversion plan without running pandoc, LaTeX or Typst.",
+            long_help = r#"Print the conversion plan without running pandoc, LaTeX or Typst.
+For every conversion, the converter, the preprocessors, the injections, the input file chunks, the command lines of all external tools and the output path are printed. The build and output directories are not modified."#,
+            conflicts_with = "watch"
+        )]
+        dry_run: bool,
     },
     #[command(about = "Initialize a new TiefDown project.")]
     Init {
@@ -120,6 +152,19 @@ If the number of conversion folders in the project is above this threshold, old
     },
     #[command(about = "Validate dependencies are installed.")]
     CheckDependencies,
+    #[command(
+        about = "Print the JSON Schema of the manifest.",
+        long_about = r#"Print the JSON Schema of the manifest.
+The schema describes the manifest of the current manifest version and can be used by TOML-aware editors for autocompletion and validation."#
+    )]
+    Schema {
+        #[arg(
+            short,
+            long,
+            help = "The file to write the schema to. If not provided, the schema is printed to stdout."
+        )]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -147,6 +192,13 @@ If the number of conversion folders in the project is above the smart_clean_thre
 If the number of conversion folders in the project is above this threshold, old folders will be cleaned, leaving only the threshold amount of folders."#
         )]
         smart_clean_threshold: Option<u32>,
+        #[arg(
+            long,
+            help = r#"Enables the build cache for the project."#,
+            long_help = r#"Enables the build cache for the project.
+If enabled, preprocessing results of unchanged input files are reused and the final processor is skipped if its inputs are identical to the last successful build. The cache is stored in the .tiefdown_cache directory and removed by the clean command."#
+        )]
+        build_cache: Option<bool>,
     },
     #[command(
         about = "Manage the preprocessors of the project.",
@@ -188,13 +240,22 @@ It can also be used for defining default templates for markdown projects."#
         about = "Manage the shared metadata of the project.",
         long_about = r#"Manage the shared metadata of the project.
 This Metadata is shared between all markdown projects.
-When converting, it is merged with the markdown project specific metadata.
-When using the same key for shared and project metadata, the project metadata overrides the shared metadata."#
+When converting, it is merged with the profile, template and markdown project specific metadata, in that order.
+When using the same key in multiple layers, the later layer overrides the earlier one. Nested tables are merged."#
     )]
     SharedMeta {
         #[command(subcommand)]
         command: ManageMetadataCommand,
     },
+    #[command(
+        about = "Inspect the effective metadata of the project.",
+        long_about = r#"Inspect the effective metadata of the project.
+Metadata is resolved in layers, lowest precedence first: shared metadata, profile metadata, template metadata, markdown project metadata and --meta overrides."#
+    )]
+    Meta {
+        #[command(subcommand)]
+        command: MetaCommands,
+    },
     #[command(
         about = "Manage the markdown projects of the project.",
         long_about = r#"Manage the markdown projects of the project.
@@ -233,6 +294,26 @@ If the number of conversion folders in the project is above this threshold, old
 The threshold is set to 5 by default, and is overwritten by the threshold in the manifest."#
     )]
     SmartClean,
+    #[command(
+        about = "Validate the project manifest without converting.",
+        long_about = r#"Validate the project manifest without converting.
+Checks that templates, profiles and markdown projects only reference preprocessors, processors, injections, templates and profiles that exist, that filters, resources and injection files exist, and that multi-file outputs are configured correctly. Unused preprocessors, processors and injections are reported as warnings.
+Fails if any errors are found."#
+    )]
+    Validate {
+        #[arg(long, help = "Also fail if any warnings are found.")]
+        deny_warnings: bool,
+    },
+    #[command(
+        about = "Upgrade the project manifest to the current manifest version.",
+        long_about = r#"Upgrade the project manifest to the current manifest version.
+Older manifests are upgraded in memory whenever they are loaded, but the file is only changed by this command or by commands that edit the manifest.
+The original manifest is backed up to manifest.toml.bak before it is overwritten."#
+    )]
+    UpgradeManifest {
+        #[arg(long, help = "Print the changes to the manifest without writing them.")]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -384,6 +465,15 @@ Can be Json or None.
 YML metadata is always generated, JSON metadata is only needed if used by an external program."#
         )]
         meta_gen_format: Option<CliMetaGenerationFormat>,
+        #[arg(
+            long,
+            help = "Where the titles of the navigation metadata are taken from.",
+            long_help = r#"Where the titles of the navigation metadata are taken from.
+Content uses the first heading of each file, falling back to the file name.
+Path uses the path of each file without its extension.
+A title in the front matter of a file always takes precedence."#
+        )]
+        nav_title_source: Option<CliNavTitleSource>,
     },
     #[command(about = "Remove a template from the project.")]
     Remove,
@@ -560,6 +650,15 @@ Can be Json or None.
 YML metadata is always generated, JSON metadata is only needed if used by an external program."#
         )]
         meta_gen_format: Option<CliMetaGenerationFormat>,
+        #[arg(
+            long,
+            help = "Where the titles of the navigation metadata are taken from.",
+            long_help = r#"Where the titles of the navigation metadata are taken from.
+Content uses the first heading of each file, falling back to the file name.
+Path uses the path of each file without its extension.
+A title in the front matter of a file always takes precedence."#
+        )]
+        nav_title_source: Option<CliNavTitleSource>,
     },
 }
 
@@ -649,6 +748,18 @@ pub(crate) enum ManageMarkdownProjectsCommand {
         output: PathBuf,
         #[arg(long, help = "The default profile to use for converting this project.")]
         default_profile: Option<String>,
+        #[arg(
+            long,
+            num_args = 1..,
+            help = "Gitignore-style patterns of the files to convert. If set, only matching files are converted."
+        )]
+        include: Option<Vec<String>>,
+        #[arg(
+            long,
+            num_args = 1..,
+            help = "Gitignore-style patterns of the files not to convert."
+        )]
+        exclude: Option<Vec<String>>,
     },
     #[command(about = "Update a markdown project in the project.")]
     Update {
@@ -660,6 +771,18 @@ pub(crate) enum ManageMarkdownProjectsCommand {
         output: Option<PathBuf>,
         #[arg(long, help = "The default profile to use for converting this project.")]
         default_profile: Option<String>,
+        #[arg(
+            long,
+            num_args = 1..,
+            help = "Gitignore-style patterns of the files to convert. Replaces the current patterns."
+        )]
+        include: Option<Vec<String>>,
+        #[arg(
+            long,
+            num_args = 1..,
+            help = "Gitignore-style patterns of the files not to convert. Replaces the current patterns."
+        )]
+        exclude: Option<Vec<String>>,
     },
     #[command(
         about = "Manage the metadata of a markdown project.",
@@ -686,6 +809,35 @@ Resources are stored in the markdown folder and copied to the conversion directo
         #[command(subcommand)]
         command: ManageResourcesCommand,
     },
+    #[command(
+        about = "Check the order of the files of a markdown project.",
+        long_about = r#"Check the order of the files of a markdown project.
+Directories with an ordering file (order.toml) are checked for unlisted files, listed files that do not exist and duplicate entries.
+Directories without an ordering file are checked for files without an order number, files with more than one number in their name and files sharing an order number."#
+    )]
+    LintOrder {
+        #[arg(help = "The name of the markdown project to check.")]
+        name: String,
+    },
+    #[command(
+        about = "List, insert and move the chapters of a markdown project.",
+        long_about = r#"List, insert and move the chapters of a markdown project.
+Chapters are the files and directories of a directory sharing an order number, like "Chapter 2 - Usage.md" and "Chapter 2 - Usage/".
+Inserting and moving chapters renumbers the following chapters, keeping leading zeros, and updates the paths of renamed files in the resources and injections of the manifest.
+Directories ordered by an ordering file (order.toml) can only be listed. Edit the ordering file to reorder them."#
+    )]
+    Chapters {
+        #[arg(help = "The name of the markdown project to manage.")]
+        name: String,
+        #[arg(
+            long,
+            short,
+            help = "The directory containing the chapters, relative to the markdown directory. Defaults to the markdown directory."
+        )]
+        directory: Option<PathBuf>,
+        #[command(subcommand)]
+        command: ManageChaptersCommand,
+    },
     #[command(about = "Remove a markdown project from the project.")]
     Remove {
         #[arg(help = "The name of the markdown project to remove.")]
@@ -695,6 +847,37 @@ Resources are stored in the markdown folder and copied to the conversion directo
     List,
 }
 
+#[derive(Subcommand)]
+pub(crate) enum ManageChaptersCommand {
+    #[command(about = "List the chapters in the order they are converted in.")]
+    List,
+    #[command(
+        about = "Insert a chapter and renumber the following chapters.",
+        long_about = r#"Insert a chapter and renumber the following chapters.
+The order number in the chapter name is replaced by the order number of the position.
+If the chapter does not exist, an empty file is created. If it exists in the directory, it is moved to the position."#
+    )]
+    Insert {
+        #[arg(
+            help = "The file name of the chapter, containing an order number, e.g. \"Chapter 4 - The Twist.md\"."
+        )]
+        chapter: String,
+        #[arg(
+            long,
+            short,
+            help = "The position to insert the chapter at, starting at 1. Defaults to the end."
+        )]
+        position: Option<usize>,
+    },
+    #[command(about = "Move a chapter to another position and renumber the chapters in between.")]
+    Move {
+        #[arg(help = "The current position of the chapter, starting at 1.")]
+        from: usize,
+        #[arg(help = "The new position of the chapter, starting at 1.")]
+        to: usize,
+    },
+}
+
 #[derive(Subcommand)]
 pub(crate) enum ManageInjectionsCommand {
     #[command(
@@ -764,16 +947,68 @@ pub(crate) enum ManageMetadataCommand {
     },
     #[command(about = "List the metadata.")]
     List,
-    // #[command(about = "Import metadata from a JSON file.")]
-    // Import {
-    //     #[arg(help = "The path to the JSON file.")]
-    //     path: String,
-    // },
-    // #[command(about = "Export metadata to a JSON file.")]
-    // Export {
-    //     #[arg(help = "The path to the JSON file.")]
-    //     path: String,
-    // },
+    #[command(
+        about = "Import metadata from a JSON, YAML or TOML file.",
+        long_about = r#"Import metadata from a JSON, YAML or TOML file.
+The file must contain a single table (or object) of metadata fields. Null values are skipped.
+Per default, the imported fields are merged into the existing metadata, overriding existing keys and merging nested tables. Use --replace to replace the metadata instead."#
+    )]
+    Import {
+        #[arg(help = "The path to the file to import.")]
+        path: PathBuf,
+        #[arg(
+            short,
+            long,
+            help = "The format of the file. If not provided, the format is determined from the file extension."
+        )]
+        format: Option<CliMetadataFormat>,
+        #[arg(
+            long,
+            help = "Replace the existing metadata instead of merging into it."
+        )]
+        replace: bool,
+    },
+    #[command(
+        about = "Export metadata to a JSON, YAML or TOML file.",
+        long_about = r#"Export metadata to a JSON, YAML or TOML file.
+An existing file is overwritten. Dates are exported as strings to JSON and YAML files."#
+    )]
+    Export {
+        #[arg(help = "The path to the file to write.")]
+        path: PathBuf,
+        #[arg(
+            short,
+            long,
+            help = "The format of the file. If not provided, the format is determined from the file extension."
+        )]
+        format: Option<CliMetadataFormat>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum MetaCommands {
+    #[command(
+        about = "Show the effective metadata of a markdown project.",
+        long_about = r#"Show the effective metadata of a markdown project.
+Prints every resolved key with its value and the layer it was taken from."#
+    )]
+    Resolve {
+        #[arg(short, long, help = "The markdown project to resolve the metadata of.")]
+        markdown_project: String,
+        #[arg(short, long, help = "The template to include the metadata of.")]
+        template: Option<String>,
+        #[arg(
+            short = 'P',
+            long,
+            help = "The profile to include the metadata of. Defaults to the default profile of the markdown project."
+        )]
+        profile: Option<String>,
+        #[arg(
+            long = "meta",
+            help = "Override metadata, in the form key=value. Can be used multiple times."
+        )]
+        metadata_overrides: Vec<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -0,0 +1,74 @@
+use clap::{
+    ValueEnum,
+    builder::{EnumValueParser, ValueParserFactory},
+};
+use color_eyre::eyre::{self, Result, eyre};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+use tiefdownlib::metadata_format::MetadataFormat;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum CliMetadataFormat {
+    Json = 0,
+    Yaml = 1,
+    Toml = 2,
+}
+
+impl FromStr for CliMetadataFormat {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(CliMetadataFormat::Json),
+            "yaml" => Ok(CliMetadataFormat::Yaml),
+            "toml" => Ok(CliMetadataFormat::Toml),
+            _ => Err(eyre!("Invalid metadata format: {}", s)),
+        }
+    }
+}
+
+impl From<CliMetadataFormat> for MetadataFormat {
+    fn from(value: CliMetadataFormat) -> Self {
+        match value {
+            CliMetadataFormat::Json => MetadataFormat::Json,
+            CliMetadataFormat::Yaml => MetadataFormat::Yaml,
+            CliMetadataFormat::Toml => MetadataFormat::Toml,
+        }
+    }
+}
+
+impl From<MetadataFormat> for CliMetadataFormat {
+    fn from(value: MetadataFormat) -> Self {
+        match value {
+            MetadataFormat::Json => CliMetadataFormat::Json,
+            MetadataFormat::Yaml => CliMetadataFormat::Yaml,
+            MetadataFormat::Toml => CliMetadataFormat::Toml,
+        }
+    }
+}
+
+impl CliMetadataFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CliMetadataFormat::Json => "Json",
+            CliMetadataFormat::Yaml => "Yaml",
+            CliMetadataFormat::Toml => "Toml",
+        }
+    }
+}
+
+impl Display for CliMetadataFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl ValueParserFactory for CliMetadataFormat {
+    type Parser = EnumValueParser<Self>;
+
+    fn value_parser() -> Self::Parser {
+        EnumValueParser::new()
+    }
+}
@@ -0,0 +1,53 @@
+use clap::{
+    ValueEnum,
+    builder::{EnumValueParser, ValueParserFactory},
+};
+use std::fmt::{Display, Formatter};
+use tiefdownlib::manifest_model::NavTitleSource;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum CliNavTitleSource {
+    Content = 0,
+    Path = 1,
+}
+
+impl From<CliNavTitleSource> for NavTitleSource {
+    fn from(value: CliNavTitleSource) -> Self {
+        match value {
+            CliNavTitleSource::Content => NavTitleSource::Content,
+            CliNavTitleSource::Path => NavTitleSource::Path,
+        }
+    }
+}
+
+impl From<NavTitleSource> for CliNavTitleSource {
+    fn from(value: NavTitleSource) -> Self {
+        match value {
+            NavTitleSource::Content => CliNavTitleSource::Content,
+            NavTitleSource::Path => CliNavTitleSource::Path,
+        }
+    }
+}
+
+impl CliNavTitleSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CliNavTitleSource::Content => "Content",
+            CliNavTitleSource::Path => "Path",
+        }
+    }
+}
+
+impl Display for CliNavTitleSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl ValueParserFactory for CliNavTitleSource {
+    type Parser = EnumValueParser<Self>;
+
+    fn value_parser() -> Self::Parser {
+        EnumValueParser::new()
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+use log::info;
+use tiefdownlib::conversion::{self, ConversionTask};
+use tiefdownlib::metadata_management;
+
+pub(crate) fn print_conversion_plan(
+    project: Option<PathBuf>,
+    conversion_queue: Vec<ConversionTask>,
+    metadata_overrides: Vec<String>,
+) -> Result<()> {
+    let metadata_overrides = metadata_management::parse_metadata_overrides(&metadata_overrides)?;
+    let plans =
+        conversion::get_conversion_plan(project, conversion_queue, Some(metadata_overrides))?;
+
+    if plans.is_empty() {
+        info!("No conversions planned.");
+        return Ok(());
+    }
+
+    for plan in plans {
+        info!("{} -> {}", plan.markdown_project, plan.template);
+        info!("  Converter: {}", plan.converter);
+
+        info!("  Preprocessors:");
+        for preprocessor in plan.preprocessors {
+            info!(
+                "    {} ({}): {} {}",
+                preprocessor.name,
+                preprocessor.extension_filter.unwrap_or("*".to_string()),
+                preprocessor.cli.unwrap_or("pandoc".to_string()),
+                preprocessor.cli_args.join(" ")
+            );
+        }
+
+        for (kind, injections) in [
+            ("Header", plan.header_injections),
+            ("Body", plan.body_injections),
+            ("Footer", plan.footer_injections),
+        ] {
+            if !injections.is_empty() {
+                info!("  {} injections:", kind);
+                for injection in injections {
+                    info!("    {}", injection.display());
+                }
+            }
+        }
+
+        info!("  Input files:");
+        for input_file in plan.input_files {
+            info!("    {}", input_file.display());
+        }
+
+        info!("  Chunks:");
+        for chunk in plan.chunks {
+            info!("    {} [{}]:", chunk.preprocessor, chunk.extension);
+            for file in chunk.files {
+                info!("      {}", file.display());
+            }
+        }
+
+        info!("  Commands:");
+        for command in plan.commands {
+            match command.working_directory {
+                Some(working_directory) => info!(
+                    "    (in {}) {}",
+                    working_directory.display(),
+                    command.command_line
+                ),
+                None => info!("    {}", command.command_line),
+            }
+        }
+
+        if let Some(output_path) = plan.output_path {
+            info!("  Output: {}", output_path.display());
+        }
+    }
+
+    Ok(())
+}
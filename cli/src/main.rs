@@ -6,14 +6,17 @@ use env_logger::fmt::Formatter;
 use log::Level;
 use std::io::Write;
 use tiefdownlib::{
-    consts, conversion, injections, markdown_project_management, metadata_management,
-    project_management,
+    chapter_management, consts, conversion, injections, manifest_schema,
+    markdown_project_management, metadata_management, project_management,
 };
 
 mod cli;
 mod cli_meta_generation_feature;
 mod cli_meta_generation_format;
+mod cli_metadata_format;
+mod cli_nav_title_source;
 mod cli_template_type;
+mod conversion_commands;
 mod project_commands;
 
 pub(crate) struct CustomLoggingStyle;
@@ -65,6 +68,10 @@ fn main() -> Result<()> {
             templates,
             profile,
             markdown_projects,
+            metadata_overrides,
+            watch,
+            jobs,
+            dry_run,
         } => {
             if profile.is_some() && templates.is_some() {
                 return Err(eyre!("Cannot specify both templates and a profile."));
@@ -77,7 +84,28 @@ fn main() -> Result<()> {
                 markdown_projects,
             )?;
 
-            conversion::convert(project, conversion_queue)?
+            if dry_run {
+                conversion_commands::print_conversion_plan(
+                    project,
+                    conversion_queue,
+                    metadata_overrides,
+                )?
+            } else {
+                let metadata_overrides =
+                    metadata_management::parse_metadata_overrides(&metadata_overrides)?;
+
+                if watch {
+                    conversion::watch(
+                        project,
+                        conversion_queue,
+                        Some(metadata_overrides),
+                        None,
+                        jobs,
+                    )?
+                } else {
+                    conversion::convert(project, conversion_queue, Some(metadata_overrides), jobs)?;
+                }
+            }
         }
         Commands::Init {
             project,
@@ -115,6 +143,7 @@ fn main() -> Result<()> {
                     nav_meta_gen_output,
                     metadata_meta_gen_output,
                     meta_gen_format,
+                    nav_title_source,
                 } => project_management::add_template(
                     project,
                     template,
@@ -134,6 +163,7 @@ fn main() -> Result<()> {
                     nav_meta_gen_output,
                     metadata_meta_gen_output,
                     meta_gen_format.map(|t| t.into()),
+                    nav_title_source.map(|t| t.into()),
                 )?,
                 TemplatesCommands::Remove => {
                     project_management::remove_template(project, template)?
@@ -159,6 +189,7 @@ fn main() -> Result<()> {
                     nav_meta_gen_output,
                     metadata_meta_gen_output,
                     meta_gen_format,
+                    nav_title_source,
                 } => {
                     if filters.is_some() && (add_filters.is_some() || remove_filters.is_some()) {
                         return Err(eyre!("Cannot specify both filters or add/remove filters."));
@@ -195,13 +226,20 @@ fn main() -> Result<()> {
                         nav_meta_gen_output,
                         metadata_meta_gen_output,
                         meta_gen_format.map(|t| t.into()),
+                        nav_title_source.map(|t| t.into()),
                     )?
                 }
             },
             ProjectCommands::UpdateSettings {
                 smart_clean,
                 smart_clean_threshold,
-            } => project_management::update_settings(project, smart_clean, smart_clean_threshold)?,
+                build_cache,
+            } => project_management::update_settings(
+                project,
+                smart_clean,
+                smart_clean_threshold,
+                build_cache,
+            )?,
             ProjectCommands::PreProcessors { command } => match command {
                 PreProcessorsCommands::Add {
                     name,
@@ -255,6 +293,33 @@ fn main() -> Result<()> {
                     metadata_management::remove_metadata(project, key)?
                 }
                 ManageMetadataCommand::List => project_commands::list_shared_metadata(project)?,
+                ManageMetadataCommand::Import {
+                    path,
+                    format,
+                    replace,
+                } => metadata_management::import_metadata(
+                    project,
+                    path,
+                    format.map(|f| f.into()),
+                    replace,
+                )?,
+                ManageMetadataCommand::Export { path, format } => {
+                    metadata_management::export_metadata(project, path, format.map(|f| f.into()))?
+                }
+            },
+            ProjectCommands::Meta { command } => match command {
+                MetaCommands::Resolve {
+                    markdown_project,
+                    template,
+                    profile,
+                    metadata_overrides,
+                } => project_commands::print_resolved_metadata(
+                    project,
+                    markdown_project,
+                    template,
+                    profile,
+                    metadata_overrides,
+                )?,
             },
             ProjectCommands::Markdown { command } => match command {
                 ManageMarkdownProjectsCommand::Add {
@@ -262,12 +327,16 @@ fn main() -> Result<()> {
                     path,
                     output,
                     default_profile,
+                    include,
+                    exclude,
                 } => markdown_project_management::add_markdown_project(
                     project,
                     name,
                     path,
                     output,
                     default_profile,
+                    include,
+                    exclude,
                 )?,
                 ManageMarkdownProjectsCommand::Remove { name } => {
                     markdown_project_management::remove_markdown_project(project, name)?
@@ -277,12 +346,16 @@ fn main() -> Result<()> {
                     path,
                     output,
                     default_profile,
+                    include,
+                    exclude,
                 } => markdown_project_management::update_markdown_project(
                     project,
                     name,
                     path,
                     output,
                     default_profile,
+                    include,
+                    exclude,
                 )?,
                 ManageMarkdownProjectsCommand::Meta { name, command } => match command {
                     ManageMetadataCommand::Set { key, value } => {
@@ -294,6 +367,25 @@ fn main() -> Result<()> {
                     ManageMetadataCommand::List => {
                         project_commands::list_markdown_project_metadata(project, name)?
                     }
+                    ManageMetadataCommand::Import {
+                        path,
+                        format,
+                        replace,
+                    } => markdown_project_management::import_metadata(
+                        project,
+                        name,
+                        path,
+                        format.map(|f| f.into()),
+                        replace,
+                    )?,
+                    ManageMetadataCommand::Export { path, format } => {
+                        markdown_project_management::export_metadata(
+                            project,
+                            name,
+                            path,
+                            format.map(|f| f.into()),
+                        )?
+                    }
                 },
                 ManageMarkdownProjectsCommand::Resources { name, command } => match command {
                     ManageResourcesCommand::Add { paths } => {
@@ -306,6 +398,26 @@ fn main() -> Result<()> {
                         project_commands::list_resources(project, name)?
                     }
                 },
+                ManageMarkdownProjectsCommand::LintOrder { name } => {
+                    project_commands::lint_file_order(project, name)?
+                }
+                ManageMarkdownProjectsCommand::Chapters {
+                    name,
+                    directory,
+                    command,
+                } => match command {
+                    ManageChaptersCommand::List => {
+                        project_commands::list_chapters(project, name, directory)?
+                    }
+                    ManageChaptersCommand::Insert { chapter, position } => {
+                        chapter_management::insert_chapter(
+                            project, &name, directory, chapter, position,
+                        )?;
+                    }
+                    ManageChaptersCommand::Move { from, to } => {
+                        chapter_management::move_chapter(project, &name, directory, from, to)?
+                    }
+                },
                 ManageMarkdownProjectsCommand::List => {
                     project_commands::list_markdown_projects(project)?
                 }
@@ -313,10 +425,21 @@ fn main() -> Result<()> {
             ProjectCommands::ListTemplates => project_commands::list_templates(project)?,
             ProjectCommands::Clean => project_management::clean(project)?,
             ProjectCommands::SmartClean => project_management::smart_clean(project)?,
+            ProjectCommands::Validate { deny_warnings } => {
+                project_commands::validate(project, deny_warnings)?
+            }
+            ProjectCommands::UpgradeManifest { dry_run } => {
+                project_commands::upgrade_manifest(project, dry_run)?
+            }
         },
         Commands::CheckDependencies => {
             project_management::check_dependencies(vec!["pandoc", "xelatex", "typst"])?
         }
+        Commands::Schema { output } => match output {
+            Some(output) => manifest_schema::write_manifest_schema(&output)?,
+            // Printed directly, as the schema should not be mixed with log output.
+            None => print!("{}", manifest_schema::get_manifest_schema()?),
+        },
     }
 
     Ok(())
@@ -1,9 +1,11 @@
 use std::path::PathBuf;
 
-use color_eyre::eyre::Result;
-use log::info;
+use color_eyre::eyre::{Result, eyre};
+use log::{error, info, warn};
 use tiefdownlib::{
+    chapter_management::{self, Chapter},
     injections, markdown_project_management, metadata_management, project_management,
+    validation::{self, Severity},
 };
 
 pub(crate) fn list_preprocessors(project: Option<PathBuf>) -> Result<()> {
@@ -71,6 +73,33 @@ pub(crate) fn list_shared_metadata(project: Option<PathBuf>) -> Result<()> {
 
     Ok(())
 }
+pub(crate) fn print_resolved_metadata(
+    project: Option<PathBuf>,
+    markdown_project: String,
+    template: Option<String>,
+    profile: Option<String>,
+    metadata_overrides: Vec<String>,
+) -> Result<()> {
+    let metadata_overrides = metadata_management::parse_metadata_overrides(&metadata_overrides)?;
+    let fields = metadata_management::resolve_metadata(
+        project,
+        markdown_project,
+        template,
+        profile,
+        Some(metadata_overrides),
+    )?;
+
+    if fields.is_empty() {
+        info!("No metadata fields found.");
+        return Ok(());
+    }
+
+    for field in fields {
+        info!("{} = {} ({})", field.key, field.value, field.layer);
+    }
+
+    Ok(())
+}
 pub(crate) fn list_markdown_project_metadata(
     project: Option<PathBuf>,
     markdown_project_name: String,
@@ -105,6 +134,74 @@ pub(crate) fn list_resources(
 
     Ok(())
 }
+pub(crate) fn lint_file_order(
+    project: Option<PathBuf>,
+    markdown_project_name: String,
+) -> Result<()> {
+    let warnings = markdown_project_management::lint_file_order(project, &markdown_project_name)?;
+
+    if warnings.is_empty() {
+        info!(
+            "No file order issues found for project {}.",
+            markdown_project_name
+        );
+        return Ok(());
+    }
+
+    for warning in warnings {
+        warn!("{}", warning);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn list_chapters(
+    project: Option<PathBuf>,
+    markdown_project_name: String,
+    directory: Option<PathBuf>,
+) -> Result<()> {
+    let chapters = chapter_management::get_chapters(project, &markdown_project_name, directory)?;
+
+    if chapters.is_empty() {
+        info!("No chapters found.");
+        return Ok(());
+    }
+
+    print_chapters(&chapters, 0);
+
+    Ok(())
+}
+
+fn print_chapters(chapters: &[Chapter], depth: usize) {
+    for chapter in chapters {
+        let names = chapter
+            .files
+            .iter()
+            .map(|file| {
+                file.file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .chain(chapter.directories.iter().map(|directory| {
+                format!(
+                    "{}/",
+                    directory.file_name().unwrap_or_default().to_string_lossy()
+                )
+            }))
+            .collect::<Vec<_>>();
+
+        info!(
+            "{}{}. {}",
+            "  ".repeat(depth),
+            chapter.position,
+            names.join(", ")
+        );
+
+        print_chapters(&chapter.children, depth + 1);
+    }
+}
+
 pub(crate) fn list_markdown_projects(project: Option<PathBuf>) -> Result<()> {
     let markdown_projects = markdown_project_management::get_markdown_projects(project)?;
 
@@ -176,3 +273,57 @@ pub(crate) fn list_injections(project: Option<PathBuf>) -> Result<()> {
 
     Ok(())
 }
+
+pub(crate) fn upgrade_manifest(project: Option<PathBuf>, dry_run: bool) -> Result<()> {
+    let upgrade = project_management::upgrade_manifest_file(project, dry_run)?;
+
+    if upgrade.from_version == upgrade.to_version {
+        info!("The manifest is already at version {}.", upgrade.to_version);
+        return Ok(());
+    }
+
+    info!("{}", upgrade.diff.trim_end());
+
+    if dry_run {
+        info!(
+            "Dry run: the manifest would be upgraded from version {} to {}.",
+            upgrade.from_version, upgrade.to_version
+        );
+    } else {
+        info!(
+            "Upgraded the manifest from version {} to {}.",
+            upgrade.from_version, upgrade.to_version
+        );
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate(project: Option<PathBuf>, deny_warnings: bool) -> Result<()> {
+    let diagnostics = validation::validate(project)?;
+
+    for diagnostic in &diagnostics {
+        match diagnostic.severity {
+            Severity::Error => error!("{}", diagnostic),
+            Severity::Warning => warn!("{}", diagnostic),
+        }
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+
+    if errors > 0 || (deny_warnings && warnings > 0) {
+        return Err(eyre!(
+            "Validation failed with {} errors and {} warnings.",
+            errors,
+            warnings
+        ));
+    }
+
+    info!("The manifest is valid with {} warnings.", warnings);
+
+    Ok(())
+}
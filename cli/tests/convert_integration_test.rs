@@ -670,6 +670,102 @@ fn test_convert_multiple_markdown_projects() {
     assert!(output_pdf_4.exists(), "Output PDF should exist");
 }
 
+#[rstest]
+#[case("2")]
+#[case("4")]
+fn test_convert_multiple_markdown_projects_in_parallel(#[case] jobs: &str) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path(), vec![]);
+
+    add_tex_template(&project_path, "Template 1", "templ1.tex", None);
+    add_tex_template(&project_path, "Template 2", "templ2.tex", None);
+
+    add_markdown_project(&project_path, "Project 1", "markdown_dir1", "out1");
+    add_markdown_project(&project_path, "Project 2", "markdown_dir2", "out2");
+
+    create_input_file(
+        &project_path.join("markdown_dir1"),
+        "test.md",
+        VALID_MARKDOWN_CONTENT,
+    );
+    create_input_file(
+        &project_path.join("markdown_dir2"),
+        "test.md",
+        VALID_MARKDOWN_CONTENT,
+    );
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+
+    let output = cmd
+        .current_dir(&project_path)
+        .arg("convert")
+        .arg("--jobs")
+        .arg(jobs)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8_lossy(&output);
+
+    let status_position = output
+        .find("Conversion status:")
+        .expect("Conversion status should be printed");
+    let status = &output[status_position..];
+
+    let expected_status = [
+        "Project 1 -> Template 1",
+        "Project 1 -> Template 2",
+        "Project 2 -> Template 1",
+        "Project 2 -> Template 2",
+    ];
+    let positions: Vec<usize> = expected_status
+        .iter()
+        .map(|s| status.find(s).expect("Task status should be printed"))
+        .collect();
+    assert!(
+        positions.windows(2).all(|w| w[0] < w[1]),
+        "Task status should be printed in queue order"
+    );
+
+    for output_pdf in [
+        project_path.join("out1").join("Template 1.pdf"),
+        project_path.join("out1").join("Template 2.pdf"),
+        project_path.join("out2").join("Template 1.pdf"),
+        project_path.join("out2").join("Template 2.pdf"),
+    ] {
+        assert!(
+            output_pdf.exists(),
+            "Output PDF {} should exist",
+            output_pdf.display()
+        );
+    }
+}
+
+#[rstest]
+fn test_convert_zero_jobs() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path(), vec![]);
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+
+    let output = cmd
+        .current_dir(&project_path)
+        .arg("convert")
+        .arg("--jobs")
+        .arg("0")
+        .assert()
+        .failure()
+        .get_output()
+        .stderr
+        .clone();
+
+    assert_contains!(
+        String::from_utf8_lossy(&output),
+        "The number of jobs must be at least 1."
+    );
+}
+
 fn add_markdown_project(
     project_path: &Path,
     project_name: &str,
@@ -729,3 +825,74 @@ Basic test content
 Basic test content 02"#
     );
 }
+
+#[rstest]
+fn test_convert_dry_run() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let project_path = create_empty_project(temp_dir.path(), vec![]);
+
+    add_tex_template(&project_path, "Template 1", "templ1.tex", None);
+
+    create_input_file(&project_path, "Chapter 1.md", VALID_MARKDOWN_CONTENT);
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    let output = cmd
+        .current_dir(&project_path)
+        .arg("convert")
+        .arg("--dry-run")
+        .output()
+        .expect("Failed to run command");
+
+    assert!(output.status.success(), "Dry run should succeed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_contains!(stdout, "Markdown -> Template 1");
+    assert_contains!(stdout, "Converter: Tex");
+    assert_contains!(
+        stdout,
+        "pandoc -t latex --lua-filter .tiefdown_conditional_content.lua -M tiefdown-markdown-project=Markdown -M 'tiefdown-template=Template 1' -M tiefdown-template-type=Tex 'Template 1_convdir/Chapter 1 - Introduction.md' 'Template 1_convdir/Chapter 1.md'"
+    );
+    assert_contains!(
+        stdout,
+        "xelatex -interaction=nonstopmode -synctex=1 templ1.tex"
+    );
+    assert_contains!(stdout, "Output: Template 1.pdf");
+
+    let output_pdf = project_path.join("Template 1.pdf");
+    assert!(!output_pdf.exists(), "Output PDF should not exist");
+}
+
+#[rstest]
+fn test_convert_writes_build_report() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let project_path = create_empty_project(temp_dir.path(), vec![]);
+
+    add_epub_template(&project_path, "Epub Template", "epub_template", None);
+
+    create_input_file(&project_path, "Chapter 1.md", VALID_MARKDOWN_CONTENT);
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("convert")
+        .assert()
+        .success();
+
+    let build_report_path = fs::read_dir(&project_path)
+        .expect("Failed to read project directory")
+        .map(|entry| entry.expect("Failed to read entry").path())
+        .map(|path| path.join("build_report.json"))
+        .find(|path| path.exists())
+        .expect("Build report should exist");
+
+    let build_report = fs::read_to_string(build_report_path).expect("Failed to read build report");
+
+    assert_contains!(build_report, r#""success": true"#);
+    assert_contains!(build_report, r#""template": "Epub Template""#);
+    assert_contains!(build_report, r#""preprocessor": "native_pandoc""#);
+    assert_contains!(build_report, r#""exit_code": 0"#);
+    assert_contains!(build_report, r#""path": "Epub Template.epub""#);
+    assert_contains!(build_report, r#""sha256": ""#);
+}
@@ -0,0 +1,110 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+#[path = "assertions.rs"]
+#[macro_use]
+mod assertions;
+
+const TEMPLATE_FRAGMENT: &str = r#"# Templates for print
+[[templates]]
+name = "Print"
+template_type = "Tex"
+template_file = "print.tex"
+output = "print.pdf"
+"#;
+
+fn create_project_with_fragment(temp_dir: &Path) -> PathBuf {
+    let project_path = temp_dir.join("project");
+    fs::create_dir(&project_path).expect("Failed to create project directory");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("init")
+        .arg("-n")
+        .assert()
+        .success();
+
+    let manifest_path = project_path.join("manifest.toml");
+    let manifest = fs::read_to_string(&manifest_path).expect("Failed to read manifest");
+    fs::write(
+        &manifest_path,
+        manifest.replacen(
+            "version = 6\n",
+            "version = 6\ninclude = [\"templates/*.toml\"]\n",
+            1,
+        ),
+    )
+    .expect("Failed to write manifest");
+
+    fs::create_dir_all(project_path.join("templates")).expect("Failed to create directory");
+    fs::write(project_path.join("templates/print.toml"), TEMPLATE_FRAGMENT)
+        .expect("Failed to write fragment");
+
+    project_path
+}
+
+#[rstest]
+fn test_list_templates_includes_fragments() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project_with_fragment(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("list-templates")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Print"));
+}
+
+#[rstest]
+fn test_update_template_writes_to_fragment() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project_with_fragment(temp_dir.path());
+    let manifest_before =
+        fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("templates")
+        .arg("Print")
+        .arg("update")
+        .arg("--output")
+        .arg("book.pdf")
+        .assert()
+        .success();
+
+    let fragment = fs::read_to_string(project_path.join("templates/print.toml"))
+        .expect("Failed to read fragment");
+    assert_contains!(fragment, "# Templates for print");
+    assert_contains!(fragment, "output = \"book.pdf\"");
+
+    let manifest =
+        fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest");
+    assert_eq!(manifest, manifest_before);
+}
+
+#[rstest]
+fn test_duplicate_template_in_fragment_fails() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project_with_fragment(temp_dir.path());
+    fs::write(project_path.join("templates/other.toml"), TEMPLATE_FRAGMENT)
+        .expect("Failed to write fragment");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("list-templates")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Template 'Print' is defined in both 'templates/other.toml' and 'templates/print.toml'.",
+        ));
+}
@@ -130,3 +130,34 @@ fn test_markdown_add_fails_to_overwrite() {
             "Markdown project with name 'first' already exists.",
         ));
 }
+
+#[rstest]
+fn test_markdown_add_with_exclude() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("add")
+        .arg("main")
+        .arg("Markdown")
+        .arg(".")
+        .arg("--exclude")
+        .arg(".DS_Store")
+        .assert()
+        .success();
+
+    let manifest_path = project_path.join("manifest.toml");
+    let manifest_content = fs::read_to_string(manifest_path).expect("Failed to read manifest file");
+
+    assert_contains!(
+        manifest_content,
+        r#"[[markdown_projects]]
+name = "main"
+path = "Markdown"
+output = "."
+exclude = [".DS_Store"]"#
+    );
+}
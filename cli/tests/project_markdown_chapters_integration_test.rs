@@ -0,0 +1,135 @@
+use assert_cmd::Command;
+use predicates::prelude::predicate;
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+#[path = "assertions.rs"]
+#[macro_use]
+mod assertions;
+
+fn create_project(temp_dir: &Path) -> PathBuf {
+    let project_path = temp_dir.join("project");
+    fs::create_dir(&project_path).expect("Failed to create project directory");
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("init")
+        .arg("-t")
+        .arg("template.tex")
+        .assert()
+        .success();
+
+    let markdown_dir = project_path.join("Markdown");
+    fs::create_dir(markdown_dir.join("Chapter 2 - Journey")).expect("Failed to create directory");
+    fs::write(markdown_dir.join("Chapter 2 - Journey.md"), "# Journey\n")
+        .expect("Failed to write file");
+    fs::write(
+        markdown_dir.join("Chapter 2 - Journey/Part 1 - Road.md"),
+        "# Road\n",
+    )
+    .expect("Failed to write file");
+
+    project_path
+}
+
+#[rstest]
+fn test_list_chapters() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("chapters")
+        .arg("Markdown")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1. Chapter 1 - Introduction.md\n2. Chapter 2 - Journey.md, Chapter 2 - Journey/\n  1. Part 1 - Road.md\n",
+        ));
+}
+
+#[rstest]
+fn test_insert_chapter() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("chapters")
+        .arg("Markdown")
+        .arg("insert")
+        .arg("Chapter 1 - Prologue.md")
+        .arg("--position")
+        .arg("2")
+        .assert()
+        .success();
+
+    let markdown_dir = project_path.join("Markdown");
+    assert!(markdown_dir.join("Chapter 1 - Introduction.md").exists());
+    assert!(markdown_dir.join("Chapter 2 - Prologue.md").exists());
+    assert!(markdown_dir.join("Chapter 3 - Journey.md").exists());
+    assert!(
+        markdown_dir
+            .join("Chapter 3 - Journey/Part 1 - Road.md")
+            .exists()
+    );
+}
+
+#[rstest]
+fn test_move_chapter() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("chapters")
+        .arg("Markdown")
+        .arg("move")
+        .arg("2")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Renamed 'Chapter 2 - Journey' to 'Chapter 1 - Journey'.",
+        ));
+
+    let markdown_dir = project_path.join("Markdown");
+    assert!(markdown_dir.join("Chapter 1 - Journey.md").exists());
+    assert!(
+        markdown_dir
+            .join("Chapter 1 - Journey/Part 1 - Road.md")
+            .exists()
+    );
+    assert!(markdown_dir.join("Chapter 2 - Introduction.md").exists());
+}
+
+#[rstest]
+fn test_move_chapter_out_of_range() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("chapters")
+        .arg("Markdown")
+        .arg("move")
+        .arg("1")
+        .arg("3")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Position 3 is out of range. Expected a position between 1 and 2.",
+        ));
+}
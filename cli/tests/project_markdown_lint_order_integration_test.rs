@@ -0,0 +1,119 @@
+use assert_cmd::Command;
+use predicates::prelude::{PredicateBooleanExt, predicate};
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+#[path = "assertions.rs"]
+#[macro_use]
+mod assertions;
+
+fn create_project(temp_dir: &Path) -> PathBuf {
+    let project_path = temp_dir.join("project");
+    fs::create_dir(&project_path).expect("Failed to create project directory");
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("init")
+        .arg("-t")
+        .arg("template.tex")
+        .assert()
+        .success();
+
+    project_path
+}
+
+#[rstest]
+fn test_lint_order_without_issues() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("lint-order")
+        .arg("Markdown")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No file order issues found for project Markdown.",
+        ));
+}
+
+#[rstest]
+fn test_lint_order_order_numbers() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+    let markdown_dir = project_path.join("Markdown");
+    fs::write(markdown_dir.join("Chapter 1 - Again.md"), "# Again\n")
+        .expect("Failed to write file");
+    fs::write(markdown_dir.join("Epilogue.md"), "# Epilogue\n").expect("Failed to write file");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("lint-order")
+        .arg("Markdown")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("'Epilogue.md' has no order number and is sorted as 0.").and(
+                predicate::str::contains(
+                    "'Chapter 1 - Again.md', 'Chapter 1 - Introduction.md' share the order number 1.",
+                ),
+            ),
+        );
+}
+
+#[rstest]
+fn test_lint_order_order_file() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+    let markdown_dir = project_path.join("Markdown");
+    fs::write(markdown_dir.join("Epilogue.md"), "# Epilogue\n").expect("Failed to write file");
+    fs::write(
+        markdown_dir.join("order.toml"),
+        "order = [\"Epilogue.md\", \"Missing.md\"]\n",
+    )
+    .expect("Failed to write ordering file");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("lint-order")
+        .arg("Markdown")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(
+                "'Missing.md' is listed in the ordering file 'order.toml', but does not exist.",
+            )
+            .and(predicate::str::contains(
+                "'Chapter 1 - Introduction.md' is not listed in the ordering file 'order.toml'.",
+            ))
+            .and(predicate::str::contains("Epilogue.md' has no order number").not()),
+        );
+}
+
+#[rstest]
+fn test_lint_order_missing_markdown_project() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("lint-order")
+        .arg("Missing")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Markdown project with name 'Missing' does not exist.",
+        ));
+}
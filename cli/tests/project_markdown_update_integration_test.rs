@@ -167,3 +167,38 @@ output = "output"
 default_profile = "profile""#
     );
 }
+
+#[rstest]
+fn test_markdown_update_include_exclude() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    create_markdown_project("name", "input", "output", &project_path);
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("update")
+        .arg("name")
+        .arg("--include")
+        .arg("*.md")
+        .arg("--exclude")
+        .arg("notes/")
+        .arg("*.swp")
+        .assert()
+        .success();
+
+    let manifest_path = project_path.join("manifest.toml");
+    let manifest_content = fs::read_to_string(manifest_path).expect("Failed to read manifest file");
+
+    assert_contains!(
+        manifest_content,
+        r#"[[markdown_projects]]
+name = "name"
+path = "input"
+output = "output"
+include = ["*.md"]
+exclude = ["notes/", "*.swp"]"#
+    );
+}
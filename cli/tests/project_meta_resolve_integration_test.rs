@@ -0,0 +1,211 @@
+use assert_cmd::Command;
+use predicates::prelude::{PredicateBooleanExt, predicate};
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+#[path = "assertions.rs"]
+#[macro_use]
+mod assertions;
+
+fn create_project(temp_dir: &Path) -> PathBuf {
+    let project_path = temp_dir.join("project");
+    fs::create_dir(&project_path).expect("Failed to create project directory");
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("init")
+        .arg("-t")
+        .arg("template.tex")
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("profiles")
+        .arg("add")
+        .arg("print")
+        .arg("template.tex")
+        .assert()
+        .success();
+
+    let manifest_path = project_path.join("manifest.toml");
+    let manifest = fs::read_to_string(&manifest_path).expect("Failed to read manifest");
+    let manifest = manifest
+        .replace(
+            "template_type = \"Tex\"\n",
+            "template_type = \"Tex\"\nmetadata_fields = { series = { number = 2 } }\n",
+        )
+        .replace(
+            "templates = [\"template.tex\"]\n",
+            "templates = [\"template.tex\"]\nmetadata_fields = { title = \"Profile\", series = { name = \"Dreams\", number = 1 } }\n",
+        );
+    fs::write(&manifest_path, manifest).expect("Failed to write manifest");
+
+    project_path
+}
+
+fn set_shared_metadata(project_path: &Path, key: &str, value: &str) {
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("set")
+        .arg(key)
+        .arg(value)
+        .assert()
+        .success();
+}
+
+fn set_markdown_project_metadata(project_path: &Path, key: &str, value: &str) {
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("meta")
+        .arg("Markdown")
+        .arg("set")
+        .arg(key)
+        .arg(value)
+        .assert()
+        .success();
+}
+
+#[rstest]
+fn test_resolve_metadata() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    set_shared_metadata(&project_path, "title", "Shared");
+    set_shared_metadata(&project_path, "author", "Shared");
+    set_shared_metadata(&project_path, "publisher", "Shared");
+    set_markdown_project_metadata(&project_path, "author", "Project");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("meta")
+        .arg("resolve")
+        .arg("-m")
+        .arg("Markdown")
+        .arg("-t")
+        .arg("template.tex")
+        .arg("-P")
+        .arg("print")
+        .arg("--meta")
+        .arg("publisher=Override")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("author = \"Project\" (markdown project 'Markdown')")
+                .and(predicate::str::contains(
+                    "publisher = \"Override\" (override)",
+                ))
+                .and(predicate::str::contains(
+                    "series.name = \"Dreams\" (profile 'print')",
+                ))
+                .and(predicate::str::contains(
+                    "series.number = 2 (template 'template.tex')",
+                ))
+                .and(predicate::str::contains(
+                    "title = \"Profile\" (profile 'print')",
+                )),
+        );
+}
+
+#[rstest]
+fn test_resolve_metadata_without_profile() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    set_shared_metadata(&project_path, "title", "Shared");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("meta")
+        .arg("resolve")
+        .arg("-m")
+        .arg("Markdown")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("title = \"Shared\" (shared metadata)")
+                .and(predicate::str::contains("series").not()),
+        );
+}
+
+#[rstest]
+fn test_resolve_metadata_default_markdown_project() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let manifest_path = project_path.join("manifest.toml");
+    let manifest = fs::read_to_string(&manifest_path).expect("Failed to read manifest");
+    fs::write(
+        &manifest_path,
+        manifest.replace(
+            "[[markdown_projects]]\nname = \"Markdown\"\npath = \"Markdown\"\noutput = \".\"\n",
+            "",
+        ),
+    )
+    .expect("Failed to write manifest");
+
+    set_shared_metadata(&project_path, "title", "Shared");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("meta")
+        .arg("resolve")
+        .arg("-m")
+        .arg("Default")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "title = \"Shared\" (shared metadata)",
+        ));
+}
+
+#[rstest]
+fn test_resolve_metadata_missing_profile() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("meta")
+        .arg("resolve")
+        .arg("-m")
+        .arg("Markdown")
+        .arg("-P")
+        .arg("missing")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Profile 'missing' not found."));
+}
+
+#[rstest]
+fn test_resolve_metadata_invalid_override() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("meta")
+        .arg("resolve")
+        .arg("-m")
+        .arg("Markdown")
+        .arg("--meta")
+        .arg("title")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid metadata override 'title'. Expected the form key=value.",
+        ));
+}
@@ -116,6 +116,10 @@ fn test_remove_preprocessor_does_not_exist() {
 
     add_preprocessor(&project_path, "My funny preprocessor");
 
+    let manifest_path = project_path.join("manifest.toml");
+    let original_manifest_content =
+        fs::read_to_string(&manifest_path).expect("Failed to read manifest file");
+
     let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
     cmd.current_dir(&project_path)
         .arg("project")
@@ -128,14 +132,7 @@ fn test_remove_preprocessor_does_not_exist() {
             "Preprocessor with name 'Remove this' does not exist.",
         ));
 
-    let manifest_path = project_path.join("manifest.toml");
-    assert!(manifest_path.exists(), "Manifest file should exist");
     let manifest_content = fs::read_to_string(manifest_path).expect("Failed to read manifest file");
 
-    assert_contains!(
-        manifest_content,
-        r#"[[custom_processors.preprocessors]]
-cli_args = ["--listings"]
-name = "My funny preprocessor""#
-    );
+    assert_eq!(manifest_content, original_manifest_content);
 }
@@ -79,3 +79,39 @@ templates = []
 "#
     );
 }
+
+#[rstest]
+fn test_add_profile_keeps_manifest_formatting() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    let manifest_path = project_path.join("manifest.toml");
+    let manifest_content =
+        fs::read_to_string(&manifest_path).expect("Failed to read manifest file");
+    let manifest_content = format!(
+        "# Maintained by hand, please keep the comments.\n{}",
+        manifest_content.replace("version = 6", "version = 6 # the manifest version")
+    );
+    fs::write(&manifest_path, &manifest_content).expect("Failed to write manifest file");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("profiles")
+        .arg("add")
+        .arg("My funny profile")
+        .arg("Template 1")
+        .assert()
+        .success();
+
+    let updated_manifest_content =
+        fs::read_to_string(manifest_path).expect("Failed to read manifest file");
+
+    assert_eq!(
+        updated_manifest_content,
+        format!(
+            "{}\n[[profiles]]\nname = \"My funny profile\"\ntemplates = [\"Template 1\"]\n",
+            manifest_content
+        )
+    );
+}
@@ -0,0 +1,100 @@
+use assert_cmd::Command;
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+#[path = "assertions.rs"]
+#[macro_use]
+mod assertions;
+
+fn create_empty_project(temp_dir: &Path) -> PathBuf {
+    let project_path = temp_dir.join("project");
+    fs::create_dir(&project_path).expect("Failed to create project directory");
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("init")
+        .arg("-n")
+        .assert()
+        .success();
+
+    fs::create_dir_all(project_path.join("template")).expect("Failed to create template directory");
+
+    project_path
+}
+
+fn add_metadata(project_path: &Path, key: &str, value: &str) {
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("set")
+        .arg(key)
+        .arg(value)
+        .assert()
+        .success();
+}
+
+#[rstest]
+#[case("metadata.json", "\"author\": \"John Doe\"")]
+#[case("metadata.yml", "author: John Doe")]
+#[case("metadata.toml", "author = \"John Doe\"")]
+fn test_shared_meta_export(#[case] file_name: &str, #[case] expected: &str) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    add_metadata(&project_path, "author", "John Doe");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("export")
+        .arg(file_name)
+        .assert()
+        .success();
+
+    let exported_content =
+        fs::read_to_string(project_path.join(file_name)).expect("Failed to read exported file");
+
+    assert_contains!(exported_content, expected);
+}
+
+#[rstest]
+fn test_shared_meta_export_round_trip() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    add_metadata(&project_path, "author", "John Doe");
+    add_metadata(&project_path, "title", "My Title");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("export")
+        .arg("metadata.json")
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("markdown")
+        .arg("meta")
+        .arg("Markdown")
+        .arg("import")
+        .arg("metadata.json")
+        .assert()
+        .success();
+
+    let manifest_content =
+        fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest");
+
+    assert_contains!(
+        manifest_content,
+        "[markdown_projects.metadata_fields]\nauthor = \"John Doe\"\ntitle = \"My Title\""
+    );
+}
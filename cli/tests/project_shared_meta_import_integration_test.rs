@@ -0,0 +1,178 @@
+use assert_cmd::Command;
+use predicates::prelude::predicate;
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+#[path = "assertions.rs"]
+#[macro_use]
+mod assertions;
+
+fn create_empty_project(temp_dir: &Path) -> PathBuf {
+    let project_path = temp_dir.join("project");
+    fs::create_dir(&project_path).expect("Failed to create project directory");
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("init")
+        .arg("-n")
+        .assert()
+        .success();
+
+    fs::create_dir_all(project_path.join("template")).expect("Failed to create template directory");
+
+    project_path
+}
+
+fn add_metadata(project_path: &Path, key: &str, value: &str) {
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("set")
+        .arg(key)
+        .arg(value)
+        .assert()
+        .success();
+}
+
+#[rstest]
+#[case(
+    "metadata.json",
+    r#"{"title": "Dream", "edition": 2, "series": {"name": "Dreams"}, "note": null}"#
+)]
+#[case(
+    "metadata.yaml",
+    "title: Dream\nedition: 2\nseries:\n  name: Dreams\nnote: ~\n"
+)]
+#[case(
+    "metadata.toml",
+    "title = \"Dream\"\nedition = 2\n\n[series]\nname = \"Dreams\"\n"
+)]
+fn test_shared_meta_import(#[case] file_name: &str, #[case] content: &str) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    add_metadata(&project_path, "author", "John Doe");
+    add_metadata(&project_path, "title", "Old Title");
+
+    fs::write(project_path.join(file_name), content).expect("Failed to write metadata file");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("import")
+        .arg(file_name)
+        .assert()
+        .success();
+
+    let manifest_content =
+        fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest");
+
+    assert_contains!(manifest_content, "author = \"John Doe\"");
+    assert_contains!(manifest_content, "title = \"Dream\"");
+    assert_contains!(manifest_content, "edition = 2");
+    assert_contains!(
+        manifest_content,
+        "[shared_metadata.series]\nname = \"Dreams\""
+    );
+    assert_not_contains!(manifest_content, "note");
+    assert_not_contains!(manifest_content, "Old Title");
+}
+
+#[rstest]
+fn test_shared_meta_import_replace() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    add_metadata(&project_path, "author", "John Doe");
+
+    fs::write(project_path.join("metadata.json"), r#"{"title": "Dream"}"#)
+        .expect("Failed to write metadata file");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("import")
+        .arg("metadata.json")
+        .arg("--replace")
+        .assert()
+        .success();
+
+    let manifest_content =
+        fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest");
+
+    assert_contains!(manifest_content, "title = \"Dream\"");
+    assert_not_contains!(manifest_content, "John Doe");
+}
+
+#[rstest]
+fn test_shared_meta_import_with_format() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    fs::write(project_path.join("metadata.txt"), "title: Dream\n")
+        .expect("Failed to write metadata file");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("import")
+        .arg("metadata.txt")
+        .arg("--format")
+        .arg("yaml")
+        .assert()
+        .success();
+
+    let manifest_content =
+        fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest");
+
+    assert_contains!(manifest_content, "title = \"Dream\"");
+}
+
+#[rstest]
+fn test_shared_meta_import_unknown_extension() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    fs::write(project_path.join("metadata.txt"), "title: Dream\n")
+        .expect("Failed to write metadata file");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("import")
+        .arg("metadata.txt")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown metadata file extension 'txt'. Please specify the format.",
+        ));
+}
+
+#[rstest]
+fn test_shared_meta_import_not_a_table() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    fs::write(project_path.join("metadata.json"), r#"["Dream"]"#)
+        .expect("Failed to write metadata file");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("shared-meta")
+        .arg("import")
+        .arg("metadata.json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "must contain a table of metadata fields.",
+        ));
+}
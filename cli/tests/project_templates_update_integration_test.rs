@@ -471,3 +471,53 @@ fn test_update_template_non_existing_template() {
             "Template with name 'non_existing_template' does not exist.",
         ));
 }
+
+#[rstest]
+fn test_update_template_nav_title_source() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let project_path = create_empty_project(&temp_dir.path());
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("templates")
+        .arg("website")
+        .arg("add")
+        .arg("--template-type")
+        .arg("tex")
+        .arg("--meta-gen-feature")
+        .arg("full")
+        .arg("--nav-title-source")
+        .arg("path")
+        .assert()
+        .success();
+
+    let manifest_path = project_path.join("manifest.toml");
+    let manifest_content =
+        fs::read_to_string(&manifest_path).expect("Failed to read manifest file");
+    assert_contains!(
+        manifest_content,
+        r#"[templates.meta_gen]
+feature = "Full"
+nav_title_source = "Path""#
+    );
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("templates")
+        .arg("website")
+        .arg("update")
+        .arg("--nav-title-source")
+        .arg("content")
+        .assert()
+        .success();
+
+    let manifest_content = fs::read_to_string(manifest_path).expect("Failed to read manifest file");
+    assert_contains!(
+        manifest_content,
+        r#"[templates.meta_gen]
+feature = "Full"
+nav_title_source = "Content""#
+    );
+}
@@ -101,3 +101,49 @@ fn test_update_manifest_change_smart_clean_threshold(#[case] threshold: &str) {
         format!("smart_clean_threshold = {}", threshold).as_str()
     );
 }
+
+#[rstest]
+fn test_update_manifest_enable_build_cache() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let project_path = create_empty_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("update-settings")
+        .arg("--build-cache")
+        .arg("true")
+        .assert()
+        .success();
+
+    let manifest_path = project_path.join("manifest.toml");
+    assert!(manifest_path.exists(), "Manifest file should exist");
+
+    let manifest_content = fs::read_to_string(manifest_path).expect("Failed to read manifest file");
+
+    assert_contains!(manifest_content, r#"build_cache = true"#);
+}
+
+#[rstest]
+fn test_update_manifest_disable_build_cache() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let project_path = create_empty_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("update-settings")
+        .arg("--build-cache")
+        .arg("false")
+        .assert()
+        .success();
+
+    let manifest_path = project_path.join("manifest.toml");
+    assert!(manifest_path.exists(), "Manifest file should exist");
+
+    let manifest_content = fs::read_to_string(manifest_path).expect("Failed to read manifest file");
+
+    assert_not_contains!(manifest_content, r#"build_cache"#);
+}
@@ -0,0 +1,133 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+#[path = "assertions.rs"]
+#[macro_use]
+mod assertions;
+
+const V1_MANIFEST: &str = r#"# My book
+markdown_dir = "Markdown"
+version = 1
+
+[[templates]]
+name = "template.tex"
+template_type = "Tex"
+"#;
+
+fn create_v1_project(temp_dir: &Path) -> PathBuf {
+    let project_path = temp_dir.join("project");
+    fs::create_dir(&project_path).expect("Failed to create project directory");
+    fs::write(project_path.join("manifest.toml"), V1_MANIFEST).expect("Failed to write manifest");
+
+    project_path
+}
+
+fn read_manifest(project_path: &Path) -> String {
+    fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest file")
+}
+
+#[rstest]
+fn test_read_only_command_does_not_write_manifest() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_v1_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("list-templates")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("template.tex"))
+        .stdout(predicate::str::contains("project upgrade-manifest"));
+
+    assert_eq!(read_manifest(&project_path), V1_MANIFEST);
+    assert!(!project_path.join("manifest.toml.bak").exists());
+}
+
+#[rstest]
+fn test_upgrade_manifest_dry_run() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_v1_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    let output = cmd
+        .current_dir(&project_path)
+        .arg("project")
+        .arg("upgrade-manifest")
+        .arg("--dry-run")
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout).to_string();
+
+    assert_contains!(stdout, "--- manifest.toml (v1)");
+    assert_contains!(stdout, "+++ manifest.toml (v6)");
+    assert_contains!(stdout, "-version = 1");
+    assert_contains!(stdout, "+version = 6");
+    assert_contains!(stdout, "would be upgraded from version 1 to 6");
+
+    assert_eq!(read_manifest(&project_path), V1_MANIFEST);
+    assert!(!project_path.join("manifest.toml.bak").exists());
+}
+
+#[rstest]
+fn test_upgrade_manifest_writes_backup() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_v1_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("upgrade-manifest")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Upgraded the manifest from version 1 to 6.",
+        ));
+
+    let backup = fs::read_to_string(project_path.join("manifest.toml.bak"))
+        .expect("Failed to read backup file");
+    assert_eq!(backup, V1_MANIFEST);
+
+    let manifest_content = read_manifest(&project_path);
+    assert_contains!(manifest_content, "version = 6");
+    assert_not_contains!(manifest_content, "markdown_dir");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("upgrade-manifest")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The manifest is already at version 6.",
+        ));
+
+    assert_eq!(read_manifest(&project_path), manifest_content);
+}
+
+#[rstest]
+fn test_editing_older_manifest_writes_backup() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_v1_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("profiles")
+        .arg("add")
+        .arg("print")
+        .arg("template.tex")
+        .assert()
+        .success();
+
+    let backup = fs::read_to_string(project_path.join("manifest.toml.bak"))
+        .expect("Failed to read backup file");
+    assert_eq!(backup, V1_MANIFEST);
+    assert_contains!(read_manifest(&project_path), "version = 6");
+}
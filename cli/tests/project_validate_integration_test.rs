@@ -0,0 +1,113 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+fn create_empty_project(temp_dir: &Path) -> PathBuf {
+    let project_path = temp_dir.join("project");
+    fs::create_dir(&project_path).expect("Failed to create project directory");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("init")
+        .arg("-n")
+        .assert()
+        .success();
+
+    project_path
+}
+
+fn add_profile(project_path: &Path, template: &str) {
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(project_path)
+        .arg("project")
+        .arg("profiles")
+        .arg("add")
+        .arg("print")
+        .arg(template)
+        .assert()
+        .success();
+}
+
+fn add_processor(project_path: &Path) {
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(project_path)
+        .arg("project")
+        .arg("processors")
+        .arg("add")
+        .arg("Unused")
+        .arg("--")
+        .arg("--listings")
+        .assert()
+        .success();
+}
+
+#[rstest]
+fn test_validate_valid_project() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The manifest is valid with 0 warnings.",
+        ));
+}
+
+#[rstest]
+fn test_validate_missing_template() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+    add_profile(&project_path, "Missing template");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("validate")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "profile 'print': Template 'Missing template' does not exist.",
+        ))
+        .stderr(predicate::str::contains(
+            "Validation failed with 1 errors and 0 warnings.",
+        ));
+}
+
+#[rstest]
+#[case(false)]
+#[case(true)]
+fn test_validate_warnings(#[case] deny_warnings: bool) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_empty_project(temp_dir.path());
+    add_processor(&project_path);
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    cmd.current_dir(&project_path)
+        .arg("project")
+        .arg("validate");
+
+    if deny_warnings {
+        cmd.arg("--deny-warnings");
+    }
+
+    let assert = cmd.assert().stdout(predicate::str::contains(
+        "processor 'Unused': The processor is not used by any template.",
+    ));
+
+    if deny_warnings {
+        assert.failure().stderr(predicate::str::contains(
+            "Validation failed with 0 errors and 1 warnings.",
+        ));
+    } else {
+        assert.success();
+    }
+}
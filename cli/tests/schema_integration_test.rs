@@ -0,0 +1,43 @@
+use assert_cmd::Command;
+use rstest::rstest;
+use std::fs;
+use tempfile::tempdir;
+
+#[path = "assertions.rs"]
+#[macro_use]
+mod assertions;
+
+#[rstest]
+fn test_schema_prints_to_stdout() {
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    let output = cmd.arg("schema").assert().success().get_output().clone();
+
+    let stdout = String::from_utf8(output.stdout).expect("Failed to read stdout");
+    assert!(stdout.starts_with('{'));
+    assert_contains!(
+        stdout,
+        "\"$schema\": \"https://json-schema.org/draft/2020-12/schema\""
+    );
+    assert_contains!(stdout, "\"templates\"");
+}
+
+#[rstest]
+fn test_schema_writes_to_file() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let schema_path = temp_dir.path().join("manifest.schema.json");
+
+    let mut cmd = Command::cargo_bin("tiefdownconverter").expect("Failed to get cargo binary");
+    let output = cmd
+        .arg("schema")
+        .arg("--output")
+        .arg(&schema_path)
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+
+    assert!(output.stdout.is_empty());
+
+    let schema = fs::read_to_string(&schema_path).expect("Failed to read schema");
+    assert_contains!(schema, "\"markdown_projects\"");
+}
@@ -0,0 +1,203 @@
+use rstest::rstest;
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    _tests::tests_common::{get_default_markdown_project, get_default_template},
+    bibliography::{Bibliography, add_bibliography_args, get_bibliography, get_resources},
+    manifest_model::{BibliographySettings, CitationMode, MarkdownProject, Template},
+    template_type::TemplateType,
+};
+
+fn create_settings(files: Vec<&str>, csl: Option<&str>) -> BibliographySettings {
+    BibliographySettings {
+        files: Some(files.into_iter().map(PathBuf::from).collect()),
+        csl: csl.map(PathBuf::from),
+        mode: None,
+        link_citations: None,
+    }
+}
+
+fn get_args(pandoc: &Command) -> Vec<String> {
+    pandoc
+        .get_args()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect()
+}
+
+#[rstest]
+fn test_get_bibliography_without_settings() {
+    let bibliography = get_bibliography(
+        Path::new("project"),
+        &get_default_markdown_project("Book"),
+        &get_default_template("book", TemplateType::Tex),
+    )
+    .expect("Failed to get bibliography");
+
+    assert_eq!(bibliography, None);
+}
+
+#[rstest]
+fn test_get_bibliography_merges_settings() {
+    let mut markdown_project_settings =
+        create_settings(vec!["sources/references.bib"], Some("styles/apa.csl"));
+    markdown_project_settings.link_citations = Some(true);
+    let mut template_settings = create_settings(vec!["template.bib"], Some("chicago.csl"));
+    template_settings.link_citations = Some(false);
+
+    let bibliography = get_bibliography(
+        Path::new("project"),
+        &MarkdownProject {
+            bibliography: Some(markdown_project_settings),
+            ..get_default_markdown_project("Book")
+        },
+        &Template {
+            bibliography: Some(template_settings),
+            ..get_default_template("book", TemplateType::Typst)
+        },
+    )
+    .expect("Failed to get bibliography");
+
+    assert_eq!(
+        bibliography,
+        Some(Bibliography {
+            files: vec!["references.bib".into(), "template.bib".into()],
+            csl: Some("chicago.csl".into()),
+            mode: CitationMode::Citeproc,
+            link_citations: Some(false),
+            sources: vec![
+                "project/Markdown/sources/references.bib".into(),
+                "project/template/template.bib".into(),
+                "project/template/chicago.csl".into(),
+            ],
+        })
+    );
+}
+
+#[rstest]
+#[case(vec!["a/references.bib", "b/references.bib"], None)]
+#[case(vec!["sources/template.bib"], Some(vec!["template.bib"]))]
+fn test_get_bibliography_rejects_duplicate_file_names(
+    #[case] markdown_project_files: Vec<&str>,
+    #[case] template_files: Option<Vec<&str>>,
+) {
+    let result = get_bibliography(
+        Path::new("project"),
+        &MarkdownProject {
+            bibliography: Some(create_settings(markdown_project_files, None)),
+            ..get_default_markdown_project("Book")
+        },
+        &Template {
+            bibliography: template_files.map(|files| create_settings(files, None)),
+            ..get_default_template("book", TemplateType::Typst)
+        },
+    );
+
+    assert!(
+        result
+            .err()
+            .is_some_and(|e| e.to_string().contains("are both copied to"))
+    );
+}
+
+#[rstest]
+fn test_get_bibliography_uses_markdown_project_csl() {
+    let bibliography = get_bibliography(
+        Path::new("project"),
+        &MarkdownProject {
+            bibliography: Some(create_settings(
+                vec!["references.bib"],
+                Some("styles/apa.csl"),
+            )),
+            ..get_default_markdown_project("Book")
+        },
+        &get_default_template("book", TemplateType::Epub),
+    )
+    .expect("Failed to get bibliography")
+    .expect("Expected a bibliography");
+
+    assert_eq!(bibliography.csl, Some("apa.csl".into()));
+}
+
+#[rstest]
+#[case(TemplateType::Tex, true)]
+#[case(TemplateType::Typst, false)]
+#[case(TemplateType::Epub, false)]
+#[case(TemplateType::CustomProcessor, false)]
+fn test_get_bibliography_biblatex_mode(
+    #[case] template_type: TemplateType,
+    #[case] supported: bool,
+) {
+    let mut settings = create_settings(vec!["references.bib"], None);
+    settings.mode = Some(CitationMode::Biblatex);
+
+    let result = get_bibliography(
+        Path::new("project"),
+        &MarkdownProject {
+            bibliography: Some(settings),
+            ..get_default_markdown_project("Book")
+        },
+        &get_default_template("book", template_type),
+    );
+
+    assert_eq!(result.is_ok(), supported);
+}
+
+#[rstest]
+fn test_get_resources_includes_bibliography() {
+    let markdown_project = MarkdownProject {
+        resources: Some(vec!["cover.png".into(), "references.bib".into()]),
+        bibliography: Some(create_settings(
+            vec!["references.bib", "more.json"],
+            Some("apa.csl"),
+        )),
+        ..get_default_markdown_project("Book")
+    };
+
+    assert_eq!(
+        get_resources(&markdown_project),
+        vec![
+            PathBuf::from("cover.png"),
+            PathBuf::from("references.bib"),
+            PathBuf::from("more.json"),
+            PathBuf::from("apa.csl"),
+        ]
+    );
+}
+
+#[rstest]
+#[case(CitationMode::Citeproc, vec![
+    "--citeproc",
+    "--bibliography",
+    "references.bib",
+    "--csl",
+    "apa.csl",
+    "-M",
+    "link-citations=true",
+])]
+#[case(CitationMode::Biblatex, vec!["--biblatex", "--bibliography", "references.bib"])]
+fn test_add_bibliography_args(#[case] mode: CitationMode, #[case] expected: Vec<&str>) {
+    let mut pandoc = Command::new("pandoc");
+    add_bibliography_args(
+        &Some(Bibliography {
+            files: vec!["references.bib".into()],
+            csl: Some("apa.csl".into()),
+            mode,
+            link_citations: Some(true),
+            sources: vec![],
+        }),
+        &mut pandoc,
+    );
+
+    assert_eq!(get_args(&pandoc), expected);
+}
+
+#[rstest]
+fn test_add_bibliography_args_without_bibliography() {
+    let mut pandoc = Command::new("pandoc");
+    add_bibliography_args(&None, &mut pandoc);
+
+    assert!(get_args(&pandoc).is_empty());
+}
@@ -0,0 +1,368 @@
+use rstest::rstest;
+use std::{cell::Cell, fs, path::Path};
+use tempfile::tempdir;
+use toml::Table;
+
+use crate::{
+    _tests::tests_common::{get_default_conversion_meta, get_default_template},
+    bibliography::Bibliography,
+    build_cache::{BUILD_CACHE_DIRECTORY, BuildCache, TaskInputs, clear_build_cache},
+    converters::conversion_meta::ConversionMeta,
+    manifest_model::{CitationMode, PreProcessor, Processors},
+    template_type::TemplateType,
+};
+
+fn create_task_cache(project_path: &Path, metadata_fields: &Table) -> BuildCache {
+    BuildCache::open(project_path)
+        .expect("Failed to open build cache")
+        .for_task(&TaskInputs {
+            conversion_meta: &get_default_conversion_meta(
+                "Default",
+                "template",
+                TemplateType::CustomProcessor,
+            ),
+            template: &get_default_template("template", TemplateType::CustomProcessor),
+            metadata_fields,
+            metadata_settings: &None,
+            custom_processors: &Processors {
+                preprocessors: vec![],
+                processors: vec![],
+            },
+            injections: &vec![],
+            template_directory_path: &project_path.join("template"),
+            resources: &vec![],
+            conversion_input_dir: &project_path.join("compiled"),
+        })
+        .expect("Failed to create task cache")
+}
+
+fn create_preprocessor() -> PreProcessor {
+    PreProcessor {
+        name: "preprocessor".to_string(),
+        cli: None,
+        cli_args: vec!["-t".to_string(), "native".to_string()],
+        extension_filter: None,
+    }
+}
+
+#[rstest]
+fn preprocessing_key_changes_with_file_content() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let compiled_path = temp_dir.path().join("compiled");
+    fs::create_dir_all(&compiled_path).expect("Failed to create compiled directory");
+    fs::write(compiled_path.join("chapter.md"), "# Chapter").expect("Failed to write file");
+
+    let cache = create_task_cache(temp_dir.path(), &Table::new());
+    let files = vec![Path::new("chapter.md").to_path_buf()];
+
+    let key = cache
+        .preprocessing_key(
+            &get_default_template("template", TemplateType::CustomProcessor),
+            &compiled_path,
+            &None,
+            &None,
+            &create_preprocessor(),
+            &files,
+        )
+        .expect("Failed to compute key");
+    let same_key = cache
+        .preprocessing_key(
+            &get_default_template("template", TemplateType::CustomProcessor),
+            &compiled_path,
+            &None,
+            &None,
+            &create_preprocessor(),
+            &files,
+        )
+        .expect("Failed to compute key");
+
+    assert!(key.is_some());
+    assert_eq!(key, same_key);
+
+    fs::write(compiled_path.join("chapter.md"), "# Changed").expect("Failed to write file");
+
+    let changed_key = cache
+        .preprocessing_key(
+            &get_default_template("template", TemplateType::CustomProcessor),
+            &compiled_path,
+            &None,
+            &None,
+            &create_preprocessor(),
+            &files,
+        )
+        .expect("Failed to compute key");
+
+    assert_ne!(key, changed_key);
+}
+
+#[rstest]
+fn preprocessing_key_changes_with_profile() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let compiled_path = temp_dir.path().join("compiled");
+    fs::create_dir_all(&compiled_path).expect("Failed to create compiled directory");
+    fs::write(compiled_path.join("chapter.md"), "# Chapter").expect("Failed to write file");
+
+    let files = vec![Path::new("chapter.md").to_path_buf()];
+    let get_key = |profile: Option<&str>| {
+        BuildCache::open(temp_dir.path())
+            .expect("Failed to open build cache")
+            .for_task(&TaskInputs {
+                conversion_meta: &ConversionMeta {
+                    profile: profile.map(str::to_string),
+                    ..get_default_conversion_meta(
+                        "Default",
+                        "template",
+                        TemplateType::CustomProcessor,
+                    )
+                },
+                template: &get_default_template("template", TemplateType::CustomProcessor),
+                metadata_fields: &Table::new(),
+                metadata_settings: &None,
+                custom_processors: &Processors {
+                    preprocessors: vec![],
+                    processors: vec![],
+                },
+                injections: &vec![],
+                template_directory_path: &temp_dir.path().join("template"),
+                resources: &vec![],
+                conversion_input_dir: &compiled_path,
+            })
+            .expect("Failed to create task cache")
+            .preprocessing_key(
+                &get_default_template("template", TemplateType::CustomProcessor),
+                &compiled_path,
+                &None,
+                &None,
+                &create_preprocessor(),
+                &files,
+            )
+            .expect("Failed to compute key")
+    };
+
+    assert_ne!(get_key(Some("print")), get_key(Some("ebook")));
+    assert_ne!(get_key(Some("print")), get_key(None));
+}
+
+#[rstest]
+fn preprocessing_key_changes_with_bibliography_file() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let compiled_path = temp_dir.path().join("compiled");
+    fs::create_dir_all(&compiled_path).expect("Failed to create compiled directory");
+    fs::write(compiled_path.join("chapter.md"), "# Chapter").expect("Failed to write file");
+
+    let bibliography_path = temp_dir.path().join("references.bib");
+    let bibliography = Bibliography {
+        files: vec![Path::new("references.bib").to_path_buf()],
+        csl: None,
+        mode: CitationMode::Citeproc,
+        link_citations: None,
+        sources: vec![bibliography_path.clone()],
+    };
+
+    let files = vec![Path::new("chapter.md").to_path_buf()];
+    let get_key = || {
+        BuildCache::open(temp_dir.path())
+            .expect("Failed to open build cache")
+            .for_task(&TaskInputs {
+                conversion_meta: &ConversionMeta {
+                    bibliography: Some(bibliography.clone()),
+                    ..get_default_conversion_meta(
+                        "Default",
+                        "template",
+                        TemplateType::CustomProcessor,
+                    )
+                },
+                template: &get_default_template("template", TemplateType::CustomProcessor),
+                metadata_fields: &Table::new(),
+                metadata_settings: &None,
+                custom_processors: &Processors {
+                    preprocessors: vec![],
+                    processors: vec![],
+                },
+                injections: &vec![],
+                template_directory_path: &temp_dir.path().join("template"),
+                resources: &vec![],
+                conversion_input_dir: &compiled_path,
+            })
+            .expect("Failed to create task cache")
+            .preprocessing_key(
+                &get_default_template("template", TemplateType::CustomProcessor),
+                &compiled_path,
+                &None,
+                &None,
+                &create_preprocessor(),
+                &files,
+            )
+            .expect("Failed to compute key")
+    };
+
+    fs::write(&bibliography_path, "@book{a, title = {A}}").expect("Failed to write file");
+    let key = get_key();
+    fs::write(&bibliography_path, "@book{a, title = {B}}").expect("Failed to write file");
+
+    assert_ne!(key, get_key());
+}
+
+#[rstest]
+fn preprocessing_key_changes_with_metadata() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let compiled_path = temp_dir.path().join("compiled");
+    fs::create_dir_all(&compiled_path).expect("Failed to create compiled directory");
+    fs::write(compiled_path.join("chapter.md"), "# Chapter").expect("Failed to write file");
+
+    let mut metadata_fields = Table::new();
+    let files = vec![Path::new("chapter.md").to_path_buf()];
+
+    let key = create_task_cache(temp_dir.path(), &metadata_fields)
+        .preprocessing_key(
+            &get_default_template("template", TemplateType::CustomProcessor),
+            &compiled_path,
+            &None,
+            &None,
+            &create_preprocessor(),
+            &files,
+        )
+        .expect("Failed to compute key");
+
+    metadata_fields.insert("title".to_string(), "Title".into());
+
+    let changed_key = create_task_cache(temp_dir.path(), &metadata_fields)
+        .preprocessing_key(
+            &get_default_template("template", TemplateType::CustomProcessor),
+            &compiled_path,
+            &None,
+            &None,
+            &create_preprocessor(),
+            &files,
+        )
+        .expect("Failed to compute key");
+
+    assert_ne!(key, changed_key);
+}
+
+#[rstest]
+fn chunks_are_stored_and_retrieved() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let cache = create_task_cache(temp_dir.path(), &Table::new());
+    let key = Some("chunk".to_string());
+
+    assert_eq!(cache.get_chunk(&key), None);
+
+    cache
+        .store_chunk(&key, "result")
+        .expect("Failed to store chunk");
+
+    assert_eq!(cache.get_chunk(&key), Some("result".to_string()));
+    assert_eq!(
+        fs::read_dir(temp_dir.path().join(BUILD_CACHE_DIRECTORY).join("chunks"))
+            .expect("Failed to read chunks directory")
+            .count(),
+        1
+    );
+}
+
+#[rstest]
+fn disabled_cache_never_hits() {
+    let cache = BuildCache::disabled();
+    let key = Some("chunk".to_string());
+
+    cache
+        .store_chunk(&key, "result")
+        .expect("Failed to store chunk");
+
+    assert_eq!(cache.get_chunk(&key), None);
+}
+
+#[rstest]
+fn run_processor_skips_unchanged_inputs() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let compiled_path = temp_dir.path().join("compiled");
+    fs::create_dir_all(&compiled_path).expect("Failed to create compiled directory");
+    fs::write(compiled_path.join("input.tex"), "content").expect("Failed to write file");
+
+    let cache = create_task_cache(temp_dir.path(), &Table::new());
+    let output_path = compiled_path.join("output.pdf");
+    let runs = Cell::new(0);
+
+    let run = || {
+        cache
+            .run_processor(
+                &compiled_path,
+                &[Path::new("input.tex")],
+                &[],
+                &output_path,
+                || {
+                    runs.set(runs.get() + 1);
+                    fs::write(&output_path, format!("run {}", runs.get()))?;
+                    Ok(())
+                },
+            )
+            .expect("Failed to run processor");
+    };
+
+    run();
+    fs::remove_file(&output_path).expect("Failed to remove output");
+    run();
+
+    assert_eq!(runs.get(), 1);
+    assert_eq!(
+        fs::read_to_string(&output_path).expect("Failed to read output"),
+        "run 1"
+    );
+
+    fs::write(compiled_path.join("input.tex"), "changed").expect("Failed to write file");
+    run();
+
+    assert_eq!(runs.get(), 2);
+}
+
+#[rstest]
+fn run_processor_reruns_on_changed_asset() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let compiled_path = temp_dir.path().join("compiled");
+    fs::create_dir_all(compiled_path.join("figures")).expect("Failed to create directory");
+    fs::write(compiled_path.join("input.tex"), "content").expect("Failed to write file");
+    fs::write(compiled_path.join("figures/figure.svg"), "<svg/>").expect("Failed to write file");
+
+    let output_path = temp_dir.path().join("output.pdf");
+    let runs = Cell::new(0);
+
+    let run = || {
+        create_task_cache(temp_dir.path(), &Table::new())
+            .run_processor(
+                &compiled_path,
+                &[Path::new("input.tex")],
+                &[],
+                &output_path,
+                || {
+                    runs.set(runs.get() + 1);
+                    fs::write(&output_path, format!("run {}", runs.get()))?;
+                    Ok(())
+                },
+            )
+            .expect("Failed to run processor");
+    };
+
+    run();
+    run();
+
+    assert_eq!(runs.get(), 1);
+
+    fs::write(compiled_path.join("figures/figure.svg"), "<svg></svg>")
+        .expect("Failed to write file");
+    run();
+
+    assert_eq!(runs.get(), 2);
+}
+
+#[rstest]
+fn clear_build_cache_removes_cache_directory() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    BuildCache::open(temp_dir.path()).expect("Failed to open build cache");
+
+    assert!(temp_dir.path().join(BUILD_CACHE_DIRECTORY).exists());
+
+    clear_build_cache(temp_dir.path()).expect("Failed to clear build cache");
+
+    assert!(!temp_dir.path().join(BUILD_CACHE_DIRECTORY).exists());
+}
@@ -0,0 +1,133 @@
+use color_eyre::eyre::eyre;
+use rstest::rstest;
+use std::{fs, path::PathBuf, time::Duration};
+use tempfile::tempdir;
+
+use crate::{
+    build_report::{WarningKind, WarningReport, create_task_report, get_build_relative_path},
+    conversion::ConversionTask,
+    conversion_recorder::{ConversionRecord, RecordedCommand},
+    manifest_model::MarkdownProject,
+    template_type::TemplateType,
+};
+
+fn create_conversion_task() -> ConversionTask {
+    ConversionTask {
+        markdown_project: MarkdownProject {
+            name: "Dream".to_string(),
+            path: PathBuf::from("Dream"),
+            output: PathBuf::from("out"),
+            metadata_fields: None,
+            default_profile: None,
+            resources: None,
+            include: None,
+            exclude: None,
+            bibliography: None,
+        },
+        template: "epub".to_string(),
+        profile: None,
+    }
+}
+
+#[rstest]
+#[case("build/out/epub_convdir/Chapter 1.md", "out/epub_convdir/Chapter 1.md")]
+#[case("epub_convdir/Chapter 1.md", "out/epub_convdir/Chapter 1.md")]
+#[case("build/out", "out")]
+#[case("build/./out", "out")]
+#[case("build", ".")]
+fn test_get_build_relative_path(#[case] path: &str, #[case] expected: &str) {
+    let relative_path = get_build_relative_path(
+        &PathBuf::from(path),
+        &PathBuf::from("build/out"),
+        &PathBuf::from("build"),
+    );
+
+    assert_eq!(relative_path, PathBuf::from(expected));
+}
+
+#[rstest]
+fn test_create_task_report() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = temp_dir.path().join("project");
+    let build_path = project_path.join("build");
+    fs::create_dir_all(project_path.join("out")).expect("Failed to create output directory");
+    fs::write(project_path.join("out/epub.epub"), "fake\n").expect("Failed to write artifact");
+
+    let record = ConversionRecord {
+        input_files: vec![PathBuf::from("epub_convdir/Chapter 1.md")],
+        commands: vec![RecordedCommand {
+            order: (0, 0),
+            working_directory: Some(build_path.join("out")),
+            command_line: "pandoc -t epub3 -o epub.epub".to_string(),
+            duration: Some(Duration::from_millis(1500)),
+            exit_code: Some(0),
+        }],
+        artifacts: vec![project_path.join("out/epub.epub")],
+        warnings: vec![WarningReport {
+            source: "pandoc".to_string(),
+            kind: WarningKind::Stderr,
+            message: "[WARNING] Missing character".to_string(),
+            file: None,
+            line: None,
+        }],
+        ..Default::default()
+    };
+
+    let report = create_task_report(
+        &project_path,
+        &build_path,
+        &create_conversion_task(),
+        Some(TemplateType::Epub),
+        &Ok(()),
+        Duration::from_secs(2),
+        record,
+    )
+    .expect("Failed to create task report");
+
+    assert!(report.success);
+    assert_eq!(report.error, None);
+    assert_eq!(report.template_type, Some(TemplateType::Epub));
+    assert_eq!(report.duration_seconds, 2.0);
+    assert_eq!(
+        report.input_files,
+        vec![PathBuf::from("out/epub_convdir/Chapter 1.md")]
+    );
+    assert_eq!(report.commands.len(), 1);
+    assert_eq!(
+        report.commands[0].working_directory,
+        Some(PathBuf::from("out"))
+    );
+    assert_eq!(report.commands[0].duration_seconds, Some(1.5));
+    assert_eq!(report.commands[0].exit_code, Some(0));
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].source, "pandoc");
+    assert_eq!(report.warnings[0].kind, WarningKind::Stderr);
+    assert_eq!(report.artifacts.len(), 1);
+    assert_eq!(report.artifacts[0].path, PathBuf::from("out/epub.epub"));
+    assert_eq!(report.artifacts[0].size, 5);
+    assert_eq!(
+        report.artifacts[0].sha256,
+        "997890bc85c5796408ceb20b0ca75dabe6fe868136e926d24ad0f36aa424f99d"
+    );
+}
+
+#[rstest]
+fn test_create_task_report_failed_task() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = temp_dir.path().join("project");
+
+    let report = create_task_report(
+        &project_path,
+        &project_path.join("build"),
+        &create_conversion_task(),
+        None,
+        &Err(eyre!("Template epub not found.")),
+        Duration::from_secs(0),
+        ConversionRecord::default(),
+    )
+    .expect("Failed to create task report");
+
+    assert!(!report.success);
+    assert_eq!(report.error, Some("Template epub not found.".to_string()));
+    assert!(report.artifacts.is_empty());
+}
@@ -0,0 +1,407 @@
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::{TempDir, tempdir};
+
+use crate::{
+    chapter_management::{Chapter, get_chapters, insert_chapter, move_chapter},
+    manifest_model::Manifest,
+};
+
+const MANIFEST: &str = r#"version = 6
+
+[[markdown_projects]]
+name = "Book"
+path = "Markdown"
+output = "."
+resources = ["Chapter 2 - Journey/cover.png"]
+
+[markdown_projects.bibliography]
+files = ["Chapter 2 - Journey/refs.bib", "sources.bib"]
+csl = "Chapter 2 - Journey/style.csl"
+
+[[templates]]
+name = "pdf"
+template_type = "Tex"
+
+[[injections]]
+name = "journey"
+files = ["pdf_convdir/Chapter 2 - Journey/Part 1 - Road.md", "header.tex"]
+
+[custom_processors]
+preprocessors = []
+processors = []
+"#;
+
+fn create_project() -> (TempDir, PathBuf) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = temp_dir.path().to_path_buf();
+    let markdown_dir = project_path.join("Markdown");
+
+    fs::create_dir_all(markdown_dir.join("Chapter 2 - Journey"))
+        .expect("Failed to create directory");
+    fs::write(project_path.join("manifest.toml"), MANIFEST).expect("Failed to write manifest");
+
+    for file in [
+        "Chapter 1 - Home.md",
+        "Chapter 2 - Journey.md",
+        "Chapter 2 - Journey/Part 1 - Road.md",
+        "Chapter 2 - Journey/Part 2 - Sea.md",
+        "Chapter 2 - Journey/cover.png",
+        "Chapter 2 - Journey/refs.bib",
+        "Chapter 2 - Journey/style.csl",
+        "Chapter 03 - Return.md",
+    ] {
+        fs::write(markdown_dir.join(file), "").expect("Failed to write file");
+    }
+
+    (temp_dir, project_path)
+}
+
+fn get_file_names(project_path: &Path) -> Vec<Vec<PathBuf>> {
+    get_chapters(Some(project_path.to_path_buf()), "Book", None)
+        .expect("Failed to get chapters")
+        .into_iter()
+        .map(|chapter| [chapter.files, chapter.directories].concat())
+        .collect()
+}
+
+fn paths(paths: &[&str]) -> Vec<PathBuf> {
+    paths.iter().map(PathBuf::from).collect()
+}
+
+#[rstest]
+fn test_get_chapters() {
+    let (_temp_dir, project_path) = create_project();
+
+    let chapters = get_chapters(Some(project_path), "Book", None).expect("Failed to get chapters");
+
+    assert_eq!(
+        chapters,
+        vec![
+            Chapter {
+                position: 1,
+                order_number: Some(1),
+                files: paths(&["Chapter 1 - Home.md"]),
+                directories: vec![],
+                children: vec![],
+            },
+            Chapter {
+                position: 2,
+                order_number: Some(2),
+                files: paths(&["Chapter 2 - Journey.md"]),
+                directories: paths(&["Chapter 2 - Journey"]),
+                children: vec![
+                    Chapter {
+                        position: 1,
+                        order_number: Some(1),
+                        files: paths(&["Chapter 2 - Journey/Part 1 - Road.md"]),
+                        directories: vec![],
+                        children: vec![],
+                    },
+                    Chapter {
+                        position: 2,
+                        order_number: Some(2),
+                        files: paths(&["Chapter 2 - Journey/Part 2 - Sea.md"]),
+                        directories: vec![],
+                        children: vec![],
+                    },
+                ],
+            },
+            Chapter {
+                position: 3,
+                order_number: Some(3),
+                files: paths(&["Chapter 03 - Return.md"]),
+                directories: vec![],
+                children: vec![],
+            },
+        ]
+    );
+}
+
+#[rstest]
+fn test_get_chapters_order_file() {
+    let (_temp_dir, project_path) = create_project();
+    fs::write(
+        project_path.join("Markdown/order.toml"),
+        "order = [\"Chapter 03 - Return.md\", \"Chapter 1 - Home.md\"]\n",
+    )
+    .expect("Failed to write ordering file");
+
+    let chapters = get_chapters(Some(project_path), "Book", None).expect("Failed to get chapters");
+
+    assert_eq!(
+        chapters
+            .iter()
+            .map(|chapter| (
+                chapter.position,
+                chapter.order_number,
+                chapter.files.clone()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (1, None, paths(&["Chapter 03 - Return.md"])),
+            (2, None, paths(&["Chapter 1 - Home.md"])),
+            (3, None, paths(&["Chapter 2 - Journey.md"])),
+            (4, None, vec![]),
+        ]
+    );
+}
+
+#[rstest]
+fn test_insert_chapter() {
+    let (_temp_dir, project_path) = create_project();
+
+    let chapter = insert_chapter(
+        Some(project_path.clone()),
+        "Book",
+        None,
+        "Chapter 9 - Storm.md".to_string(),
+        Some(2),
+    )
+    .expect("Failed to insert chapter");
+
+    assert_eq!(chapter, PathBuf::from("Chapter 2 - Storm.md"));
+    assert_eq!(
+        get_file_names(&project_path),
+        vec![
+            paths(&["Chapter 1 - Home.md"]),
+            paths(&["Chapter 2 - Storm.md"]),
+            paths(&["Chapter 3 - Journey.md", "Chapter 3 - Journey"]),
+            paths(&["Chapter 04 - Return.md"]),
+        ]
+    );
+
+    let manifest: Manifest = toml::from_str(
+        &fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest"),
+    )
+    .expect("Failed to parse manifest");
+
+    let markdown_project = &manifest.markdown_projects.unwrap()[0];
+    assert_eq!(
+        markdown_project.resources,
+        Some(paths(&["Chapter 3 - Journey/cover.png"]))
+    );
+    let bibliography = markdown_project.bibliography.as_ref().unwrap();
+    assert_eq!(
+        bibliography.files,
+        Some(paths(&["Chapter 3 - Journey/refs.bib", "sources.bib"]))
+    );
+    assert_eq!(
+        bibliography.csl,
+        Some(PathBuf::from("Chapter 3 - Journey/style.csl"))
+    );
+    assert_eq!(
+        manifest.injections.unwrap()[0].files,
+        paths(&[
+            "pdf_convdir/Chapter 3 - Journey/Part 1 - Road.md",
+            "header.tex"
+        ])
+    );
+}
+
+#[rstest]
+fn test_insert_chapter_at_end() {
+    let (_temp_dir, project_path) = create_project();
+
+    let chapter = insert_chapter(
+        Some(project_path.clone()),
+        "Book",
+        None,
+        "Chapter 1 - Epilogue.md".to_string(),
+        None,
+    )
+    .expect("Failed to insert chapter");
+
+    assert_eq!(chapter, PathBuf::from("Chapter 4 - Epilogue.md"));
+    assert!(
+        project_path
+            .join("Markdown/Chapter 4 - Epilogue.md")
+            .is_file()
+    );
+    assert!(
+        project_path
+            .join("Markdown/Chapter 03 - Return.md")
+            .is_file()
+    );
+}
+
+#[rstest]
+fn test_insert_existing_chapter() {
+    let (_temp_dir, project_path) = create_project();
+    fs::write(
+        project_path.join("Markdown/Chapter 03 - Return.md"),
+        "# Return\n",
+    )
+    .expect("Failed to write file");
+
+    let chapter = insert_chapter(
+        Some(project_path.clone()),
+        "Book",
+        None,
+        "Chapter 03 - Return.md".to_string(),
+        Some(1),
+    )
+    .expect("Failed to insert chapter");
+
+    assert_eq!(chapter, PathBuf::from("Chapter 01 - Return.md"));
+    assert_eq!(
+        fs::read_to_string(project_path.join("Markdown/Chapter 01 - Return.md"))
+            .expect("Failed to read chapter"),
+        "# Return\n"
+    );
+    assert_eq!(
+        get_file_names(&project_path),
+        vec![
+            paths(&["Chapter 01 - Return.md"]),
+            paths(&["Chapter 2 - Home.md"]),
+            paths(&["Chapter 3 - Journey.md", "Chapter 3 - Journey"]),
+        ]
+    );
+}
+
+#[rstest]
+fn test_insert_chapter_existing_file_leaves_directory_untouched() {
+    let (_temp_dir, project_path) = create_project();
+    fs::write(
+        project_path.join("manifest.toml"),
+        MANIFEST.replace(
+            "output = \".\"\n",
+            "output = \".\"\nexclude = [\"*Storm*\"]\n",
+        ),
+    )
+    .expect("Failed to write manifest");
+    fs::write(project_path.join("Markdown/Chapter 2 - Storm.md"), "")
+        .expect("Failed to write file");
+    let file_names = get_file_names(&project_path);
+    let manifest =
+        fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest");
+
+    let result = insert_chapter(
+        Some(project_path.clone()),
+        "Book",
+        None,
+        "Chapter 9 - Storm.md".to_string(),
+        Some(2),
+    );
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("Cannot create chapter 'Chapter 2 - Storm.md' as it already exists.".to_string())
+    );
+    assert_eq!(get_file_names(&project_path), file_names);
+    assert_eq!(
+        fs::read_to_string(project_path.join("manifest.toml")).expect("Failed to read manifest"),
+        manifest
+    );
+}
+
+#[rstest]
+fn test_move_chapter() {
+    let (_temp_dir, project_path) = create_project();
+
+    move_chapter(Some(project_path.clone()), "Book", None, 3, 1).expect("Failed to move chapter");
+
+    assert_eq!(
+        get_file_names(&project_path),
+        vec![
+            paths(&["Chapter 01 - Return.md"]),
+            paths(&["Chapter 2 - Home.md"]),
+            paths(&["Chapter 3 - Journey.md", "Chapter 3 - Journey"]),
+        ]
+    );
+    assert!(
+        fs::read_to_string(project_path.join("manifest.toml"))
+            .expect("Failed to read manifest")
+            .contains("Chapter 3 - Journey/cover.png")
+    );
+}
+
+#[rstest]
+fn test_move_chapter_in_subdirectory() {
+    let (_temp_dir, project_path) = create_project();
+
+    move_chapter(
+        Some(project_path.clone()),
+        "Book",
+        Some(PathBuf::from("Chapter 2 - Journey")),
+        2,
+        1,
+    )
+    .expect("Failed to move chapter");
+
+    let journey_dir = project_path.join("Markdown/Chapter 2 - Journey");
+    assert!(journey_dir.join("Part 1 - Sea.md").is_file());
+    assert!(journey_dir.join("Part 2 - Road.md").is_file());
+    assert!(journey_dir.join("cover.png").is_file());
+    assert!(
+        fs::read_to_string(project_path.join("manifest.toml"))
+            .expect("Failed to read manifest")
+            .contains("pdf_convdir/Chapter 2 - Journey/Part 2 - Road.md")
+    );
+}
+
+#[rstest]
+#[case(0, 1)]
+#[case(1, 4)]
+fn test_move_chapter_out_of_range(#[case] from: usize, #[case] to: usize) {
+    let (_temp_dir, project_path) = create_project();
+
+    let result = move_chapter(Some(project_path.clone()), "Book", None, from, to);
+
+    assert!(result.is_err());
+    assert!(project_path.join("Markdown/Chapter 1 - Home.md").is_file());
+}
+
+#[rstest]
+fn test_move_chapter_order_file() {
+    let (_temp_dir, project_path) = create_project();
+    fs::write(
+        project_path.join("Markdown/order.toml"),
+        "summary = \"SUMMARY.md\"\n",
+    )
+    .expect("Failed to write ordering file");
+    fs::write(
+        project_path.join("Markdown/SUMMARY.md"),
+        "- Chapter 1 - Home.md\n",
+    )
+    .expect("Failed to write summary");
+
+    let result = move_chapter(Some(project_path.clone()), "Book", None, 2, 1);
+
+    assert!(result.is_err());
+    assert!(project_path.join("Markdown/Chapter 1 - Home.md").is_file());
+}
+
+#[rstest]
+fn test_move_chapter_without_order_number() {
+    let (_temp_dir, project_path) = create_project();
+    fs::write(project_path.join("Markdown/Epilogue.md"), "").expect("Failed to write file");
+
+    let result = move_chapter(Some(project_path.clone()), "Book", None, 2, 1);
+
+    assert!(result.is_err());
+    assert!(project_path.join("Markdown/Chapter 1 - Home.md").is_file());
+}
+
+#[rstest]
+#[case("Storm.md")]
+#[case("Part 1/Chapter 4 - Storm.md")]
+fn test_insert_chapter_invalid_name(#[case] chapter: &str) {
+    let (_temp_dir, project_path) = create_project();
+
+    let result = insert_chapter(Some(project_path), "Book", None, chapter.to_string(), None);
+
+    assert!(result.is_err());
+}
+
+#[rstest]
+fn test_get_chapters_missing_markdown_project() {
+    let (_temp_dir, project_path) = create_project();
+
+    let result = get_chapters(Some(project_path), "Missing", None);
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,104 @@
+use rstest::rstest;
+use toml::Table;
+
+use crate::converters::common::{flatten_metadata, normalize_metadata, preprocess_cli_args};
+
+const METADATA: &str = r#"
+title = "Dream"
+edition = 2
+price = 9.5
+published = true
+release = 2025-03-01
+authors = ["Lena Tauchner", "Jane Doe"]
+
+[series]
+name = "Dreams"
+number = 1
+
+[[isbn]]
+format = "Paperback"
+number = "978-3-16-148410-0"
+"#;
+
+#[rstest]
+fn test_flatten_metadata() {
+    let metadata_fields: Table = toml::from_str(METADATA).expect("Failed to parse metadata");
+
+    let flattened_metadata = flatten_metadata(&metadata_fields, &|_, value| value.to_string());
+
+    let expected = vec![
+        ("authors", "Lena Tauchner, Jane Doe"),
+        ("authors.count", "2"),
+        ("authors.1", "Lena Tauchner"),
+        ("authors.2", "Jane Doe"),
+        ("edition", "2"),
+        ("isbn.count", "1"),
+        ("isbn.1.format", "Paperback"),
+        ("isbn.1.number", "978-3-16-148410-0"),
+        ("price", "9.5"),
+        ("published", "true"),
+        ("release", "2025-03-01"),
+        ("series.name", "Dreams"),
+        ("series.number", "1"),
+        ("title", "Dream"),
+    ];
+
+    assert_eq!(
+        flattened_metadata
+            .iter()
+            .map(|(path, value)| (path.as_str(), value.as_str()))
+            .collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[rstest]
+fn test_flatten_metadata_formats_values_with_path() {
+    let metadata_fields: Table = toml::from_str(METADATA).expect("Failed to parse metadata");
+
+    let flattened_metadata = flatten_metadata(&metadata_fields, &|path, value| {
+        format!("{}={}", path, value)
+    });
+
+    assert!(flattened_metadata.contains(&(
+        "authors".to_string(),
+        "authors.1=Lena Tauchner, authors.2=Jane Doe".to_string()
+    )));
+    assert!(flattened_metadata.contains(&("authors.count".to_string(), "2".to_string())));
+    assert!(
+        flattened_metadata.contains(&("series.name".to_string(), "series.name=Dreams".to_string()))
+    );
+}
+
+#[rstest]
+#[case("--title={{title}}", "--title=Dream")]
+#[case("--edition={{edition}}", "--edition=2")]
+#[case("--author={{authors}}", "--author=Lena Tauchner, Jane Doe")]
+#[case("--first-author={{authors.1}}", "--first-author=Lena Tauchner")]
+#[case("--series={{series.name}} {{series.number}}", "--series=Dreams 1")]
+#[case("--isbn={{isbn.1.number}}", "--isbn=978-3-16-148410-0")]
+#[case("--release={{release}}", "--release=2025-03-01")]
+#[case("--unknown={{unknown}}", "--unknown={{unknown}}")]
+fn test_preprocess_cli_args(#[case] arg: &str, #[case] expected: &str) {
+    let metadata_fields: Table = toml::from_str(METADATA).expect("Failed to parse metadata");
+
+    let processed_args = preprocess_cli_args(&[arg.to_string()], &metadata_fields);
+
+    assert_eq!(processed_args, vec![expected.to_string()]);
+}
+
+#[rstest]
+fn test_normalize_metadata() {
+    let metadata_fields: Table = toml::from_str(METADATA).expect("Failed to parse metadata");
+
+    let normalized_metadata = normalize_metadata(&metadata_fields);
+
+    assert_eq!(
+        normalized_metadata.get("release"),
+        Some(&toml::Value::String("2025-03-01".to_string()))
+    );
+    assert_eq!(
+        serde_yaml::to_string(&normalized_metadata["series"]).expect("Failed to serialize"),
+        "name: Dreams\nnumber: 1\n"
+    );
+}
@@ -0,0 +1,127 @@
+use rstest::rstest;
+use std::{fs, process::Command};
+use tempfile::tempdir;
+
+use crate::{
+    _tests::tests_common::{get_default_conversion_meta, get_default_template},
+    converters::{
+        common::add_lua_filters,
+        conditional_content::{CONDITIONAL_CONTENT_FILTER, write_conditional_content_filter},
+        conversion_meta::ConversionMeta,
+    },
+    manifest_model::Template,
+    template_type::TemplateType,
+};
+
+fn get_args(pandoc: &Command) -> Vec<String> {
+    pandoc
+        .get_args()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect()
+}
+
+#[rstest]
+#[case(Some("ebook"), vec![
+    ("tiefdown-markdown-project", "Book"),
+    ("tiefdown-template", "ebook"),
+    ("tiefdown-template-type", "Epub"),
+    ("tiefdown-profile", "ebook"),
+])]
+#[case(None, vec![
+    ("tiefdown-markdown-project", "Book"),
+    ("tiefdown-template", "ebook"),
+    ("tiefdown-template-type", "Epub"),
+])]
+fn test_get_metadata_fields(#[case] profile: Option<&str>, #[case] expected: Vec<(&str, &str)>) {
+    let conversion_meta = ConversionMeta {
+        profile: profile.map(str::to_string),
+        ..get_default_conversion_meta("Book", "ebook", TemplateType::Epub)
+    };
+    let metadata_fields = conversion_meta.get_metadata_fields();
+
+    assert_eq!(
+        metadata_fields
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[rstest]
+fn test_add_lua_filters_adds_conditional_content_filter_first() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    fs::write(temp_dir.path().join("filter.lua"), "").expect("Failed to write filter");
+
+    let mut pandoc = Command::new("pandoc");
+    add_lua_filters(
+        &Template {
+            filters: Some(vec!["filter.lua".to_string()]),
+            ..get_default_template("ebook", TemplateType::Epub)
+        },
+        &ConversionMeta {
+            profile: Some("ebook".to_string()),
+            ..get_default_conversion_meta("Book", "ebook", TemplateType::Epub)
+        },
+        temp_dir.path(),
+        &mut pandoc,
+    )
+    .expect("Failed to add lua filters");
+
+    assert_eq!(
+        get_args(&pandoc),
+        vec![
+            "--lua-filter",
+            ".tiefdown_conditional_content.lua",
+            "-M",
+            "tiefdown-markdown-project=Book",
+            "-M",
+            "tiefdown-template=ebook",
+            "-M",
+            "tiefdown-template-type=Epub",
+            "-M",
+            "tiefdown-profile=ebook",
+            "--lua-filter",
+            "filter.lua",
+        ]
+    );
+    assert!(
+        !temp_dir
+            .path()
+            .join(".tiefdown_conditional_content.lua")
+            .exists()
+    );
+}
+
+#[rstest]
+fn test_add_lua_filters_without_template_filters() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let mut pandoc = Command::new("pandoc");
+    add_lua_filters(
+        &get_default_template("ebook", TemplateType::Epub),
+        &get_default_conversion_meta("Book", "ebook", TemplateType::Epub),
+        temp_dir.path(),
+        &mut pandoc,
+    )
+    .expect("Failed to add lua filters");
+
+    assert_eq!(
+        get_args(&pandoc)[..2],
+        ["--lua-filter", ".tiefdown_conditional_content.lua"]
+    );
+}
+
+#[rstest]
+fn test_write_conditional_content_filter() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let filter_path = temp_dir.path().join(".tiefdown_conditional_content.lua");
+    fs::write(&filter_path, "-- outdated").expect("Failed to write filter");
+
+    write_conditional_content_filter(temp_dir.path()).expect("Failed to write filter");
+
+    assert_eq!(
+        fs::read_to_string(&filter_path).expect("Failed to read filter"),
+        CONDITIONAL_CONTENT_FILTER
+    );
+}
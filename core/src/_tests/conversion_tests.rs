@@ -0,0 +1,316 @@
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+use crate::_tests::tests_common::get_default_markdown_project;
+use crate::conversion::{
+    ConversionTask, get_affected_conversion_tasks, get_conversion_plan, get_conversion_queue,
+    group_conversion_tasks,
+};
+use crate::manifest_model::MarkdownProject;
+use crate::template_type::TemplateType;
+
+const MANIFEST: &str = r#"version = 6
+
+[[markdown_projects]]
+name = "Dream"
+path = "Dream"
+output = "Dream"
+resources = ["cover.png"]
+
+[[markdown_projects]]
+name = "Reality"
+path = "Reality"
+output = "Reality"
+exclude = ["notes/"]
+
+[[templates]]
+name = "pdf"
+template_type = "Tex"
+template_file = "pdf.tex"
+filters = ["filters/pdf.lua"]
+
+[[templates]]
+name = "epub"
+template_type = "Epub"
+header_injections = ["copyright"]
+
+[custom_processors]
+preprocessors = []
+processors = []
+
+[[injections]]
+name = "copyright"
+files = ["copyright.html"]
+"#;
+
+fn create_project(temp_dir: &Path) -> PathBuf {
+    let project_path = temp_dir.join("project");
+
+    fs::create_dir_all(project_path.join("Dream")).expect("Failed to create markdown directory");
+    fs::create_dir_all(project_path.join("Reality")).expect("Failed to create markdown directory");
+    fs::create_dir_all(project_path.join("template/filters"))
+        .expect("Failed to create template directory");
+    fs::write(project_path.join("manifest.toml"), MANIFEST).expect("Failed to write manifest");
+
+    project_path
+}
+
+fn get_affected_task_names(project_path: &Path, changed_paths: &[&str]) -> Vec<String> {
+    let queue = get_conversion_queue(Some(project_path.to_path_buf()), None, None, None)
+        .expect("Failed to create conversion queue");
+    let changed_paths: Vec<PathBuf> = changed_paths.iter().map(PathBuf::from).collect();
+
+    get_affected_conversion_tasks(project_path, &queue, &changed_paths)
+        .expect("Failed to get affected conversion tasks")
+        .iter()
+        .map(|task| format!("{}/{}", task.markdown_project.name, task.template))
+        .collect()
+}
+
+#[rstest]
+#[case(vec!["Dream/Chapter 1.md"], vec!["Dream/pdf", "Dream/epub"])]
+#[case(vec!["Reality/Chapter 1.md"], vec!["Reality/pdf", "Reality/epub"])]
+#[case(vec!["Dream/cover.png"], vec!["Dream/pdf", "Dream/epub"])]
+#[case(vec!["template/pdf.tex"], vec!["Dream/pdf", "Reality/pdf"])]
+#[case(vec!["template/filters/pdf.lua"], vec!["Dream/pdf", "Reality/pdf"])]
+#[case(vec!["template/copyright.html"], vec!["Dream/epub", "Reality/epub"])]
+#[case(vec!["template/shared.png"], vec!["Dream/pdf", "Dream/epub", "Reality/pdf", "Reality/epub"])]
+#[case(vec!["manifest.toml"], vec!["Dream/pdf", "Dream/epub", "Reality/pdf", "Reality/epub"])]
+#[case(vec!["Reality/Chapter 1.md", "template/pdf.tex"], vec!["Dream/pdf", "Reality/pdf", "Reality/epub"])]
+#[case(vec!["2025-01-01_12-00-00/Dream/pdf.pdf"], vec![])]
+#[case(vec!["README.md"], vec![])]
+#[case(vec!["Reality/notes/Idea.md"], vec![])]
+#[case(vec!["Reality/.tiefdownignore"], vec!["Reality/pdf", "Reality/epub"])]
+fn test_get_affected_conversion_tasks(
+    #[case] changed_paths: Vec<&str>,
+    #[case] expected_tasks: Vec<&str>,
+) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+
+    let affected_tasks = get_affected_task_names(&project_path, &changed_paths);
+
+    assert_eq!(affected_tasks, expected_tasks);
+}
+
+#[rstest]
+fn test_get_affected_conversion_tasks_with_absolute_paths() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+    fs::write(project_path.join("Dream/Chapter 1.md"), "# Chapter 1")
+        .expect("Failed to write markdown file");
+
+    let changed_path = project_path
+        .join("Dream/Chapter 1.md")
+        .canonicalize()
+        .expect("Failed to canonicalize path");
+
+    let affected_tasks = get_affected_task_names(&project_path, &[changed_path.to_str().unwrap()]);
+
+    assert_eq!(affected_tasks, vec!["Dream/pdf", "Dream/epub"]);
+}
+
+#[rstest]
+fn test_get_conversion_plan() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+    fs::write(project_path.join("Dream/Chapter 1.md"), "# Chapter 1")
+        .expect("Failed to write markdown file");
+    fs::write(project_path.join("Dream/Chapter 2.tex"), "Chapter 2")
+        .expect("Failed to write tex file");
+    fs::write(project_path.join("Dream/cover.png"), "").expect("Failed to write resource");
+    fs::write(project_path.join("template/pdf.tex"), "").expect("Failed to write template");
+    fs::write(project_path.join("template/filters/pdf.lua"), "").expect("Failed to write filter");
+    fs::write(
+        project_path.join("template/copyright.html"),
+        "<p>Copyright</p>",
+    )
+    .expect("Failed to write injection");
+    fs::create_dir_all(project_path.join("template/epub"))
+        .expect("Failed to create template directory");
+
+    let queue = get_conversion_queue(
+        Some(project_path.clone()),
+        None,
+        None,
+        Some(vec!["Dream".to_string()]),
+    )
+    .expect("Failed to create conversion queue");
+
+    let plans = get_conversion_plan(Some(project_path.clone()), queue, None)
+        .expect("Failed to get conversion plan");
+
+    assert_eq!(plans.len(), 2);
+
+    let pdf_plan = &plans[0];
+    assert_eq!(pdf_plan.template, "pdf");
+    assert_eq!(pdf_plan.converter, TemplateType::Tex);
+    assert_eq!(
+        pdf_plan
+            .preprocessors
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["default_tex_preprocessor", "default_tex_preprocessor"]
+    );
+    assert_eq!(
+        pdf_plan.input_files,
+        vec![
+            PathBuf::from("Dream/pdf_convdir/Chapter 1.md"),
+            PathBuf::from("Dream/pdf_convdir/Chapter 2.tex"),
+        ]
+    );
+    assert_eq!(
+        pdf_plan
+            .chunks
+            .iter()
+            .map(|c| c.extension.as_str())
+            .collect::<Vec<_>>(),
+        vec!["md", "tex"]
+    );
+    assert_eq!(
+        pdf_plan
+            .commands
+            .iter()
+            .map(|c| c.command_line.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "pandoc -t latex --lua-filter .tiefdown_conditional_content.lua -M tiefdown-markdown-project=Dream -M tiefdown-template=pdf -M tiefdown-template-type=Tex --lua-filter filters/pdf.lua 'pdf_convdir/Chapter 1.md'",
+            "cat 'pdf_convdir/Chapter 2.tex'",
+            "xelatex -interaction=nonstopmode -synctex=1 pdf.tex",
+        ]
+    );
+    assert_eq!(
+        pdf_plan.commands[0].working_directory,
+        Some(PathBuf::from("Dream"))
+    );
+    assert_eq!(pdf_plan.output_path, Some(PathBuf::from("Dream/pdf.pdf")));
+
+    let epub_plan = &plans[1];
+    assert_eq!(epub_plan.template, "epub");
+    assert_eq!(epub_plan.converter, TemplateType::Epub);
+    assert_eq!(
+        epub_plan.header_injections,
+        vec![PathBuf::from("Dream/copyright.html")]
+    );
+    assert_eq!(
+        epub_plan.output_path,
+        Some(PathBuf::from("Dream/epub.epub"))
+    );
+
+    assert!(
+        !project_path.join("Dream/pdf.pdf").exists(),
+        "Dry run should not write outputs"
+    );
+}
+
+#[rstest]
+#[case("", vec!["xelatex -interaction=nonstopmode -synctex=1 pdf.tex"])]
+#[case(
+    "engine = \"Lualatex\"",
+    vec!["lualatex -interaction=nonstopmode -synctex=1 pdf.tex"]
+)]
+#[case(
+    "engine = \"Pdflatex\"\ntools = [\"Bibtex\", \"Makeindex\"]",
+    vec![
+        "pdflatex -interaction=nonstopmode -synctex=1 pdf.tex",
+        "bibtex pdf",
+        "makeindex pdf.idx",
+        "pdflatex -interaction=nonstopmode -synctex=1 pdf.tex",
+    ]
+)]
+#[case(
+    "engine = \"Lualatex\"\nlatexmk = true\ntools = [\"Biber\"]",
+    vec!["latexmk -lualatex -interaction=nonstopmode -synctex=1 pdf.tex"]
+)]
+fn test_get_conversion_plan_latex_settings(
+    #[case] latex_settings: &str,
+    #[case] expected_commands: Vec<&str>,
+) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project_path = create_project(temp_dir.path());
+    let manifest = MANIFEST.replace(
+        "filters = [\"filters/pdf.lua\"]\n",
+        &format!("[templates.latex]\n{}\n", latex_settings),
+    );
+    fs::write(project_path.join("manifest.toml"), manifest).expect("Failed to write manifest");
+    fs::write(project_path.join("Dream/Chapter 1.tex"), "Chapter 1")
+        .expect("Failed to write tex file");
+    fs::write(project_path.join("Dream/cover.png"), "").expect("Failed to write resource");
+    fs::write(project_path.join("template/pdf.tex"), "").expect("Failed to write template");
+
+    let queue = get_conversion_queue(
+        Some(project_path.clone()),
+        Some(vec!["pdf".to_string()]),
+        None,
+        Some(vec!["Dream".to_string()]),
+    )
+    .expect("Failed to create conversion queue");
+
+    let plans = get_conversion_plan(Some(project_path.clone()), queue, None)
+        .expect("Failed to get conversion plan");
+
+    assert_eq!(plans.len(), 1);
+    assert_eq!(
+        plans[0]
+            .commands
+            .iter()
+            .skip(1)
+            .map(|c| c.command_line.as_str())
+            .collect::<Vec<_>>(),
+        expected_commands
+    );
+}
+
+#[rstest]
+fn test_group_conversion_tasks_normalizes_outputs() {
+    let create_task = |name: &str, output: &str| ConversionTask {
+        markdown_project: MarkdownProject {
+            output: PathBuf::from(output),
+            ..get_default_markdown_project(name)
+        },
+        template: "pdf".to_string(),
+        profile: None,
+    };
+
+    let queue = vec![
+        create_task("One", "./out"),
+        create_task("Two", "other"),
+        create_task("Three", "out"),
+        create_task("Four", "other/../out/"),
+    ];
+
+    assert_eq!(
+        group_conversion_tasks(Path::new("./build"), &queue),
+        vec![vec![0, 2, 3], vec![1]]
+    );
+}
+
+#[rstest]
+#[case(vec!["sub", "other", "."], vec![vec![0, 1, 2]])]
+#[case(vec!["a/b", "c", "a"], vec![vec![0, 2], vec![1]])]
+#[case(vec!["a/x", "b", "a/y", "a"], vec![vec![0, 2, 3], vec![1]])]
+#[case(vec!["ab", "a"], vec![vec![0], vec![1]])]
+fn test_group_conversion_tasks_groups_nested_outputs(
+    #[case] outputs: Vec<&str>,
+    #[case] expected: Vec<Vec<usize>>,
+) {
+    let queue = outputs
+        .iter()
+        .enumerate()
+        .map(|(index, output)| ConversionTask {
+            markdown_project: MarkdownProject {
+                output: PathBuf::from(output),
+                ..get_default_markdown_project(&index.to_string())
+            },
+            template: "pdf".to_string(),
+            profile: None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(group_conversion_tasks(Path::new("build"), &queue), expected);
+}
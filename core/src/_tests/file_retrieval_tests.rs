@@ -0,0 +1,230 @@
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+use crate::{
+    file_retrieval::{FileOrder, get_file_order_warnings, get_sorted_files, read_file_order},
+    injections::RenderingInjections,
+    input_filter::InputFilter,
+};
+
+fn create_files(dir: &Path, files: &[&str]) {
+    for file in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).expect("Failed to create directory");
+        fs::write(path, "# Chapter\n").expect("Failed to write file");
+    }
+}
+
+fn get_sorted_file_names(dir: &Path) -> Vec<PathBuf> {
+    get_sorted_files(dir, dir, dir, &RenderingInjections::new(), false)
+        .expect("Failed to get sorted files")
+}
+
+#[rstest]
+fn test_get_sorted_files_by_order_number() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let dir = temp_dir.path();
+    create_files(
+        dir,
+        &[
+            "Chapter 10.md",
+            "Chapter 2.md",
+            "Chapter 1.md",
+            "Chapter 1/Section 2.md",
+            "Chapter 1/Section 1.md",
+        ],
+    );
+
+    assert_eq!(
+        get_sorted_file_names(dir),
+        vec![
+            PathBuf::from("Chapter 1.md"),
+            PathBuf::from("Chapter 1/Section 1.md"),
+            PathBuf::from("Chapter 1/Section 2.md"),
+            PathBuf::from("Chapter 2.md"),
+            PathBuf::from("Chapter 10.md"),
+        ]
+    );
+}
+
+#[rstest]
+#[case(vec![(
+    "order.toml",
+    "order = [\"Prologue.md\", \"1984 revisited.md\", \"Part 2\", \"Chapter 1.md\"]\n"
+)])]
+#[case(vec![
+    ("order.toml", "summary = \"SUMMARY.md\"\n"),
+    (
+        "SUMMARY.md",
+        "# Summary\n\n- [Prologue](Prologue.md)\n- [1984 Revisited](<1984 revisited.md>)\n- Part 2\n  - [Ending](Part%202/Ending.md)\n- [One](./Chapter 1.md)\n"
+    ),
+])]
+fn test_get_sorted_files_with_order_file(#[case] order_files: Vec<(&str, &str)>) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let dir = temp_dir.path();
+    create_files(
+        dir,
+        &[
+            "Chapter 1.md",
+            "1984 revisited.md",
+            "Prologue.md",
+            "Part 2/Ending.md",
+            "Unlisted 0.md",
+        ],
+    );
+    for (order_file, content) in order_files {
+        fs::write(dir.join(order_file), content).expect("Failed to write ordering file");
+    }
+
+    assert_eq!(
+        get_sorted_file_names(dir),
+        vec![
+            PathBuf::from("Prologue.md"),
+            PathBuf::from("1984 revisited.md"),
+            PathBuf::from("Part 2/Ending.md"),
+            PathBuf::from("Chapter 1.md"),
+            PathBuf::from("Unlisted 0.md"),
+        ]
+    );
+}
+
+#[rstest]
+fn test_get_sorted_files_converts_summary_without_order_file() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let dir = temp_dir.path();
+    create_files(dir, &["Chapter 1.md"]);
+    fs::write(dir.join("SUMMARY.md"), "- Chapter 1.md\n").expect("Failed to write file");
+
+    assert_eq!(
+        get_sorted_file_names(dir),
+        vec![PathBuf::from("SUMMARY.md"), PathBuf::from("Chapter 1.md")]
+    );
+}
+
+#[rstest]
+fn test_get_sorted_files_with_nested_order() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let dir = temp_dir.path();
+    create_files(
+        dir,
+        &["Part 1/B.md", "Part 1/A.md", "Part 2/B.md", "Part 2/A.md"],
+    );
+    fs::write(
+        dir.join("order.toml"),
+        "order = [\"Part 2\", \"Part 1/B.md\", \"Part 1/A.md\"]\n",
+    )
+    .expect("Failed to write ordering file");
+    fs::write(
+        dir.join("Part 2").join("order.toml"),
+        "order = [\"B.md\"]\n",
+    )
+    .expect("Failed to write ordering file");
+
+    assert_eq!(
+        get_sorted_file_names(dir),
+        vec![
+            PathBuf::from("Part 2/B.md"),
+            PathBuf::from("Part 2/A.md"),
+            PathBuf::from("Part 1/B.md"),
+            PathBuf::from("Part 1/A.md"),
+        ]
+    );
+}
+
+#[rstest]
+fn test_read_file_order_summary() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let dir = temp_dir.path();
+    fs::write(dir.join("SUMMARY.md"), "- B.md\n").expect("Failed to write file");
+
+    assert_eq!(
+        read_file_order(dir).expect("Failed to read file order"),
+        None
+    );
+
+    fs::write(dir.join("order.toml"), "summary = \"SUMMARY.md\"\n").expect("Failed to write file");
+
+    assert_eq!(
+        read_file_order(dir).expect("Failed to read file order"),
+        Some(FileOrder {
+            path: dir.join("order.toml"),
+            summary: Some(dir.join("SUMMARY.md")),
+            entries: vec![PathBuf::from("B.md")],
+        })
+    );
+}
+
+#[rstest]
+#[case("order = \"A.md\"\n")]
+#[case("order = [\"A.md\"]\nsummary = \"SUMMARY.md\"\n")]
+#[case("summary = \"Missing.md\"\n")]
+#[case("")]
+fn test_read_file_order_invalid(#[case] content: &str) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let dir = temp_dir.path();
+    fs::write(dir.join("SUMMARY.md"), "- A.md\n").expect("Failed to write file");
+    fs::write(dir.join("order.toml"), content).expect("Failed to write file");
+
+    assert!(read_file_order(dir).is_err());
+}
+
+#[rstest]
+fn test_get_file_order_warnings_without_order_file() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let dir = temp_dir.path();
+    create_files(
+        dir,
+        &[
+            "Chapter 1.md",
+            "Chapter 1 - Again.md",
+            "Chapter 2 - 1984 revisited.md",
+            "Epilogue.md",
+            "Chapter 1/Section 1.md",
+            "resources/image.md",
+        ],
+    );
+
+    let warnings =
+        get_file_order_warnings(dir, &[PathBuf::from("resources")], &InputFilter::default())
+            .expect("Failed to get file order warnings");
+
+    assert_eq!(
+        warnings,
+        vec![
+            "'Chapter 2 - 1984 revisited.md' contains more than one number. Its order number is 2."
+                .to_string(),
+            "'Epilogue.md' has no order number and is sorted as 0.".to_string(),
+            "'Chapter 1 - Again.md', 'Chapter 1.md' share the order number 1.".to_string(),
+        ]
+    );
+}
+
+#[rstest]
+fn test_get_file_order_warnings_with_order_file() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let dir = temp_dir.path();
+    create_files(dir, &["A.md", "B.md", "Part 1/C.md", "Part 1/D.md"]);
+    fs::write(
+        dir.join("order.toml"),
+        "order = [\"A.md\", \"Missing.md\", \"A.md\", \"Part 1/C.md\"]\n",
+    )
+    .expect("Failed to write ordering file");
+
+    let warnings = get_file_order_warnings(dir, &[], &InputFilter::default())
+        .expect("Failed to get file order warnings");
+
+    assert_eq!(
+        warnings,
+        vec![
+            "'Missing.md' is listed in the ordering file 'order.toml', but does not exist."
+                .to_string(),
+            "'A.md' is listed more than once in the ordering file 'order.toml'.".to_string(),
+            "'B.md' is not listed in the ordering file 'order.toml'.".to_string(),
+            "'Part 1/D.md' is not listed in the ordering file 'order.toml'.".to_string(),
+        ]
+    );
+}
@@ -0,0 +1,141 @@
+use rstest::rstest;
+use std::{fs, path::PathBuf};
+use tempfile::tempdir;
+use toml::Table;
+
+use crate::{
+    _tests::tests_common::get_default_template,
+    front_matter::{
+        is_excluded_by_front_matter, parse_front_matter, read_front_matter, remove_excluded_files,
+    },
+    manifest_model::NavTitleSource,
+    nav_meta_generation::retrieve_nav_meta,
+    template_type::TemplateType,
+};
+
+fn table(content: &str) -> Table {
+    toml::from_str(content).expect("Failed to parse table")
+}
+
+#[rstest]
+#[case(
+    "---\ntitle: One\ntags: [a, b]\n---\n# One\n",
+    Some("title = \"One\"\ntags = [\"a\", \"b\"]")
+)]
+#[case("---\ntitle: One\n...\n# One\n", Some("title = \"One\""))]
+#[case("---\r\ntitle: One\r\n---\r\n# One\r\n", Some("title = \"One\""))]
+#[case("---\n---\n# One\n", None)]
+#[case("# One\n---\ntitle: One\n---\n", None)]
+#[case("---\ntitle: One\n", None)]
+#[case("---\nJust a rule\n---\n", None)]
+fn test_parse_front_matter(#[case] content: &str, #[case] expected: Option<&str>) {
+    assert_eq!(parse_front_matter(content), expected.map(table));
+}
+
+#[rstest]
+#[case(b"---\ndraft: true\n---\n\xff\xfe".to_vec(), Some("draft = true"))]
+#[case(b"---\ndraft: \xff\n---\n# One\n".to_vec(), None)]
+#[case(b"\xff\xd8\xff---\n".to_vec(), None)]
+#[case(b"----\ndraft: true\n---\n".to_vec(), None)]
+#[case(b"---\ndraft: true\n".to_vec(), None)]
+fn test_read_front_matter(#[case] content: Vec<u8>, #[case] expected: Option<&str>) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let path = temp_dir.path().join("Chapter 1.md");
+    fs::write(&path, content).expect("Failed to write file");
+
+    assert_eq!(
+        read_front_matter(&path).expect("Failed to read front matter"),
+        expected.map(table)
+    );
+}
+
+#[rstest]
+#[case("draft = true", "epub", TemplateType::Epub, true)]
+#[case("draft = false", "epub", TemplateType::Epub, false)]
+#[case("exclude_from = [\"epub\"]", "epub", TemplateType::Epub, true)]
+#[case("exclude_from = \"epub\"", "epub", TemplateType::Epub, true)]
+#[case("exclude_from = [\"epub\"]", "pdf", TemplateType::Tex, false)]
+#[case("exclude_from = [\"tex\"]", "pdf", TemplateType::Tex, true)]
+#[case("exclude_from = [\"Print\"]", "print", TemplateType::Tex, false)]
+#[case("title = \"One\"", "pdf", TemplateType::Tex, false)]
+fn test_is_excluded_by_front_matter(
+    #[case] front_matter: &str,
+    #[case] template_name: &str,
+    #[case] template_type: TemplateType,
+    #[case] expected: bool,
+) {
+    let template = get_default_template(template_name, template_type);
+
+    assert_eq!(
+        is_excluded_by_front_matter(&table(front_matter), &template),
+        expected
+    );
+}
+
+#[rstest]
+fn test_remove_excluded_files() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let input_dir = temp_dir.path();
+    fs::create_dir(input_dir.join("Part 1")).expect("Failed to create directory");
+    fs::write(input_dir.join("Chapter 1.md"), "# One\n").expect("Failed to write file");
+    fs::write(
+        input_dir.join("Chapter 2.md"),
+        "---\ndraft: true\n---\n# Two\n",
+    )
+    .expect("Failed to write file");
+    fs::write(
+        input_dir.join("Part 1").join("Chapter 3.md"),
+        "---\nexclude_from: [epub]\n---\n# Three\n",
+    )
+    .expect("Failed to write file");
+    fs::write(input_dir.join("cover.png"), [0xff, 0xd8, 0xff]).expect("Failed to write file");
+
+    remove_excluded_files(input_dir, &get_default_template("epub", TemplateType::Epub))
+        .expect("Failed to remove excluded files");
+
+    assert!(input_dir.join("Chapter 1.md").exists());
+    assert!(!input_dir.join("Chapter 2.md").exists());
+    assert!(!input_dir.join("Part 1").join("Chapter 3.md").exists());
+    assert!(input_dir.join("cover.png").exists());
+}
+
+#[rstest]
+fn test_retrieve_nav_meta_front_matter() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let compiled_directory_path = temp_dir.path();
+    let conversion_input_dir = compiled_directory_path.join("html_convdir");
+    fs::create_dir(&conversion_input_dir).expect("Failed to create directory");
+    fs::write(
+        conversion_input_dir.join("Chapter 1.md"),
+        "---\ntitle: The Beginning\nsubtitle: Of It All\nrelease: 2025-03-01\n---\n# One\n",
+    )
+    .expect("Failed to write file");
+    fs::write(conversion_input_dir.join("Chapter 2.md"), "# Two\n").expect("Failed to write file");
+
+    let nav_meta = retrieve_nav_meta(
+        &vec![
+            PathBuf::from("html_convdir/Chapter 1.md"),
+            PathBuf::from("html_convdir/Chapter 2.md"),
+        ],
+        compiled_directory_path,
+        &conversion_input_dir,
+        &Some("html".to_string()),
+        NavTitleSource::Path,
+        true,
+    )
+    .expect("Failed to retrieve nav meta");
+
+    let nodes = nav_meta.nodes.expect("Nav meta should contain nodes");
+
+    assert_eq!(nodes[0].title, "The Beginning");
+    assert_eq!(
+        nodes[0].metadata,
+        table("subtitle = \"Of It All\"\nrelease = \"2025-03-01\"")
+    );
+    assert_eq!(nodes[1].title, "Chapter 2");
+    assert!(nodes[1].metadata.is_empty());
+
+    let nav_meta_yaml =
+        serde_yaml::to_string(&nodes[1]).expect("Failed to serialize nav meta node");
+    assert!(!nav_meta_yaml.contains("metadata"));
+}
@@ -0,0 +1,112 @@
+use rstest::rstest;
+use std::{fs, path::Path};
+use tempfile::tempdir;
+
+use crate::{
+    _tests::tests_common::get_default_markdown_project,
+    input_filter::{InputFilter, copy_filtered_files},
+    manifest_model::MarkdownProject,
+};
+
+fn create_input_filter(
+    include: Option<Vec<&str>>,
+    exclude: Option<Vec<&str>>,
+    ignore_file: Option<&str>,
+) -> InputFilter {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    if let Some(ignore_file) = ignore_file {
+        fs::write(temp_dir.path().join(".tiefdownignore"), ignore_file)
+            .expect("Failed to write ignore file");
+    }
+
+    let to_strings = |patterns: Vec<&str>| {
+        patterns
+            .into_iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+    };
+    let markdown_project = MarkdownProject {
+        include: include.map(to_strings),
+        exclude: exclude.map(to_strings),
+        ..get_default_markdown_project("Markdown")
+    };
+
+    InputFilter::new(&markdown_project, temp_dir.path()).expect("Failed to create input filter")
+}
+
+#[rstest]
+#[case("Chapter 1.md", false, true)]
+#[case(".DS_Store", false, false)]
+#[case("Part 1/.DS_Store", false, false)]
+#[case(".Chapter 1.md.swp", false, false)]
+#[case("Chapter 1.md~", false, false)]
+#[case("notes", true, false)]
+#[case("notes/Idea.md", false, false)]
+#[case("Part 1/notes/Idea.md", false, false)]
+#[case("notes.md", false, true)]
+#[case("drafts/Keep.md", false, true)]
+#[case("drafts/Draft.md", false, false)]
+#[case("Part 1/drafts/Draft.md", false, true)]
+#[case("Part 1/Important.tmp", false, true)]
+#[case("Scratch.tmp", false, false)]
+#[case(".tiefdownignore", false, false)]
+fn test_input_filter_exclude(#[case] path: &str, #[case] is_dir: bool, #[case] expected: bool) {
+    let input_filter = create_input_filter(
+        None,
+        Some(vec![".DS_Store", "*.swp"]),
+        Some(
+            "# Editor files\n*~\n\nnotes/\n/drafts/*\n!/drafts/Keep.md\n*.tmp\n!**/Important.tmp\n",
+        ),
+    );
+
+    assert_eq!(input_filter.is_included(Path::new(path), is_dir), expected);
+}
+
+#[rstest]
+#[case("Chapter 1.md", false, true)]
+#[case("Part 1/Chapter 2.md", false, true)]
+#[case("Part 1", true, true)]
+#[case("Chapter 1.txt", false, false)]
+#[case("Chapter 1.draft.md", false, false)]
+#[case("cover.png", false, false)]
+fn test_input_filter_include(#[case] path: &str, #[case] is_dir: bool, #[case] expected: bool) {
+    let input_filter = create_input_filter(Some(vec!["*.md"]), Some(vec!["*.draft.md"]), None);
+
+    assert_eq!(input_filter.is_included(Path::new(path), is_dir), expected);
+}
+
+#[rstest]
+fn test_copy_filtered_files() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let markdown_dir = temp_dir.path().join("Markdown");
+    let dir = temp_dir.path().join("convdir");
+
+    for file in [
+        "Chapter 1.md",
+        ".DS_Store",
+        ".tiefdownignore",
+        "notes/Idea.md",
+        "Part 1/Chapter 2.md",
+        "Part 1/Chapter 2.md.swp",
+        "Part 2/Chapter 3.md.swp",
+    ] {
+        let path = markdown_dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).expect("Failed to create directory");
+        fs::write(path, "").expect("Failed to write file");
+    }
+
+    let input_filter =
+        create_input_filter(None, Some(vec![".DS_Store", "*.swp"]), Some("notes/\n"));
+
+    copy_filtered_files(&markdown_dir, &dir, &input_filter).expect("Failed to copy filtered files");
+
+    assert!(dir.join("Chapter 1.md").exists());
+    assert!(dir.join("Part 1/Chapter 2.md").exists());
+    assert!(!dir.join(".DS_Store").exists());
+    assert!(!dir.join(".tiefdownignore").exists());
+    assert!(!dir.join("notes").exists());
+    assert!(!dir.join("Part 1/Chapter 2.md.swp").exists());
+    assert!(!dir.join("Part 2").exists());
+    assert!(markdown_dir.join("Part 2/Chapter 3.md.swp").exists());
+}
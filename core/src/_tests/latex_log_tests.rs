@@ -0,0 +1,258 @@
+use rstest::rstest;
+use std::{fs, path::PathBuf};
+use tempfile::tempdir;
+
+use crate::{
+    build_report::WarningKind,
+    conversion_recorder::ConversionRecorder,
+    converters::latex_log::{LatexIssueKind, check_latex_log, needs_rerun, parse_latex_log},
+};
+
+const OUTPUT_TEX: &str = r#"\section{Introduction}
+
+\foo{bar} baz
+
+See \ref{sec:intro}.
+"#;
+
+const LOG: &str = r#"This is XeTeX, Version 3.141592653-2.6-0.999995 (TeX Live 2023) (preloaded format=xelatex)
+entering extended mode
+**template.tex
+(./template.tex
+LaTeX2e <2022-11-01> patch level 1
+(/usr/share/texlive/texmf-dist/tex/latex/base/article.cls
+Document Class: article 2022/07/02 v1.4n Standard LaTeX document class
+)
+(./output.tex
+! Undefined control sequence.
+l.3 \foo
+        {bar} baz
+The control sequence at the end of the top line
+of your error message was never \def'ed.
+
+
+LaTeX Warning: Reference `sec:intro' on page 1 undefined on input line 5.
+
+Overfull \hbox (12.34pt too wide) in paragraph at lines 7--8
+[]\TU/lmr/m/n/10 Averyveryverylongword|
+
+Missing character: There is no ✓ in font lmroman10-regular!
+)
+LaTeX Font Warning: Font shape `TU/lmr/b/sc' undefined
+(Font)              using `TU/lmr/b/n' instead on input line 20.
+
+[1] (./template.aux) )
+Output written on template.pdf (1 page).
+"#;
+
+const LOG_WITHOUT_ERRORS: &str = r#"(./template.tex
+(./output.tex
+
+LaTeX Warning: Citation `doe2020' on page 1 undefined on input line 2.
+
+)
+[1] (./template.aux) )
+"#;
+
+#[rstest]
+fn test_parse_latex_log() {
+    let issues = parse_latex_log(LOG).expect("Failed to parse log");
+
+    let issues = issues
+        .iter()
+        .map(|issue| {
+            (
+                issue.kind,
+                issue.message.as_str(),
+                issue.file.clone(),
+                issue.line,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        issues,
+        vec![
+            (
+                LatexIssueKind::FatalError,
+                "Undefined control sequence.",
+                Some(PathBuf::from("output.tex")),
+                Some(3)
+            ),
+            (
+                LatexIssueKind::UndefinedReference,
+                "Reference `sec:intro' undefined.",
+                Some(PathBuf::from("output.tex")),
+                Some(5)
+            ),
+            (
+                LatexIssueKind::OverfullBox,
+                r"Overfull \hbox (12.34pt too wide) in paragraph",
+                Some(PathBuf::from("output.tex")),
+                Some(7)
+            ),
+            (
+                LatexIssueKind::MissingFont,
+                "Missing character: There is no ✓ in font lmroman10-regular!",
+                Some(PathBuf::from("output.tex")),
+                None
+            ),
+            (
+                LatexIssueKind::MissingFont,
+                "Font shape `TU/lmr/b/sc' undefined, using `TU/lmr/b/n' instead on input line 20.",
+                Some(PathBuf::from("template.tex")),
+                Some(20)
+            ),
+        ]
+    );
+}
+
+#[rstest]
+fn test_parse_latex_log_error_source_line() {
+    let issues = parse_latex_log(LOG).expect("Failed to parse log");
+
+    assert_eq!(issues[0].source_line, Some(r"\foo".to_string()));
+}
+
+#[rstest]
+#[case("LaTeX Warning: Citation `a-rather-long-citation-key' on page 1 undefined on input line 9.")]
+#[case(
+    "LaTeX Warning: Reference `fig:a-rather-long-figure-label' on page 12 undefined on input line 9."
+)]
+fn test_parse_latex_log_wrapped_lines(#[case] warning: &str) {
+    let (first, second) = warning.split_at(79);
+    let log = format!("(./output.tex\n{}\n{}\n)\n", first, second);
+
+    let issues = parse_latex_log(&log).expect("Failed to parse log");
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, LatexIssueKind::UndefinedReference);
+    assert_eq!(issues[0].line, Some(9));
+}
+
+#[rstest]
+fn test_parse_latex_log_duplicate_issues() {
+    let log = format!("{}{}", LOG_WITHOUT_ERRORS, LOG_WITHOUT_ERRORS);
+
+    let issues = parse_latex_log(&log).expect("Failed to parse log");
+
+    assert_eq!(issues.len(), 1);
+}
+
+#[rstest]
+fn test_check_latex_log_fails_on_fatal_errors() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    fs::write(temp_dir.path().join("template.log"), LOG).expect("Failed to write log");
+    fs::write(temp_dir.path().join("output.tex"), OUTPUT_TEX).expect("Failed to write output");
+
+    let recorder = ConversionRecorder::new(false);
+
+    let result = check_latex_log(
+        temp_dir.path(),
+        &PathBuf::from("template.log"),
+        &PathBuf::from("output.tex"),
+        "xelatex",
+        &recorder,
+    );
+
+    let error = result.expect_err("Fatal errors should fail the conversion");
+    assert!(
+        error
+            .to_string()
+            .contains(r"Undefined control sequence. (output.tex, line 3): \foo{bar} baz"),
+        "Unexpected error: {}",
+        error
+    );
+
+    let warnings = recorder.get_record().warnings;
+    assert_eq!(
+        warnings.iter().map(|w| w.kind).collect::<Vec<_>>(),
+        vec![
+            WarningKind::UndefinedReference,
+            WarningKind::OverfullBox,
+            WarningKind::MissingFont,
+            WarningKind::MissingFont,
+        ]
+    );
+    assert_eq!(
+        warnings[0].message,
+        r"Reference `sec:intro' undefined. (output.tex, line 5): See \ref{sec:intro}."
+    );
+    assert_eq!(warnings[0].source, "xelatex");
+    assert_eq!(warnings[0].file, Some(PathBuf::from("output.tex")));
+    assert_eq!(warnings[0].line, Some(5));
+}
+
+#[rstest]
+fn test_check_latex_log_without_errors() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    fs::write(temp_dir.path().join("template.log"), LOG_WITHOUT_ERRORS)
+        .expect("Failed to write log");
+    fs::write(temp_dir.path().join("output.tex"), OUTPUT_TEX).expect("Failed to write output");
+
+    let recorder = ConversionRecorder::new(false);
+
+    check_latex_log(
+        temp_dir.path(),
+        &PathBuf::from("template.log"),
+        &PathBuf::from("output.tex"),
+        "xelatex",
+        &recorder,
+    )
+    .expect("Warnings should not fail the conversion");
+
+    let warnings = recorder.get_record().warnings;
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, WarningKind::UndefinedReference);
+}
+
+#[rstest]
+fn test_check_latex_log_missing_log() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let recorder = ConversionRecorder::new(false);
+
+    check_latex_log(
+        temp_dir.path(),
+        &PathBuf::from("template.log"),
+        &PathBuf::from("output.tex"),
+        "xelatex",
+        &recorder,
+    )
+    .expect("A missing log should not fail the conversion");
+}
+
+#[rstest]
+#[case(
+    "LaTeX Warning: Label(s) may have changed. Rerun to get cross-references right.",
+    true
+)]
+#[case(
+    "LaTeX Warning: There were undefined references.\nRerun to get citations correct.",
+    true
+)]
+#[case(
+    "Package rerunfilecheck Warning: File `template.out' has changed.\n(rerunfilecheck)                Rerun to get outlines right",
+    true
+)]
+#[case("Package biblatex Warning: Please rerun LaTeX.", true)]
+#[case(
+    "Package longtable Warning: Table widths have changed. Rerun LaTeX.",
+    true
+)]
+#[case("LaTeX Warning: There were undefined references.", false)]
+#[case("Output written on template.pdf (1 page).", false)]
+fn test_needs_rerun(#[case] log_content: &str, #[case] expected: bool) {
+    assert_eq!(needs_rerun(log_content), expected);
+}
+
+#[rstest]
+fn test_needs_rerun_with_wrapped_line() {
+    // TeX wraps log lines at 79 characters, which may split the marker.
+    let message = "Package rerunfilecheck Warning: File `template-name.out' has changed. Rerun to get outlines right";
+    let (first_line, second_line) = message.split_at(79);
+    let log_content = format!("{}\n{}\n", first_line, second_line);
+
+    assert!(!second_line.contains("Rerun to get outlines right"));
+    assert!(needs_rerun(&log_content));
+}
@@ -0,0 +1,174 @@
+use rstest::rstest;
+use toml::Table;
+
+use crate::{manifest_document::update_manifest_document, manifest_model::Manifest};
+
+const MANIFEST: &str = r#"# Our book project
+version = 6
+smart_clean = true # keep the last five conversions
+
+# Print templates
+[[templates]]
+name = "print"
+template_type = "Tex"
+template_file = "print.tex"
+latex = { engine = "Lualatex", max_passes = 3 }
+
+[[templates]]
+name = "ebook"
+template_type = "Epub"
+
+[[templates]]
+name = "web"
+template_type = "CustomPreprocessors"
+
+[custom_processors]
+preprocessors = []
+processors = []
+
+[shared_metadata]
+author = "Jane Doe" # the pen name
+"#;
+
+fn update(change: impl FnOnce(&mut Table)) -> String {
+    let previous_manifest: Table = toml::from_str(MANIFEST).expect("Failed to parse manifest");
+    let mut manifest = previous_manifest.clone();
+    change(&mut manifest);
+
+    update_manifest_document(MANIFEST, &previous_manifest, &manifest)
+        .expect("Failed to update manifest")
+}
+
+fn get_template(manifest: &mut Table, index: usize) -> &mut Table {
+    manifest["templates"]
+        .as_array_mut()
+        .and_then(|templates| templates[index].as_table_mut())
+        .expect("Expected a template")
+}
+
+#[rstest]
+fn test_update_manifest_document_without_changes() {
+    assert_eq!(update(|_| {}), MANIFEST);
+}
+
+#[rstest]
+fn test_update_manifest_document_changes_value() {
+    let manifest = update(|manifest| {
+        manifest["shared_metadata"]
+            .as_table_mut()
+            .expect("Expected shared metadata")
+            .insert("author".to_string(), "John Doe".into());
+    });
+
+    assert_eq!(
+        manifest,
+        MANIFEST.replace(
+            r#"author = "Jane Doe" # the pen name"#,
+            r#"author = "John Doe" # the pen name"#
+        )
+    );
+}
+
+#[rstest]
+fn test_update_manifest_document_adds_value_to_table() {
+    let manifest = update(|manifest| {
+        get_template(manifest, 1).insert("output".to_string(), "book.epub".into());
+    });
+
+    assert_eq!(
+        manifest,
+        MANIFEST.replace(
+            "template_type = \"Epub\"\n",
+            "template_type = \"Epub\"\noutput = \"book.epub\"\n"
+        )
+    );
+}
+
+#[rstest]
+fn test_update_manifest_document_keeps_inline_tables() {
+    let manifest = update(|manifest| {
+        get_template(manifest, 0)["latex"]
+            .as_table_mut()
+            .expect("Expected latex settings")
+            .insert("max_passes".to_string(), 4.into());
+    });
+
+    assert_eq!(
+        manifest,
+        MANIFEST.replace("max_passes = 3 }", "max_passes = 4 }")
+    );
+}
+
+#[rstest]
+fn test_update_manifest_document_removes_table_by_name() {
+    let manifest = update(|manifest| {
+        manifest["templates"]
+            .as_array_mut()
+            .expect("Expected templates")
+            .remove(1);
+    });
+
+    assert_eq!(
+        manifest,
+        MANIFEST.replace(
+            "[[templates]]\nname = \"ebook\"\ntemplate_type = \"Epub\"\n\n",
+            ""
+        )
+    );
+}
+
+#[rstest]
+fn test_update_manifest_document_appends_table() {
+    let manifest = update(|manifest| {
+        let mut template = Table::new();
+        template.insert("name".to_string(), "slides".into());
+        template.insert("template_type".to_string(), "Typst".into());
+        manifest["templates"]
+            .as_array_mut()
+            .expect("Expected templates")
+            .push(template.into());
+    });
+
+    assert_eq!(
+        manifest,
+        MANIFEST.replace(
+            "template_type = \"CustomPreprocessors\"\n",
+            "template_type = \"CustomPreprocessors\"\n\n[[templates]]\nname = \"slides\"\ntemplate_type = \"Typst\"\n"
+        )
+    );
+}
+
+#[rstest]
+fn test_update_manifest_document_removes_value() {
+    let manifest = update(|manifest| {
+        manifest.remove("smart_clean");
+    });
+
+    assert_eq!(
+        manifest,
+        MANIFEST.replace("smart_clean = true # keep the last five conversions\n", "")
+    );
+}
+
+#[rstest]
+fn test_update_manifest_document_keeps_unknown_keys() {
+    let existing_content = MANIFEST
+        .replace("version = 6\n", "version = 6\neditor = \"vim\"\n")
+        .replace(
+            "name = \"ebook\"\n",
+            "name = \"ebook\"\nnotes = \"needs a cover\"\n",
+        );
+    let previous_manifest: Manifest =
+        toml::from_str(&existing_content).expect("Failed to parse manifest");
+    let mut manifest: Manifest =
+        toml::from_str(&existing_content).expect("Failed to parse manifest");
+    manifest.smart_clean = None;
+
+    let manifest = update_manifest_document(&existing_content, &previous_manifest, &manifest)
+        .expect("Failed to update manifest");
+
+    assert_eq!(
+        manifest,
+        existing_content.replace("smart_clean = true # keep the last five conversions\n", "")
+    );
+}
@@ -0,0 +1,378 @@
+use rstest::rstest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+
+use crate::{
+    project_management::{
+        add_processor, load_and_convert_manifest, remove_processor, save_manifest,
+        upgrade_manifest_file,
+    },
+    template_type::TemplateType,
+};
+
+const MANIFEST: &str = r#"version = 6
+include = ["templates/*.toml", "processors.toml"]
+
+[[markdown_projects]]
+name = "Markdown"
+path = "Markdown"
+output = "."
+"#;
+
+const PDF_FRAGMENT: &str = r#"# The print template
+[[templates]]
+name = "PDF"
+template_type = "Tex"
+template_file = "template.tex"
+output = "book.pdf"
+"#;
+
+const PROCESSORS_FRAGMENT: &str = r#"[[custom_processors.processors]]
+name = "Listings"
+processor_args = ["--listings"]
+"#;
+
+const V4_MANIFEST: &str = r#"version = 4
+include = ["templates/*.toml", "preprocessors.toml"]
+
+[custom_processors]
+preprocessors = []
+processors = []
+"#;
+
+const V4_TEMPLATE_FRAGMENT: &str = r#"[[templates]]
+name = "ebook"
+template_type = "CustomPandoc"
+output = "book.epub"
+preprocessor = "Epub"
+"#;
+
+const V4_PREPROCESSORS_FRAGMENT: &str = r#"[[custom_processors.preprocessors]]
+name = "Epub"
+pandoc_args = ["-t", "epub", "-o", "book.epub"]
+"#;
+
+fn create_project(project: &Path) {
+    fs::create_dir(project.join("templates")).expect("Failed to create templates directory");
+    fs::write(project.join("manifest.toml"), MANIFEST).expect("Failed to write manifest");
+    fs::write(project.join("templates/pdf.toml"), PDF_FRAGMENT).expect("Failed to write fragment");
+    fs::write(project.join("processors.toml"), PROCESSORS_FRAGMENT)
+        .expect("Failed to write fragment");
+}
+
+fn create_v4_project(project: &Path) {
+    fs::create_dir(project.join("templates")).expect("Failed to create templates directory");
+    fs::write(project.join("manifest.toml"), V4_MANIFEST).expect("Failed to write manifest");
+    fs::write(project.join("templates/ebook.toml"), V4_TEMPLATE_FRAGMENT)
+        .expect("Failed to write fragment");
+    fs::write(
+        project.join("preprocessors.toml"),
+        V4_PREPROCESSORS_FRAGMENT,
+    )
+    .expect("Failed to write fragment");
+}
+
+fn read_file(path: &Path) -> String {
+    fs::read_to_string(path).expect("Failed to read file")
+}
+
+#[rstest]
+fn load_and_convert_manifest_merges_fragments() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_project(temp_dir.path());
+
+    let manifest = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"))
+        .expect("Failed to load manifest");
+
+    assert_eq!(manifest.templates.len(), 1);
+    assert_eq!(manifest.templates[0].name, "PDF");
+    assert_eq!(manifest.custom_processors.processors[0].name, "Listings");
+    assert!(manifest.custom_processors.preprocessors.is_empty());
+    assert_eq!(
+        manifest
+            .get_fragment("templates", "PDF")
+            .map(|f| f.path.clone()),
+        Some(PathBuf::from("templates/pdf.toml"))
+    );
+    assert_eq!(
+        manifest
+            .get_fragment("custom_processors.processors", "Listings")
+            .map(|f| f.path.clone()),
+        Some(PathBuf::from("processors.toml"))
+    );
+    assert!(
+        manifest
+            .get_fragment("markdown_projects", "Markdown")
+            .is_none()
+    );
+}
+
+#[rstest]
+fn load_and_convert_manifest_rejects_duplicate_names() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_project(temp_dir.path());
+    fs::write(
+        temp_dir.path().join("templates/print.toml"),
+        PDF_FRAGMENT.replace("book.pdf", "print.pdf"),
+    )
+    .expect("Failed to write fragment");
+
+    let result = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"));
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some(
+            "Template 'PDF' is defined in both 'templates/pdf.toml' and 'templates/print.toml'."
+                .to_string()
+        )
+    );
+}
+
+#[rstest]
+fn load_and_convert_manifest_rejects_duplicate_names_in_fragment() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_project(temp_dir.path());
+    fs::write(
+        temp_dir.path().join("templates/pdf.toml"),
+        format!("{}\n{}", PDF_FRAGMENT, PDF_FRAGMENT),
+    )
+    .expect("Failed to write fragment");
+
+    let result = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"));
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("Template 'PDF' is defined more than once in 'templates/pdf.toml'.".to_string())
+    );
+}
+
+#[rstest]
+fn load_and_convert_manifest_rejects_duplicate_names_in_manifest() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_project(temp_dir.path());
+    fs::write(
+        temp_dir.path().join("manifest.toml"),
+        format!(
+            "{}\n[[markdown_projects]]\nname = \"Markdown\"\npath = \"Other\"\noutput = \"other\"\n",
+            MANIFEST
+        ),
+    )
+    .expect("Failed to write manifest");
+
+    let result = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"));
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some(
+            "Markdown project 'Markdown' is defined more than once in 'manifest.toml'.".to_string()
+        )
+    );
+}
+
+#[rstest]
+fn load_and_convert_manifest_rejects_unsupported_fragment_keys() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_project(temp_dir.path());
+    fs::write(
+        temp_dir.path().join("processors.toml"),
+        format!("smart_clean = true\n{}", PROCESSORS_FRAGMENT),
+    )
+    .expect("Failed to write fragment");
+
+    let result = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"));
+
+    assert!(
+        result
+            .err()
+            .is_some_and(|e| e.to_string().contains("defines 'smart_clean'"))
+    );
+}
+
+#[rstest]
+fn load_and_convert_manifest_rejects_missing_fragment() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_project(temp_dir.path());
+    fs::remove_file(temp_dir.path().join("processors.toml")).expect("Failed to remove fragment");
+
+    let result = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"));
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("Included manifest fragment 'processors.toml' does not exist.".to_string())
+    );
+}
+
+#[rstest]
+fn save_manifest_writes_entries_to_their_fragment() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let manifest_path = temp_dir.path().join("manifest.toml");
+    create_project(temp_dir.path());
+
+    let mut manifest = load_and_convert_manifest(&manifest_path).expect("Failed to load manifest");
+    manifest.templates[0].output = Some(PathBuf::from("print.pdf"));
+    save_manifest(&manifest_path, &manifest).expect("Failed to save manifest");
+
+    assert_eq!(read_file(&manifest_path), MANIFEST);
+    assert_eq!(
+        read_file(&temp_dir.path().join("templates/pdf.toml")),
+        PDF_FRAGMENT.replace("book.pdf", "print.pdf")
+    );
+    assert_eq!(
+        read_file(&temp_dir.path().join("processors.toml")),
+        PROCESSORS_FRAGMENT
+    );
+}
+
+#[rstest]
+fn editing_apis_keep_entries_in_their_fragment() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let project = Some(temp_dir.path().to_path_buf());
+    create_project(temp_dir.path());
+
+    add_processor(
+        project.clone(),
+        "Typst Fonts".to_string(),
+        vec!["--font-path".to_string()],
+    )
+    .expect("Failed to add processor");
+    remove_processor(project, "Listings".to_string()).expect("Failed to remove processor");
+
+    let manifest = read_file(&temp_dir.path().join("manifest.toml"));
+    assert!(manifest.contains("name = \"Typst Fonts\""));
+    assert!(!manifest.contains("[[templates]]"));
+    assert!(!read_file(&temp_dir.path().join("processors.toml")).contains("Listings"));
+    assert_eq!(
+        read_file(&temp_dir.path().join("templates/pdf.toml")),
+        PDF_FRAGMENT
+    );
+}
+
+#[rstest]
+fn load_and_convert_manifest_skips_build_and_markdown_directories() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_project(temp_dir.path());
+    fs::write(
+        temp_dir.path().join("manifest.toml"),
+        MANIFEST.replace("\"templates/*.toml\"", "\"**/*.toml\""),
+    )
+    .expect("Failed to write manifest");
+    for dir in [
+        "Markdown",
+        "2025-01-01_12-00-00/templates",
+        ".tiefdown_cache",
+    ] {
+        fs::create_dir_all(temp_dir.path().join(dir)).expect("Failed to create directory");
+    }
+    fs::write(
+        temp_dir.path().join("Markdown/order.toml"),
+        "order = [\"Chapter 1.md\"]\n",
+    )
+    .expect("Failed to write order file");
+    fs::write(
+        temp_dir
+            .path()
+            .join("2025-01-01_12-00-00/templates/pdf.toml"),
+        PDF_FRAGMENT,
+    )
+    .expect("Failed to write fragment");
+    fs::write(temp_dir.path().join(".tiefdown_cache/cache.toml"), "")
+        .expect("Failed to write cache file");
+
+    let manifest = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"))
+        .expect("Failed to load manifest");
+
+    assert_eq!(
+        manifest
+            .fragments
+            .iter()
+            .map(|f| f.path.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            PathBuf::from("processors.toml"),
+            PathBuf::from("templates/pdf.toml")
+        ]
+    );
+}
+
+#[rstest]
+fn load_and_convert_manifest_upgrades_fragments() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_v4_project(temp_dir.path());
+
+    let manifest = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"))
+        .expect("Failed to load manifest");
+
+    assert_eq!(
+        manifest.templates[0].template_type,
+        TemplateType::CustomPreprocessors
+    );
+    assert_eq!(
+        manifest.templates[0]
+            .preprocessors
+            .as_ref()
+            .and_then(|p| p.combined_output.clone()),
+        Some(PathBuf::from("book.epub"))
+    );
+    assert_eq!(
+        manifest.custom_processors.preprocessors[0].cli_args,
+        vec!["-t", "epub"]
+    );
+}
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn upgrade_manifest_file_upgrades_fragments(#[case] dry_run: bool) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_v4_project(temp_dir.path());
+
+    let upgrade = upgrade_manifest_file(Some(temp_dir.path().to_path_buf()), dry_run)
+        .expect("Failed to upgrade manifest");
+
+    assert!(
+        upgrade
+            .diff
+            .contains("--- templates/ebook.toml (v4)\n+++ templates/ebook.toml (v6)\n")
+    );
+    assert!(
+        upgrade
+            .diff
+            .contains("--- preprocessors.toml (v4)\n+++ preprocessors.toml (v6)\n")
+    );
+
+    let template_fragment = read_file(&temp_dir.path().join("templates/ebook.toml"));
+    let preprocessors_fragment = read_file(&temp_dir.path().join("preprocessors.toml"));
+    if dry_run {
+        assert!(upgrade.fragment_backup_paths.is_empty());
+        assert_eq!(template_fragment, V4_TEMPLATE_FRAGMENT);
+        assert_eq!(preprocessors_fragment, V4_PREPROCESSORS_FRAGMENT);
+        return;
+    }
+
+    assert_eq!(
+        upgrade.fragment_backup_paths,
+        vec![
+            temp_dir.path().join("templates/ebook.toml.bak"),
+            temp_dir.path().join("preprocessors.toml.bak")
+        ]
+    );
+    assert_eq!(
+        read_file(&temp_dir.path().join("templates/ebook.toml.bak")),
+        V4_TEMPLATE_FRAGMENT
+    );
+    assert!(template_fragment.contains("template_type = \"CustomPreprocessors\""));
+    assert!(preprocessors_fragment.contains("cli_args = [\"-t\", \"epub\"]"));
+
+    let manifest = read_file(&temp_dir.path().join("manifest.toml"));
+    assert!(manifest.contains("version = 6"));
+    assert!(!manifest.contains("ebook"));
+    assert!(!manifest.contains("Epub"));
+
+    let manifest = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"))
+        .expect("Failed to load manifest");
+    assert_eq!(manifest.templates.len(), 1);
+    assert_eq!(manifest.custom_processors.preprocessors.len(), 1);
+}
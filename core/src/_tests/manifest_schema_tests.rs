@@ -0,0 +1,119 @@
+use rstest::rstest;
+use serde_json::Value;
+use std::{fs, path::PathBuf};
+
+use crate::{
+    consts::CURRENT_MANIFEST_VERSION, manifest_model::Manifest,
+    manifest_schema::get_manifest_schema,
+};
+
+fn get_docs_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../docs")
+}
+
+fn get_schema() -> Value {
+    serde_json::from_str(&get_manifest_schema().unwrap()).unwrap()
+}
+
+/// Resolves a `$ref` of the schema to its definition.
+fn resolve<'a>(schema: &'a Value, value: &'a Value) -> &'a Value {
+    match value["$ref"].as_str() {
+        Some(reference) => {
+            let name = reference.trim_start_matches("#/$defs/");
+            &schema["$defs"][name]
+        }
+        None => value,
+    }
+}
+
+/// Asserts that every key of a serialized manifest value is described by the schema.
+fn assert_covered(schema: &Value, value_schema: &Value, value: &toml::Value, path: &str) {
+    let value_schema = resolve(schema, value_schema);
+
+    match value {
+        toml::Value::Table(table) => {
+            let Some(properties) = value_schema["properties"].as_object() else {
+                return;
+            };
+            for (key, value) in table {
+                let property = properties
+                    .get(key)
+                    .unwrap_or_else(|| panic!("'{}.{}' is missing in the schema", path, key));
+                assert_covered(schema, property, value, &format!("{}.{}", path, key));
+            }
+        }
+        toml::Value::Array(array) => {
+            for value in array {
+                assert_covered(schema, &value_schema["items"], value, path);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[rstest]
+fn test_manifest_schema_matches_published_schema() {
+    let published = fs::read_to_string(get_docs_path().join("manifest.schema.json")).unwrap();
+
+    assert_eq!(
+        published,
+        get_manifest_schema().unwrap(),
+        "docs/manifest.schema.json is out of date. Regenerate it with `tiefdownconverter schema -o docs/manifest.schema.json`."
+    );
+}
+
+#[rstest]
+fn test_manifest_schema_requires_current_version() {
+    let schema = get_schema();
+
+    assert_eq!(
+        schema["properties"]["version"]["const"],
+        CURRENT_MANIFEST_VERSION
+    );
+    assert!(
+        schema["required"]
+            .as_array()
+            .unwrap()
+            .contains(&Value::from("version"))
+    );
+}
+
+#[rstest]
+fn test_manifest_schema_lists_enum_variants() {
+    let schema = get_schema();
+
+    assert_eq!(
+        schema["$defs"]["TemplateType"]["enum"],
+        serde_json::json!([
+            "Tex",
+            "Typst",
+            "Epub",
+            "CustomPreprocessors",
+            "CustomProcessor"
+        ])
+    );
+}
+
+#[rstest]
+fn test_manifest_schema_covers_serialized_manifest() {
+    let content = fs::read_to_string(get_docs_path().join("manifest.toml")).unwrap();
+    let manifest: Manifest = toml::from_str(&content).unwrap();
+    let serialized = toml::Value::try_from(&manifest).unwrap();
+    let schema = get_schema();
+
+    assert_covered(&schema, &schema, &serialized, "manifest");
+}
+
+#[rstest]
+fn test_manifest_schema_allows_unknown_keys() {
+    let schema = get_schema();
+
+    assert!(schema.get("additionalProperties").is_none());
+    assert!(
+        schema["$defs"]
+            .as_object()
+            .unwrap()
+            .values()
+            .all(|definition| definition.get("additionalProperties").is_none())
+    );
+}
@@ -0,0 +1,359 @@
+use rstest::rstest;
+use std::fs;
+use tempfile::tempdir;
+use toml::{Table, Value};
+
+use crate::{
+    manifest_model::Manifest,
+    metadata_format::MetadataFormat,
+    metadata_management::{
+        MetadataLayer, apply_metadata_import, get_metadata_layers, get_metadata_provenance,
+        merge_metadata_layers, parse_metadata_overrides, read_metadata_file, set_metadata,
+        write_metadata_file,
+    },
+};
+
+const MANIFEST: &str = r#"
+version = 6
+
+[[markdown_projects]]
+name = "Book"
+path = "Markdown"
+output = "."
+
+[markdown_projects.metadata_fields]
+author = "Project"
+
+[[templates]]
+name = "pdf"
+template_type = "Tex"
+
+[templates.metadata_fields.series]
+number = 2
+
+[[profiles]]
+name = "print"
+templates = ["pdf"]
+
+[profiles.metadata_fields]
+title = "Profile"
+series = { name = "Dreams", number = 1 }
+
+[custom_processors]
+preprocessors = []
+processors = []
+
+[shared_metadata]
+title = "Shared"
+author = "Shared"
+publisher = "Shared"
+"#;
+
+fn table(content: &str) -> Table {
+    toml::from_str(content).expect("Failed to parse metadata")
+}
+
+#[rstest]
+fn test_parse_metadata_overrides() {
+    let overrides = parse_metadata_overrides(&[
+        "title=My Title".to_string(),
+        "series.name=Dreams".to_string(),
+        "series.number=1".to_string(),
+        "equation=a=b".to_string(),
+    ])
+    .expect("Failed to parse overrides");
+
+    assert_eq!(
+        overrides,
+        table(
+            r#"
+title = "My Title"
+equation = "a=b"
+series = { name = "Dreams", number = "1" }
+"#
+        )
+    );
+}
+
+#[rstest]
+#[case("title")]
+#[case("=value")]
+#[case("series.=value")]
+fn test_parse_metadata_overrides_invalid(#[case] metadata_override: &str) {
+    assert!(parse_metadata_overrides(&[metadata_override.to_string()]).is_err());
+}
+
+#[rstest]
+fn test_merge_metadata_layers() {
+    let layers = vec![
+        (
+            MetadataLayer::Shared,
+            table(
+                r#"
+title = "Shared"
+authors = ["A", "B"]
+series = { name = "Dreams", number = 1 }
+"#,
+            ),
+        ),
+        (
+            MetadataLayer::MarkdownProject("Book".to_string()),
+            table(
+                r#"
+authors = ["C"]
+series = { number = 2 }
+"#,
+            ),
+        ),
+    ];
+
+    let metadata = merge_metadata_layers(&layers);
+
+    assert_eq!(
+        metadata,
+        table(
+            r#"
+title = "Shared"
+authors = ["C"]
+series = { name = "Dreams", number = 2 }
+"#
+        )
+    );
+}
+
+#[rstest]
+fn test_merge_metadata_layers_replaces_table_with_value() {
+    let layers = vec![
+        (
+            MetadataLayer::Shared,
+            table("series = { name = \"Dreams\" }"),
+        ),
+        (MetadataLayer::Override, table("series = \"None\"")),
+    ];
+
+    let metadata = merge_metadata_layers(&layers);
+
+    assert_eq!(metadata["series"], Value::String("None".to_string()));
+}
+
+#[rstest]
+fn test_get_metadata_layers() {
+    let manifest: Manifest = toml::from_str(MANIFEST).expect("Failed to parse manifest");
+    let markdown_project = &manifest.markdown_projects.as_ref().unwrap()[0];
+
+    let layers = get_metadata_layers(
+        &manifest,
+        markdown_project,
+        Some(&manifest.templates[0]),
+        Some("print"),
+        &table("publisher = \"Override\""),
+    )
+    .expect("Failed to get metadata layers");
+
+    assert_eq!(
+        layers
+            .iter()
+            .map(|(layer, _)| layer.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            MetadataLayer::Shared,
+            MetadataLayer::Profile("print".to_string()),
+            MetadataLayer::Template("pdf".to_string()),
+            MetadataLayer::MarkdownProject("Book".to_string()),
+            MetadataLayer::Override,
+        ]
+    );
+
+    let metadata = merge_metadata_layers(&layers);
+    let provenance = get_metadata_provenance(&layers, &metadata);
+
+    assert_eq!(
+        provenance
+            .iter()
+            .map(|field| (
+                field.key.as_str(),
+                field.value.to_string(),
+                field.layer.to_string()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "author",
+                "\"Project\"".to_string(),
+                "markdown project 'Book'".to_string()
+            ),
+            (
+                "publisher",
+                "\"Override\"".to_string(),
+                "override".to_string()
+            ),
+            (
+                "series.name",
+                "\"Dreams\"".to_string(),
+                "profile 'print'".to_string()
+            ),
+            (
+                "series.number",
+                "2".to_string(),
+                "template 'pdf'".to_string()
+            ),
+            (
+                "title",
+                "\"Profile\"".to_string(),
+                "profile 'print'".to_string()
+            ),
+        ]
+    );
+}
+
+#[rstest]
+fn test_get_metadata_layers_without_profile_and_template() {
+    let manifest: Manifest = toml::from_str(MANIFEST).expect("Failed to parse manifest");
+    let markdown_project = &manifest.markdown_projects.as_ref().unwrap()[0];
+
+    let layers = get_metadata_layers(&manifest, markdown_project, None, None, &Table::new())
+        .expect("Failed to get metadata layers");
+
+    let metadata = merge_metadata_layers(&layers);
+
+    assert_eq!(
+        metadata,
+        table(
+            r#"
+title = "Shared"
+author = "Project"
+publisher = "Shared"
+"#
+        )
+    );
+}
+
+#[rstest]
+fn test_get_metadata_layers_missing_profile() {
+    let manifest: Manifest = toml::from_str(MANIFEST).expect("Failed to parse manifest");
+    let markdown_project = &manifest.markdown_projects.as_ref().unwrap()[0];
+
+    let result = get_metadata_layers(
+        &manifest,
+        markdown_project,
+        None,
+        Some("missing"),
+        &Table::new(),
+    );
+
+    assert!(result.is_err());
+}
+
+#[rstest]
+#[case(
+    "metadata.json",
+    r#"{"title": "Dream", "price": 9.5, "note": null, "authors": ["A", null, "B"], "series": {"number": 1}}"#
+)]
+#[case(
+    "metadata.yaml",
+    "title: Dream\nprice: 9.5\nnote: ~\nauthors: [A, ~, B]\nseries:\n  number: 1\n"
+)]
+#[case(
+    "metadata.toml",
+    "title = \"Dream\"\nprice = 9.5\nauthors = [\"A\", \"B\"]\nseries = { number = 1 }\n"
+)]
+fn test_read_metadata_file(#[case] file_name: &str, #[case] content: &str) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let path = temp_dir.path().join(file_name);
+    fs::write(&path, content).expect("Failed to write metadata file");
+
+    let metadata = read_metadata_file(&path, None).expect("Failed to read metadata file");
+
+    assert_eq!(
+        metadata,
+        table(
+            r#"
+title = "Dream"
+price = 9.5
+authors = ["A", "B"]
+series = { number = 1 }
+"#
+        )
+    );
+}
+
+#[rstest]
+fn test_read_metadata_file_yaml_keys() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let path = temp_dir.path().join("metadata.txt");
+    fs::write(&path, "2025: Release\ntrue: Yes\n").expect("Failed to write metadata file");
+
+    let metadata = read_metadata_file(&path, Some(MetadataFormat::Yaml))
+        .expect("Failed to read metadata file");
+
+    assert_eq!(metadata, table("2025 = \"Release\"\ntrue = \"Yes\""));
+}
+
+#[rstest]
+#[case(MetadataFormat::Json, "\"release\": \"2025-03-01\"")]
+#[case(MetadataFormat::Yaml, "release: 2025-03-01")]
+#[case(MetadataFormat::Toml, "release = 2025-03-01")]
+fn test_write_metadata_file_dates(#[case] format: MetadataFormat, #[case] expected: &str) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let path = temp_dir.path().join("metadata");
+
+    write_metadata_file(&path, Some(format), &table("release = 2025-03-01"))
+        .expect("Failed to write metadata file");
+
+    let content = fs::read_to_string(&path).expect("Failed to read metadata file");
+    assert!(content.contains(expected), "{}", content);
+
+    assert_eq!(
+        read_metadata_file(&path, Some(format))
+            .expect("Failed to read metadata file")
+            .len(),
+        1
+    );
+}
+
+#[rstest]
+#[case(
+    false,
+    "title = \"Dream\"\nauthor = \"Me\"\nseries = { name = \"Dreams\", number = 2 }"
+)]
+#[case(true, "title = \"Dream\"\nseries = { number = 2 }")]
+fn test_apply_metadata_import(#[case] replace: bool, #[case] expected: &str) {
+    let mut metadata =
+        table("title = \"Old\"\nauthor = \"Me\"\nseries = { name = \"Dreams\", number = 1 }");
+
+    apply_metadata_import(
+        &mut metadata,
+        table("title = \"Dream\"\nseries = { number = 2 }"),
+        replace,
+    );
+
+    assert_eq!(metadata, table(expected));
+}
+
+#[rstest]
+fn test_set_metadata_keeps_unknown_keys() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let manifest_path = temp_dir.path().join("manifest.toml");
+    fs::write(
+        &manifest_path,
+        MANIFEST
+            .replace("version = 6\n", "version = 6\neditor = \"vim\"\n")
+            .replace(
+                "template_type = \"Tex\"\n",
+                "template_type = \"Tex\"\nnotes = \"print only\"\n",
+            ),
+    )
+    .expect("Failed to write manifest");
+
+    set_metadata(
+        Some(temp_dir.path().to_path_buf()),
+        "title".to_string(),
+        "Updated".to_string(),
+    )
+    .expect("Failed to set metadata");
+
+    let manifest = fs::read_to_string(&manifest_path).expect("Failed to read manifest");
+    assert!(manifest.contains("editor = \"vim\"\n"));
+    assert!(manifest.contains("notes = \"print only\"\n"));
+    assert!(manifest.contains("title = \"Updated\"\n"));
+}
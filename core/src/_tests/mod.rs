@@ -1,7 +1,25 @@
-// NOTE: Not needed until rewrite of Manifest injection
-// mod tests_common;
-
+mod bibliography_tests;
+mod build_cache_tests;
+mod build_report_tests;
+mod chapter_management_tests;
+mod common_tests;
+mod conditional_content_tests;
+mod conversion_tests;
+mod file_retrieval_tests;
+mod front_matter_tests;
+mod input_filter_tests;
+mod latex_log_tests;
 // mod injections_tests;
+mod manifest_document_tests;
+mod manifest_includes_tests;
 mod manifest_model_tests;
+mod manifest_schema_tests;
+mod metadata_management_tests;
+mod nav_meta_generation_tests;
 mod project_management_tests;
 mod template_type_tests;
+mod tests_common;
+mod tex_converter_tests;
+mod text_diff_tests;
+mod typst_converter_tests;
+mod validation_tests;
@@ -0,0 +1,309 @@
+use rstest::rstest;
+use std::{fs, path::PathBuf};
+use tempfile::tempdir;
+
+use crate::{
+    manifest_model::NavTitleSource,
+    nav_meta_generation::{
+        NavMeta, NavMetaNodeId, NavTreeNode, NavTreeNodeKind, get_breadcrumbs, retrieve_nav_meta,
+        slugify,
+    },
+};
+
+fn get_nav_title(file_name: &str, content: &str, nav_title_source: NavTitleSource) -> String {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let compiled_directory_path = temp_dir.path();
+    let conversion_input_dir = compiled_directory_path.join("html_convdir");
+    fs::create_dir_all(conversion_input_dir.join("Part 1")).expect("Failed to create directory");
+    fs::write(conversion_input_dir.join("Part 1").join(file_name), content)
+        .expect("Failed to write file");
+
+    let nav_meta = retrieve_nav_meta(
+        &vec![PathBuf::from("html_convdir/Part 1").join(file_name)],
+        compiled_directory_path,
+        &conversion_input_dir,
+        &Some("html".to_string()),
+        nav_title_source,
+        true,
+    )
+    .expect("Failed to retrieve nav meta");
+
+    nav_meta.nodes.expect("Nav meta should contain nodes")[0]
+        .title
+        .clone()
+}
+
+#[rstest]
+#[case("Chapter 1.md", "# The Beginning\n\n## Section\n", "The Beginning")]
+#[case("Chapter 1.md", "Intro text.\n\n### Deep ###\n", "Deep")]
+#[case("Chapter 1.md", "# The Beginning {#sec:beginning}\n", "The Beginning")]
+#[case("Chapter 1.md", "The Beginning\n=============\n", "The Beginning")]
+#[case("Chapter 1.md", "The\nBeginning\n---\n", "The Beginning")]
+#[case("Chapter 1.md", "```\n# Not a heading\n```\n\n# Heading\n", "Heading")]
+#[case("Chapter 1.md", "~~~\nNot a heading\n---\n~~~\n", "Chapter 1")]
+#[case("Chapter 1.md", "#Not a heading\n", "Chapter 1")]
+#[case(
+    "Chapter 1.md",
+    "# The *big* [idea](x.md) of `code` and __strong__ ~~words~~\n",
+    "The big idea of code and strong words"
+)]
+#[case(
+    "Chapter 1.md",
+    "# A ![logo](logo.png) and snake_case_name\n",
+    "A logo and snake_case_name"
+)]
+#[case(
+    "Chapter 1.md",
+    "---\ntitle: The *Front*\n---\n# Heading\n",
+    "The Front"
+)]
+#[case("Chapter 1.md", "---\ntitle: Front\n---\n# Heading\n", "Front")]
+#[case("Chapter 1.md", "---\nauthor: Me\n---\nHeading\n---\n", "Heading")]
+#[case(
+    "Chapter 1.html",
+    "<html><head><title>Page &amp; Title</title></head><body><h1>Heading</h1></body></html>",
+    "Page & Title"
+)]
+#[case(
+    "Chapter 1.html",
+    "<body><h2>Sub</h2><h1 class=\"title\">The <em>Big</em>\n Heading</h1></body>",
+    "The Big Heading"
+)]
+#[case(
+    "Chapter 1.typ",
+    "#set page(width: 10cm)\n\n= The Beginning <intro>\n",
+    "The Beginning"
+)]
+#[case("Chapter 1.typ", "```\n= Not a heading\n```\n== Section\n", "Section")]
+#[case("Chapter 1.txt", "# Heading\n", "Chapter 1")]
+fn test_retrieve_nav_meta_content_title(
+    #[case] file_name: &str,
+    #[case] content: &str,
+    #[case] expected: &str,
+) {
+    assert_eq!(
+        get_nav_title(file_name, content, NavTitleSource::Content),
+        expected
+    );
+}
+
+#[rstest]
+#[case("# The Beginning\n", "Part 1/Chapter 1")]
+#[case("---\ntitle: Front\n---\n# The Beginning\n", "Front")]
+fn test_retrieve_nav_meta_path_title(#[case] content: &str, #[case] expected: &str) {
+    assert_eq!(
+        get_nav_title("Chapter 1.md", content, NavTitleSource::Path),
+        expected
+    );
+}
+
+fn retrieve_nav_meta_for_files(files: &[(&str, &str)], multi_file_output: bool) -> NavMeta {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let compiled_directory_path = temp_dir.path();
+    let conversion_input_dir = compiled_directory_path.join("html_convdir");
+
+    for (file_name, content) in files {
+        let path = conversion_input_dir.join(file_name);
+        fs::create_dir_all(path.parent().unwrap()).expect("Failed to create directory");
+        fs::write(path, content).expect("Failed to write file");
+    }
+
+    retrieve_nav_meta(
+        &files
+            .iter()
+            .map(|(file_name, _)| PathBuf::from("html_convdir").join(file_name))
+            .collect(),
+        compiled_directory_path,
+        &conversion_input_dir,
+        &multi_file_output.then(|| "html".to_string()),
+        NavTitleSource::Content,
+        multi_file_output,
+    )
+    .expect("Failed to retrieve nav meta")
+}
+
+fn get_section_anchors(nav_meta: NavMeta) -> Vec<Vec<String>> {
+    nav_meta
+        .nodes
+        .expect("Nodes should exist")
+        .iter()
+        .map(|node| {
+            node.sections
+                .iter()
+                .filter_map(|section| section.anchor.clone())
+                .collect()
+        })
+        .collect()
+}
+
+fn get_tree_outline(nodes: &[NavTreeNode], depth: usize, outline: &mut Vec<String>) {
+    for node in nodes {
+        outline.push(format!(
+            "{}{:?} {} {}{}",
+            "  ".repeat(depth),
+            node.kind,
+            node.title,
+            node.path.display(),
+            node.anchor
+                .as_ref()
+                .map(|anchor| format!("#{}", anchor))
+                .unwrap_or_default()
+        ));
+        get_tree_outline(&node.children, depth + 1, outline);
+    }
+}
+
+#[rstest]
+#[case("Hello World", "hello-world")]
+#[case("What's *new*, again?", "whats-new-again")]
+#[case("1.2 Setup  Guide", "setup-guide")]
+#[case("snake_case and-hyphen v1.0", "snake_case-and-hyphen-v1.0")]
+#[case("Über Straße", "über-straße")]
+#[case("123 !!!", "section")]
+fn test_slugify(#[case] title: &str, #[case] expected: &str) {
+    assert_eq!(slugify(title), expected);
+}
+
+#[rstest]
+#[case(true, vec![vec!["introduction-1"], vec!["the-big-idea", "introduction"]])]
+#[case(false, vec![vec!["introduction-1"], vec!["the-big-idea", "introduction-2"]])]
+fn test_retrieve_nav_meta_section_anchors(
+    #[case] multi_file_output: bool,
+    #[case] expected: Vec<Vec<&str>>,
+) {
+    let nav_meta = retrieve_nav_meta_for_files(
+        &[
+            ("Chapter 1.md", "# Introduction\n\n## Introduction\n"),
+            (
+                "Chapter 2.md",
+                "## The *big* [idea](x.md)\n\n## Introduction\n",
+            ),
+        ],
+        multi_file_output,
+    );
+
+    assert_eq!(get_section_anchors(nav_meta), expected);
+}
+
+#[rstest]
+fn test_retrieve_nav_meta_tree() {
+    let nav_meta = retrieve_nav_meta_for_files(
+        &[
+            (
+                "Introduction.md",
+                "# Introduction\n\n## Why\n\n### Really\n",
+            ),
+            (
+                "Part 1/Chapter 1.md",
+                "# Chapter 1\n\n### Early\n\n## Setup\n\n### Install {#install-it}\n\n## Setup\n",
+            ),
+            (
+                "Part 1/Section/Chapter 2.typ",
+                "= Chapter 2\n\n== Usage <usage>\n",
+            ),
+            (
+                "Part 2/Chapter 3.html",
+                "<h1>Chapter 3</h1><h2 id=\"first\">First</h2><h4>Ignored</h4>",
+            ),
+        ],
+        true,
+    );
+
+    let mut outline = vec![];
+    get_tree_outline(&nav_meta.tree.expect("Tree should exist"), 0, &mut outline);
+
+    assert_eq!(
+        outline,
+        vec![
+            "File Introduction Introduction.html",
+            "  Section Why Introduction.html#why",
+            "    Section Really Introduction.html#really",
+            "Directory Part 1 Part 1",
+            "  File Chapter 1 Part 1/Chapter 1.html",
+            "    Section Early Part 1/Chapter 1.html#early",
+            "    Section Setup Part 1/Chapter 1.html#setup",
+            "      Section Install Part 1/Chapter 1.html#install-it",
+            "    Section Setup Part 1/Chapter 1.html#setup-1",
+            "  Directory Section Part 1/Section",
+            "    File Chapter 2 Part 1/Section/Chapter 2.html",
+            "      Section Usage Part 1/Section/Chapter 2.html#usage",
+            "Directory Part 2 Part 2",
+            "  File Chapter 3 Part 2/Chapter 3.html",
+            "    Section First Part 2/Chapter 3.html#first",
+        ]
+    );
+
+    let nodes = nav_meta.nodes.expect("Nodes should exist");
+    assert_eq!(nodes[0].sections.len(), 1);
+    assert_eq!(nodes[0].sections[0].kind, NavTreeNodeKind::Section);
+    assert_eq!(nodes[0].sections[0].children.len(), 1);
+}
+
+#[rstest]
+fn test_retrieve_nav_meta_tree_keeps_file_order() {
+    let nav_meta = retrieve_nav_meta_for_files(
+        &[
+            ("Part 1/Chapter 1.md", "# One\n"),
+            ("Interlude.md", "# Interlude\n"),
+            ("Part 1/Chapter 2.md", "# Two\n"),
+        ],
+        true,
+    );
+
+    let mut outline = vec![];
+    get_tree_outline(&nav_meta.tree.expect("Tree should exist"), 0, &mut outline);
+
+    assert_eq!(
+        outline,
+        vec![
+            "Directory Part 1 Part 1",
+            "  File One Part 1/Chapter 1.html",
+            "File Interlude Interlude.html",
+            "Directory Part 1 Part 1",
+            "  File Two Part 1/Chapter 2.html",
+        ]
+    );
+}
+
+#[rstest]
+fn test_get_breadcrumbs() {
+    let nav_meta = retrieve_nav_meta_for_files(
+        &[
+            ("Introduction.md", "# Introduction\n"),
+            ("Part 1/Section/Chapter 1.md", "# Chapter 1\n"),
+        ],
+        true,
+    );
+    let tree = nav_meta.tree.expect("Tree should exist");
+
+    let breadcrumbs = get_breadcrumbs(
+        &tree,
+        &NavMetaNodeId {
+            value: "1_Chapter 1".to_string(),
+        },
+    )
+    .expect("Breadcrumbs should exist");
+
+    assert_eq!(
+        breadcrumbs
+            .iter()
+            .map(|breadcrumb| (breadcrumb.title.as_str(), breadcrumb.path.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("Part 1", PathBuf::from("Part 1")),
+            ("Section", PathBuf::from("Part 1/Section")),
+            ("Chapter 1", PathBuf::from("Part 1/Section/Chapter 1.html")),
+        ]
+    );
+    assert!(breadcrumbs[2].id.is_some());
+
+    assert!(
+        get_breadcrumbs(
+            &tree,
+            &NavMetaNodeId {
+                value: "missing".to_string(),
+            },
+        )
+        .is_none()
+    );
+}
@@ -1,6 +1,30 @@
 use rstest::rstest;
+use std::{fs, path::Path};
+use tempfile::tempdir;
 
-use crate::project_management::check_dependencies;
+use crate::{
+    consts::CURRENT_MANIFEST_VERSION,
+    project_management::{
+        check_dependencies, load_and_convert_manifest, save_manifest, upgrade_manifest_file,
+    },
+};
+
+const V1_MANIFEST: &str = r#"# My book
+markdown_dir = "Markdown"
+version = 1
+
+[[templates]]
+name = "template.tex"
+template_type = "Tex"
+"#;
+
+fn write_manifest(project: &Path, content: &str) {
+    fs::write(project.join("manifest.toml"), content).expect("Failed to write manifest");
+}
+
+fn read_manifest(project: &Path) -> String {
+    fs::read_to_string(project.join("manifest.toml")).expect("Failed to read manifest")
+}
 
 #[rstest]
 fn check_dependencies_valid() {
@@ -13,3 +37,100 @@ fn check_dependencies_invalid() {
     let dependencies = vec!["ls", "invalid_command_that_no_sane_person_would_have"];
     assert!(check_dependencies(dependencies).is_err());
 }
+
+#[rstest]
+fn load_and_convert_manifest_does_not_write_upgrade() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    write_manifest(temp_dir.path(), V1_MANIFEST);
+
+    let manifest = load_and_convert_manifest(&temp_dir.path().join("manifest.toml"))
+        .expect("Failed to load manifest");
+
+    assert_eq!(manifest.version, CURRENT_MANIFEST_VERSION);
+    assert_eq!(read_manifest(temp_dir.path()), V1_MANIFEST);
+    assert!(!temp_dir.path().join("manifest.toml.bak").exists());
+}
+
+#[rstest]
+fn save_manifest_backs_up_older_manifest() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let manifest_path = temp_dir.path().join("manifest.toml");
+    write_manifest(temp_dir.path(), V1_MANIFEST);
+
+    let manifest = load_and_convert_manifest(&manifest_path).expect("Failed to load manifest");
+    save_manifest(&manifest_path, &manifest).expect("Failed to save manifest");
+
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("manifest.toml.bak"))
+            .expect("Failed to read backup"),
+        V1_MANIFEST
+    );
+    assert!(read_manifest(temp_dir.path()).contains("version = 6"));
+}
+
+#[rstest]
+fn save_manifest_does_not_back_up_current_manifest() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let manifest_path = temp_dir.path().join("manifest.toml");
+    write_manifest(temp_dir.path(), V1_MANIFEST);
+    upgrade_manifest_file(Some(temp_dir.path().to_path_buf()), false)
+        .expect("Failed to upgrade manifest");
+    fs::remove_file(temp_dir.path().join("manifest.toml.bak")).expect("Failed to remove backup");
+
+    let manifest = load_and_convert_manifest(&manifest_path).expect("Failed to load manifest");
+    save_manifest(&manifest_path, &manifest).expect("Failed to save manifest");
+
+    assert!(!temp_dir.path().join("manifest.toml.bak").exists());
+}
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn upgrade_manifest_file_upgrades_older_manifest(#[case] dry_run: bool) {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    write_manifest(temp_dir.path(), V1_MANIFEST);
+
+    let upgrade = upgrade_manifest_file(Some(temp_dir.path().to_path_buf()), dry_run)
+        .expect("Failed to upgrade manifest");
+
+    assert_eq!(upgrade.from_version, 1);
+    assert_eq!(upgrade.to_version, CURRENT_MANIFEST_VERSION);
+    assert!(
+        upgrade
+            .diff
+            .contains("--- manifest.toml (v1)\n+++ manifest.toml (v6)\n")
+    );
+    assert!(upgrade.diff.contains("\n-version = 1\n"));
+    assert!(upgrade.diff.contains("\n+version = 6\n"));
+
+    let backup_path = temp_dir.path().join("manifest.toml.bak");
+    if dry_run {
+        assert_eq!(upgrade.backup_path, None);
+        assert!(!backup_path.exists());
+        assert_eq!(read_manifest(temp_dir.path()), V1_MANIFEST);
+    } else {
+        assert_eq!(upgrade.backup_path, Some(backup_path.clone()));
+        assert_eq!(
+            fs::read_to_string(backup_path).expect("Failed to read backup"),
+            V1_MANIFEST
+        );
+        assert!(read_manifest(temp_dir.path()).contains("version = 6"));
+    }
+}
+
+#[rstest]
+fn upgrade_manifest_file_keeps_current_manifest() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    write_manifest(temp_dir.path(), V1_MANIFEST);
+    upgrade_manifest_file(Some(temp_dir.path().to_path_buf()), false)
+        .expect("Failed to upgrade manifest");
+    let manifest_content = read_manifest(temp_dir.path());
+
+    let upgrade = upgrade_manifest_file(Some(temp_dir.path().to_path_buf()), false)
+        .expect("Failed to upgrade manifest");
+
+    assert_eq!(upgrade.from_version, CURRENT_MANIFEST_VERSION);
+    assert!(upgrade.diff.is_empty());
+    assert_eq!(upgrade.backup_path, None);
+    assert_eq!(read_manifest(temp_dir.path()), manifest_content);
+}
@@ -1,8 +1,13 @@
 use crate::{
     consts::CURRENT_MANIFEST_VERSION,
-    manifest_model::{Manifest, Processors},
+    converters::conversion_meta::ConversionMeta,
+    manifest_model::{Manifest, MarkdownProject, Processors, Template},
+    template_type::TemplateType,
 };
 
+// NOTE: Only used by the injections tests, which are disabled until the rewrite of Manifest
+// injection.
+#[allow(dead_code)]
 pub(crate) fn get_default_manifest() -> Manifest {
     Manifest {
         version: CURRENT_MANIFEST_VERSION,
@@ -14,9 +19,60 @@ pub(crate) fn get_default_manifest() -> Manifest {
         },
         smart_clean: None,
         smart_clean_threshold: None,
+        build_cache: None,
         shared_metadata: None,
         metadata_settings: None,
         profiles: None,
         injections: None,
+        include: None,
+        fragments: vec![],
+    }
+}
+
+pub(crate) fn get_default_template(name: &str, template_type: TemplateType) -> Template {
+    Template {
+        name: name.to_string(),
+        template_type,
+        template_file: None,
+        output: None,
+        filters: None,
+        preprocessors: None,
+        processor: None,
+        multi_file_output: None,
+        meta_gen: None,
+        latex: None,
+        metadata_fields: None,
+        bibliography: None,
+        header_injections: None,
+        body_injections: None,
+        footer_injections: None,
+    }
+}
+
+pub(crate) fn get_default_markdown_project(name: &str) -> MarkdownProject {
+    MarkdownProject {
+        name: name.to_string(),
+        path: "Markdown".into(),
+        output: ".".into(),
+        metadata_fields: None,
+        default_profile: None,
+        resources: None,
+        include: None,
+        exclude: None,
+        bibliography: None,
+    }
+}
+
+pub(crate) fn get_default_conversion_meta(
+    markdown_project: &str,
+    template: &str,
+    template_type: TemplateType,
+) -> ConversionMeta {
+    ConversionMeta {
+        markdown_project: markdown_project.to_string(),
+        template: template.to_string(),
+        template_type,
+        profile: None,
+        bibliography: None,
     }
 }
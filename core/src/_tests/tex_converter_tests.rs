@@ -0,0 +1,132 @@
+use rstest::rstest;
+use std::fs;
+use tempfile::tempdir;
+use toml::Table;
+
+use crate::{
+    converters::tex_converter::{escape_latex, generate_tex_metadata, get_latex_dependencies},
+    manifest_model::{LatexEngine, LatexSettings, LatexTool, MetadataSettings},
+};
+
+#[rstest]
+#[case(None, None, None, vec!["xelatex"])]
+#[case(Some(LatexEngine::Lualatex), None, None, vec!["lualatex"])]
+#[case(
+    Some(LatexEngine::Pdflatex),
+    None,
+    Some(vec![LatexTool::Biber, LatexTool::Makeglossaries]),
+    vec!["pdflatex", "biber", "makeglossaries"]
+)]
+#[case(
+    Some(LatexEngine::Lualatex),
+    Some(true),
+    Some(vec![LatexTool::Biber]),
+    vec!["lualatex", "latexmk"]
+)]
+fn test_get_latex_dependencies(
+    #[case] engine: Option<LatexEngine>,
+    #[case] latexmk: Option<bool>,
+    #[case] tools: Option<Vec<LatexTool>>,
+    #[case] expected: Vec<&str>,
+) {
+    let latex_settings = LatexSettings {
+        engine,
+        latexmk,
+        max_passes: None,
+        tools,
+    };
+
+    assert_eq!(get_latex_dependencies(Some(&latex_settings)), expected);
+}
+
+#[rstest]
+fn test_get_latex_dependencies_without_settings() {
+    assert_eq!(get_latex_dependencies(None), vec!["xelatex"]);
+}
+
+#[rstest]
+#[case("Plain Title", "Plain Title")]
+#[case("Salt & Pepper", r"Salt \& Pepper")]
+#[case("100%", r"100\%")]
+#[case("C#", r"C\#")]
+#[case("snake_case", r"snake\_case")]
+#[case("$5", r"\$5")]
+#[case("{braces}", r"\{braces\}")]
+#[case(r"back\slash", r"back\textbackslash{}slash")]
+#[case("x^2", r"x\textasciicircum{}2")]
+#[case("~user", r"\textasciitilde{}user")]
+#[case(r"\textbf{&}", r"\textbackslash{}textbf\{\&\}")]
+#[case("Übersetzung – „Titel“", "Übersetzung – „Titel“")]
+fn test_escape_latex(#[case] value: &str, #[case] expected: &str) {
+    assert_eq!(escape_latex(value), expected);
+}
+
+#[rstest]
+fn test_generate_tex_metadata() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let mut metadata_fields = Table::new();
+    metadata_fields.insert("title".to_string(), "Salt & Pepper".into());
+    metadata_fields.insert("subtitle".to_string(), r"\textit{A Story}".into());
+
+    let metadata_settings = MetadataSettings {
+        metadata_prefix: None,
+        raw_fields: Some(vec!["subtitle".to_string()]),
+    };
+
+    generate_tex_metadata(temp_dir.path(), &metadata_fields, &metadata_settings)
+        .expect("Failed to generate metadata");
+
+    let metadata =
+        fs::read_to_string(temp_dir.path().join("metadata.tex")).expect("Failed to read metadata");
+
+    assert!(metadata.contains(r"\expandafter\def\csname meta@title\endcsname{Salt \& Pepper}"));
+    assert!(
+        metadata.contains(r"\expandafter\def\csname meta@subtitle\endcsname{\textit{A Story}}")
+    );
+}
+
+#[rstest]
+fn test_generate_tex_metadata_with_structured_values() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let metadata_fields: Table = toml::from_str(
+        r#"
+authors = ["Lena Tauchner", "Salt & Pepper"]
+edition = 2
+
+[series]
+name = "Dreams_1"
+tagline = "\\emph{Dreams}"
+"#,
+    )
+    .expect("Failed to parse metadata");
+
+    let metadata_settings = MetadataSettings {
+        metadata_prefix: None,
+        raw_fields: Some(vec!["series.tagline".to_string()]),
+    };
+
+    generate_tex_metadata(temp_dir.path(), &metadata_fields, &metadata_settings)
+        .expect("Failed to generate metadata");
+
+    let metadata =
+        fs::read_to_string(temp_dir.path().join("metadata.tex")).expect("Failed to read metadata");
+
+    for expected in [
+        r"\expandafter\def\csname meta@authors\endcsname{Lena Tauchner, Salt \& Pepper}",
+        r"\expandafter\def\csname meta@authors.count\endcsname{2}",
+        r"\expandafter\def\csname meta@authors.1\endcsname{Lena Tauchner}",
+        r"\expandafter\def\csname meta@authors.2\endcsname{Salt \& Pepper}",
+        r"\expandafter\def\csname meta@edition\endcsname{2}",
+        r"\expandafter\def\csname meta@series.name\endcsname{Dreams\_1}",
+        r"\expandafter\def\csname meta@series.tagline\endcsname{\emph{Dreams}}",
+    ] {
+        assert!(
+            metadata.contains(expected),
+            "Expected '{}' in metadata:\n{}",
+            expected,
+            metadata
+        );
+    }
+}
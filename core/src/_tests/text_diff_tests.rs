@@ -0,0 +1,39 @@
+use rstest::rstest;
+
+use crate::text_diff::get_unified_diff;
+
+#[rstest]
+fn test_get_unified_diff_equal_texts() {
+    assert_eq!(get_unified_diff("old", "new", "a\nb\n", "a\nb\n"), "");
+}
+
+#[rstest]
+fn test_get_unified_diff_changed_line() {
+    let diff = get_unified_diff("old", "new", "a\nb\nc\n", "a\nB\nc\n");
+
+    assert_eq!(diff, "--- old\n+++ new\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n");
+}
+
+#[rstest]
+fn test_get_unified_diff_separate_hunks() {
+    let old = (1..=20).map(|i| i.to_string()).collect::<Vec<_>>();
+    let mut new = old.clone();
+    new[1] = "two".to_string();
+    new.push("21".to_string());
+
+    let diff = get_unified_diff("old", "new", &old.join("\n"), &new.join("\n"));
+
+    assert_eq!(
+        diff,
+        "--- old\n+++ new\n\
+         @@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n 4\n 5\n\
+         @@ -18,3 +18,4 @@\n 18\n 19\n 20\n+21\n"
+    );
+}
+
+#[rstest]
+fn test_get_unified_diff_from_empty_text() {
+    let diff = get_unified_diff("old", "new", "", "a\n");
+
+    assert_eq!(diff, "--- old\n+++ new\n@@ -0,0 +1,1 @@\n+a\n");
+}
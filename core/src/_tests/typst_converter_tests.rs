@@ -0,0 +1,102 @@
+use rstest::rstest;
+use std::fs;
+use tempfile::tempdir;
+use toml::Table;
+
+use crate::{
+    converters::typst_converter::{escape_typst_string, generate_typst_metadata},
+    manifest_model::MetadataSettings,
+};
+
+#[rstest]
+#[case("Plain Title", "Plain Title")]
+#[case(r#"The "Quoted" Title"#, r#"The \"Quoted\" Title"#)]
+#[case(r"back\slash", r"back\\slash")]
+#[case(r#"\""#, r#"\\\""#)]
+#[case("first\nsecond", r"first\nsecond")]
+#[case("carriage\rreturn", r"carriage\rreturn")]
+#[case("tab\tstop", r"tab\tstop")]
+#[case("#let x = 1 & $y$ * _z_", "#let x = 1 & $y$ * _z_")]
+#[case("Übersetzung – „Titel“", "Übersetzung – „Titel“")]
+fn test_escape_typst_string(#[case] value: &str, #[case] expected: &str) {
+    assert_eq!(escape_typst_string(value), expected);
+}
+
+#[rstest]
+fn test_generate_typst_metadata() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let mut metadata_fields = Table::new();
+    metadata_fields.insert("title".to_string(), r#"Say "Hi" \o/"#.into());
+    metadata_fields.insert("subtitle".to_string(), "[A *bold* story]".into());
+
+    let metadata_settings = MetadataSettings {
+        metadata_prefix: Some("book".to_string()),
+        raw_fields: Some(vec!["subtitle".to_string()]),
+    };
+
+    generate_typst_metadata(temp_dir.path(), &metadata_fields, &metadata_settings)
+        .expect("Failed to generate metadata");
+
+    let metadata =
+        fs::read_to_string(temp_dir.path().join("metadata.typ")).expect("Failed to read metadata");
+
+    assert_eq!(
+        metadata,
+        "#let book = (\n  subtitle: [A *bold* story],\n  title: \"Say \\\"Hi\\\" \\\\o/\",\n)"
+    );
+}
+
+#[rstest]
+fn test_generate_typst_metadata_with_structured_values() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let metadata_fields: Table = toml::from_str(
+        r#"
+authors = ["Lena Tauchner", "Jane \"JD\" Doe"]
+editors = ["Max Mustermann"]
+keywords = []
+edition = 2
+price = 10.0
+published = true
+release = 2025-03-01
+printed = 2025-03-01T12:30:00Z
+"page count" = 300
+
+[series]
+name = "Dreams"
+tagline = "[*Dreams*]"
+
+[empty]
+"#,
+    )
+    .expect("Failed to parse metadata");
+
+    let metadata_settings = MetadataSettings {
+        metadata_prefix: None,
+        raw_fields: Some(vec!["series.tagline".to_string()]),
+    };
+
+    generate_typst_metadata(temp_dir.path(), &metadata_fields, &metadata_settings)
+        .expect("Failed to generate metadata");
+
+    let metadata =
+        fs::read_to_string(temp_dir.path().join("metadata.typ")).expect("Failed to read metadata");
+
+    assert_eq!(
+        metadata,
+        r#"#let meta = (
+  authors: ("Lena Tauchner", "Jane \"JD\" Doe"),
+  edition: 2,
+  editors: ("Max Mustermann",),
+  empty: (:),
+  keywords: (),
+  "page count": 300,
+  price: 10.0,
+  printed: datetime(year: 2025, month: 3, day: 1, hour: 12, minute: 30, second: 0),
+  published: true,
+  release: datetime(year: 2025, month: 3, day: 1),
+  series: (name: "Dreams", tagline: [*Dreams*]),
+)"#
+    );
+}
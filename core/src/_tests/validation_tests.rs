@@ -0,0 +1,204 @@
+use rstest::rstest;
+use std::{fs, path::Path};
+use tempfile::tempdir;
+
+use crate::validation::{Diagnostic, DiagnosticKind, Severity, validate};
+
+const VALID_MANIFEST: &str = r#"version = 6
+
+[[markdown_projects]]
+name = "Book"
+path = "Markdown"
+output = "."
+default_profile = "print"
+resources = ["cover.png"]
+
+[[templates]]
+name = "pdf"
+template_type = "Tex"
+template_file = "template.tex"
+filters = ["filters/"]
+processor = "Shell escape"
+header_injections = ["Header"]
+
+[templates.preprocessors]
+preprocessors = ["Listings"]
+combined_output = "output.tex"
+
+[[templates]]
+name = "html"
+template_type = "CustomPreprocessors"
+multi_file_output = true
+footer_injections = ["Footer"]
+
+[templates.preprocessors]
+preprocessors = []
+output_extension = "html"
+
+[[custom_processors.preprocessors]]
+name = "Listings"
+cli_args = ["--listings"]
+
+[[custom_processors.processors]]
+name = "Shell escape"
+processor_args = ["-shell-escape"]
+
+[[profiles]]
+name = "print"
+templates = ["pdf"]
+
+[[injections]]
+name = "Header"
+files = ["header.tex"]
+
+[[injections]]
+name = "Footer"
+files = ["cover.png", "html_convdir/Chapter 1.md"]
+"#;
+
+fn create_project(project: &Path, manifest: &str) {
+    fs::write(project.join("manifest.toml"), manifest).expect("Failed to write manifest");
+    fs::create_dir_all(project.join("template/filters")).expect("Failed to create filters");
+    fs::write(project.join("template/header.tex"), "").expect("Failed to write header");
+    fs::create_dir_all(project.join("Markdown")).expect("Failed to create markdown directory");
+    fs::write(project.join("Markdown/cover.png"), "").expect("Failed to write cover");
+    fs::write(project.join("Markdown/Chapter 1.md"), "").expect("Failed to write chapter");
+}
+
+fn get_kinds(diagnostics: &[Diagnostic]) -> Vec<(Severity, DiagnosticKind)> {
+    diagnostics.iter().map(|d| (d.severity, d.kind)).collect()
+}
+
+fn validate_manifest(manifest: &str) -> Vec<Diagnostic> {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    create_project(temp_dir.path(), manifest);
+
+    validate(Some(temp_dir.path().to_path_buf())).expect("Failed to validate project")
+}
+
+#[rstest]
+fn test_validate_valid_project() {
+    assert_eq!(validate_manifest(VALID_MANIFEST), vec![]);
+}
+
+#[rstest]
+#[case(
+    r#"preprocessors = ["Listings"]"#,
+    r#"preprocessors = ["Listings", "Missing"]"#,
+    DiagnosticKind::MissingPreprocessor
+)]
+#[case(
+    r#"processor = "Shell escape""#,
+    r#"processor = "Missing""#,
+    DiagnosticKind::MissingProcessor
+)]
+#[case(
+    r#"header_injections = ["Header"]"#,
+    r#"header_injections = ["Header", "Missing"]"#,
+    DiagnosticKind::MissingInjection
+)]
+#[case(
+    r#"templates = ["pdf"]"#,
+    r#"templates = ["pdf", "epub"]"#,
+    DiagnosticKind::MissingTemplate
+)]
+#[case(
+    r#"default_profile = "print""#,
+    r#"default_profile = "ebook""#,
+    DiagnosticKind::MissingProfile
+)]
+#[case(
+    r#"filters = ["filters/"]"#,
+    r#"filters = ["missing.lua"]"#,
+    DiagnosticKind::MissingFilter
+)]
+#[case(
+    r#"files = ["header.tex"]"#,
+    r#"files = ["missing.tex"]"#,
+    DiagnosticKind::MissingInjectionFile
+)]
+#[case(
+    r#""html_convdir/Chapter 1.md""#,
+    r#""html_convdir/Chapter 2.md""#,
+    DiagnosticKind::MissingInjectionFile
+)]
+#[case(
+    r#"resources = ["cover.png"]"#,
+    r#"resources = ["cover.png", "back.png"]"#,
+    DiagnosticKind::MissingResource
+)]
+#[case(
+    r#"output_extension = "html""#,
+    "output_extension = \"html\"\ncombined_output = \"index.html\"",
+    DiagnosticKind::InvalidMultiFileOutput
+)]
+#[case(
+    r#"output_extension = "html""#,
+    "",
+    DiagnosticKind::InvalidMultiFileOutput
+)]
+#[case(
+    r#"template_type = "CustomPreprocessors""#,
+    r#"template_type = "Epub""#,
+    DiagnosticKind::InvalidMultiFileOutput
+)]
+#[case(r#"name = "html""#, r#"name = "pdf""#, DiagnosticKind::DuplicateName)]
+fn test_validate_errors(
+    #[case] valid: &str,
+    #[case] invalid: &str,
+    #[case] expected: DiagnosticKind,
+) {
+    let diagnostics = validate_manifest(&VALID_MANIFEST.replacen(valid, invalid, 1))
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error)
+        .collect::<Vec<_>>();
+
+    assert_eq!(get_kinds(&diagnostics), vec![(Severity::Error, expected)]);
+}
+
+#[rstest]
+fn test_validate_unused_entries() {
+    let manifest = format!(
+        r#"{}
+[[custom_processors.preprocessors]]
+name = "Unused preprocessor"
+cli_args = []
+
+[[custom_processors.processors]]
+name = "Unused processor"
+processor_args = []
+
+[[injections]]
+name = "Unused injection"
+files = ["header.tex"]
+"#,
+        VALID_MANIFEST
+    );
+
+    let diagnostics = validate_manifest(&manifest);
+
+    assert_eq!(
+        get_kinds(&diagnostics),
+        vec![
+            (Severity::Warning, DiagnosticKind::UnusedPreprocessor),
+            (Severity::Warning, DiagnosticKind::UnusedProcessor),
+            (Severity::Warning, DiagnosticKind::UnusedInjection),
+        ]
+    );
+    assert_eq!(
+        diagnostics[0].location,
+        "preprocessor 'Unused preprocessor'"
+    );
+}
+
+#[rstest]
+fn test_validate_reports_location() {
+    let diagnostics = validate_manifest(
+        &VALID_MANIFEST.replace(r#"processor = "Shell escape""#, r#"processor = "Missing""#),
+    );
+
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "template 'pdf': Processor 'Missing' does not exist."
+    );
+}
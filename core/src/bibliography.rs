@@ -0,0 +1,201 @@
+use color_eyre::eyre::{Result, eyre};
+use log::debug;
+use serde::Serialize;
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    manifest_model::{BibliographySettings, CitationMode, MarkdownProject, Template},
+    template_type::TemplateType,
+};
+
+/// The bibliography of a conversion, merged from the bibliography settings of the markdown
+/// project and the template.
+///
+/// # Fields
+///
+/// * `files` - The bibliography files, relative to the compiled directory.
+/// * `csl` - The CSL style file, relative to the compiled directory.
+/// * `mode` - How citations are processed.
+/// * `link_citations` - Whether citations link to their bibliography entry.
+/// * `sources` - The bibliography files and CSL style file in the project directory.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct Bibliography {
+    pub(crate) files: Vec<PathBuf>,
+    pub(crate) csl: Option<PathBuf>,
+    pub(crate) mode: CitationMode,
+    pub(crate) link_citations: Option<bool>,
+    #[serde(skip)]
+    pub(crate) sources: Vec<PathBuf>,
+}
+
+/// Merges the bibliography settings of the markdown project and the template.
+///
+/// The bibliography files of both are used. The CSL style, mode and citation options of the
+/// template take precedence over the ones of the markdown project. The files of the markdown
+/// project are copied to the compiled directory like resources, the files of the template are
+/// copied along with the template directory.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory.
+/// * `markdown_project` - The markdown project to convert.
+/// * `template` - The template to convert with.
+///
+/// # Returns
+///
+/// A Result containing either an error or the bibliography, if the markdown project or the
+/// template has bibliography settings.
+pub(crate) fn get_bibliography(
+    project: &Path,
+    markdown_project: &MarkdownProject,
+    template: &Template,
+) -> Result<Option<Bibliography>> {
+    if markdown_project.bibliography.is_none() && template.bibliography.is_none() {
+        return Ok(None);
+    }
+
+    let markdown_project_settings = markdown_project.bibliography.clone().unwrap_or_default();
+    let template_settings = template.bibliography.clone().unwrap_or_default();
+
+    let markdown_dir = project.join(&markdown_project.path);
+    let template_dir = project.join("template");
+
+    let mut files = vec![];
+    let mut sources = vec![];
+
+    for file in markdown_project_settings.files.iter().flatten() {
+        files.push(get_resource_file_name(file));
+        sources.push(markdown_dir.join(file));
+    }
+
+    for file in template_settings.files.iter().flatten() {
+        files.push(file.clone());
+        sources.push(template_dir.join(file));
+    }
+
+    // Bibliography files of the markdown project are copied to the root of the compiled
+    // directory, where they must not overwrite each other or the files of the template.
+    for (index, file) in files.iter().enumerate() {
+        if let Some(other) = files[..index].iter().position(|f| f == file) {
+            return Err(eyre!(
+                "Bibliography files '{}' and '{}' are both copied to '{}' in the compiled directory. Rename one of them.",
+                sources[other].display(),
+                sources[index].display(),
+                file.display()
+            ));
+        }
+    }
+
+    let csl = match (&template_settings.csl, &markdown_project_settings.csl) {
+        (Some(csl), _) => {
+            sources.push(template_dir.join(csl));
+            Some(csl.clone())
+        }
+        (None, Some(csl)) => {
+            sources.push(markdown_dir.join(csl));
+            Some(get_resource_file_name(csl))
+        }
+        (None, None) => None,
+    };
+
+    let mode = template_settings
+        .mode
+        .or(markdown_project_settings.mode)
+        .unwrap_or_default();
+
+    if mode == CitationMode::Biblatex && template.template_type != TemplateType::Tex {
+        return Err(eyre!(
+            "Template '{}' uses the Biblatex citation mode, which is only supported by Tex templates.",
+            template.name
+        ));
+    }
+
+    debug!(
+        "Using {} bibliography files in {:?} mode for template '{}'.",
+        files.len(),
+        mode,
+        template.name
+    );
+
+    Ok(Some(Bibliography {
+        files,
+        csl,
+        mode,
+        link_citations: template_settings
+            .link_citations
+            .or(markdown_project_settings.link_citations),
+        sources,
+    }))
+}
+
+/// Returns the resources of a markdown project, including its bibliography files and CSL style
+/// file. All paths are relative to the markdown directory.
+pub(crate) fn get_resources(markdown_project: &MarkdownProject) -> Vec<PathBuf> {
+    let mut resources = markdown_project.resources.clone().unwrap_or_default();
+
+    for file in get_bibliography_paths(&markdown_project.bibliography) {
+        if !resources.contains(&file) {
+            resources.push(file);
+        }
+    }
+
+    resources
+}
+
+/// Returns the bibliography files and the CSL style file of bibliography settings.
+pub(crate) fn get_bibliography_paths(settings: &Option<BibliographySettings>) -> Vec<PathBuf> {
+    let Some(settings) = settings else {
+        return vec![];
+    };
+
+    settings
+        .files
+        .iter()
+        .flatten()
+        .chain(settings.csl.iter())
+        .cloned()
+        .collect()
+}
+
+/// Adds the citation arguments of the bibliography to the pandoc command.
+///
+/// # Arguments
+///
+/// * `bibliography` - The bibliography of the conversion, if any.
+/// * `pandoc` - The pandoc command.
+pub(crate) fn add_bibliography_args(bibliography: &Option<Bibliography>, pandoc: &mut Command) {
+    let Some(bibliography) = bibliography else {
+        return;
+    };
+
+    debug!("Adding bibliography to pandoc conversion parameters.");
+
+    match bibliography.mode {
+        CitationMode::Citeproc => pandoc.arg("--citeproc"),
+        CitationMode::Biblatex => pandoc.arg("--biblatex"),
+    };
+
+    for file in &bibliography.files {
+        pandoc.arg("--bibliography").arg(file);
+    }
+
+    if bibliography.mode == CitationMode::Citeproc {
+        if let Some(csl) = &bibliography.csl {
+            pandoc.arg("--csl").arg(csl);
+        }
+
+        if let Some(link_citations) = bibliography.link_citations {
+            pandoc
+                .arg("-M")
+                .arg(format!("link-citations={}", link_citations));
+        }
+    }
+}
+
+/// Resources are copied to the root of the compiled directory.
+fn get_resource_file_name(path: &Path) -> PathBuf {
+    PathBuf::from(path.file_name().unwrap_or(path.as_os_str()))
+}
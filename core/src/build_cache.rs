@@ -0,0 +1,392 @@
+use color_eyre::eyre::Result;
+use fs_extra::{dir, file};
+use log::{debug, info};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use toml::Table;
+
+use crate::{
+    converters::{
+        conditional_content::CONDITIONAL_CONTENT_FILTER, conversion_meta::ConversionMeta,
+    },
+    manifest_model::{Injection, MetadataSettings, PreProcessor, Processors, Template},
+    nav_meta_generation::NavMeta,
+};
+
+pub(crate) const BUILD_CACHE_DIRECTORY: &str = ".tiefdown_cache";
+
+const CHUNKS_DIRECTORY: &str = "chunks";
+const ARTIFACTS_DIRECTORY: &str = "artifacts";
+
+static TEMPORARY_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// The inputs of a conversion task the build cache fingerprints.
+///
+/// # Fields
+///
+/// * `conversion_meta` - The conversion the task runs.
+/// * `template` - The template of the task.
+/// * `metadata_fields` - The merged metadata fields of the task.
+/// * `metadata_settings` - The metadata settings of the project.
+/// * `custom_processors` - The custom processors of the project.
+/// * `injections` - The injections of the project.
+/// * `template_directory_path` - The template directory of the project.
+/// * `resources` - The resources of the markdown project.
+/// * `conversion_input_dir` - The copy of the markdown project the task converts.
+///   * Includes assets like images or included files that are only read by the final processor.
+pub(crate) struct TaskInputs<'a> {
+    pub(crate) conversion_meta: &'a ConversionMeta,
+    pub(crate) template: &'a Template,
+    pub(crate) metadata_fields: &'a Table,
+    pub(crate) metadata_settings: &'a Option<MetadataSettings>,
+    pub(crate) custom_processors: &'a Processors,
+    pub(crate) injections: &'a Vec<Injection>,
+    pub(crate) template_directory_path: &'a Path,
+    pub(crate) resources: &'a Vec<PathBuf>,
+    pub(crate) conversion_input_dir: &'a Path,
+}
+
+/// Persistent, content addressed cache for conversion results.
+///
+/// The cache stores the output of every preprocessing chunk keyed on the input file contents,
+/// the preprocessor definition, the metadata and the lua filters used. Additionally, the result
+/// of the final processor (xelatex, typst, pandoc) is stored for every markdown project and
+/// template combination, keyed on everything the processor reads. Only the last successful
+/// processor result is kept per combination.
+///
+/// A disabled cache never hits and never writes anything.
+#[derive(Clone)]
+pub(crate) struct BuildCache {
+    cache_directory_path: Option<PathBuf>,
+    slot: String,
+    task_fingerprint: String,
+    template_fingerprint: String,
+}
+
+impl BuildCache {
+    pub(crate) fn open(project_directory_path: &Path) -> Result<BuildCache> {
+        let cache_directory_path = project_directory_path.join(BUILD_CACHE_DIRECTORY);
+
+        fs::create_dir_all(cache_directory_path.join(CHUNKS_DIRECTORY))?;
+        fs::create_dir_all(cache_directory_path.join(ARTIFACTS_DIRECTORY))?;
+
+        debug!(
+            "Opened build cache at '{}'.",
+            cache_directory_path.display()
+        );
+
+        Ok(BuildCache {
+            cache_directory_path: Some(cache_directory_path),
+            slot: String::new(),
+            task_fingerprint: String::new(),
+            template_fingerprint: String::new(),
+        })
+    }
+
+    pub(crate) fn disabled() -> BuildCache {
+        BuildCache {
+            cache_directory_path: None,
+            slot: String::new(),
+            task_fingerprint: String::new(),
+            template_fingerprint: String::new(),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.cache_directory_path.is_some()
+    }
+
+    /// Creates the cache view for a single conversion task.
+    ///
+    /// The task fingerprint covers everything that influences every step of the conversion,
+    /// the template fingerprint covers the files that are only read by the final processor.
+    pub(crate) fn for_task(&self, task_inputs: &TaskInputs) -> Result<BuildCache> {
+        if !self.is_enabled() {
+            return Ok(BuildCache::disabled());
+        }
+
+        let TaskInputs {
+            conversion_meta,
+            template,
+            metadata_fields,
+            metadata_settings,
+            custom_processors,
+            injections,
+            template_directory_path,
+            resources,
+            conversion_input_dir,
+        } = task_inputs;
+
+        let mut slot = CacheKey::new();
+        slot.update_str(&conversion_meta.markdown_project);
+        slot.update_str(&template.name);
+
+        let mut task_fingerprint = CacheKey::new();
+        task_fingerprint.update_serialized(conversion_meta)?;
+        task_fingerprint.update_serialized(template)?;
+        task_fingerprint.update_serialized(metadata_fields)?;
+        task_fingerprint.update_serialized(metadata_settings)?;
+        task_fingerprint.update_serialized(custom_processors)?;
+        task_fingerprint.update_serialized(injections)?;
+        for source in conversion_meta
+            .bibliography
+            .iter()
+            .flat_map(|bibliography| bibliography.sources.iter())
+        {
+            task_fingerprint.update_path(source)?;
+        }
+
+        let mut template_fingerprint = CacheKey::new();
+        template_fingerprint.update_path(template_directory_path)?;
+        for resource in resources.iter() {
+            template_fingerprint.update_path(resource)?;
+        }
+        template_fingerprint.update_path(conversion_input_dir)?;
+
+        Ok(BuildCache {
+            cache_directory_path: self.cache_directory_path.clone(),
+            slot: slot.finish(),
+            task_fingerprint: task_fingerprint.finish(),
+            template_fingerprint: template_fingerprint.finish(),
+        })
+    }
+
+    /// Computes the cache key of a preprocessing chunk.
+    ///
+    /// Returns `None` if the cache is disabled.
+    pub(crate) fn preprocessing_key(
+        &self,
+        template: &Template,
+        compiled_directory_path: &Path,
+        metadata_file: &Option<PathBuf>,
+        nav_meta_data: &Option<(NavMeta, PathBuf)>,
+        preprocessor: &PreProcessor,
+        files: &Vec<PathBuf>,
+    ) -> Result<Option<String>> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+
+        let mut key = CacheKey::new();
+        key.update_str(&self.task_fingerprint);
+        key.update_serialized(preprocessor)?;
+
+        key.update_str(CONDITIONAL_CONTENT_FILTER);
+        for filter in template.filters.clone().unwrap_or_default() {
+            key.update_path(&compiled_directory_path.join(filter))?;
+        }
+
+        if let Some(metadata_file) = metadata_file {
+            key.update_path(&compiled_directory_path.join(metadata_file))?;
+        }
+
+        if let Some((nav_meta, _)) = nav_meta_data {
+            key.update_serialized(nav_meta)?;
+        }
+
+        for file in files {
+            key.update_str(&file.to_string_lossy());
+            key.update_path(&compiled_directory_path.join(file))?;
+        }
+
+        Ok(Some(key.finish()))
+    }
+
+    pub(crate) fn get_chunk(&self, key: &Option<String>) -> Option<String> {
+        let path = self.chunk_path(key)?;
+
+        let content = fs::read_to_string(path).ok()?;
+        debug!("Build cache hit for preprocessing chunk {}.", key.as_ref()?);
+
+        Some(content)
+    }
+
+    pub(crate) fn store_chunk(&self, key: &Option<String>, content: &str) -> Result<()> {
+        if let Some(path) = self.chunk_path(key) {
+            // Parallel tasks can store the same chunk, so the chunk is written to a unique
+            // temporary file first and moved into place, which never exposes partial content.
+            let temporary_path = path.with_extension(format!(
+                "{}.{}.tmp",
+                std::process::id(),
+                TEMPORARY_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::write(&temporary_path, content)?;
+            if let Err(e) = fs::rename(&temporary_path, &path) {
+                let _ = fs::remove_file(&temporary_path);
+                return Err(e.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the final processor of a conversion, unless its inputs are identical to the last
+    /// successful build of the same markdown project and template.
+    ///
+    /// # Arguments
+    ///
+    /// * `compiled_directory_path` - The directory the processor runs in.
+    /// * `inputs` - The generated files the processor reads, relative to the compiled directory.
+    /// * `processor_args` - The arguments passed to the processor.
+    /// * `output_path` - The result of the processor. Can be a file or a directory.
+    /// * `processor` - The closure running the processor.
+    pub(crate) fn run_processor(
+        &self,
+        compiled_directory_path: &Path,
+        inputs: &[&Path],
+        processor_args: &[String],
+        output_path: &Path,
+        processor: impl FnOnce() -> Result<()>,
+    ) -> Result<()> {
+        let Some(cache_directory_path) = &self.cache_directory_path else {
+            return processor();
+        };
+
+        let mut key = CacheKey::new();
+        key.update_str(&self.task_fingerprint);
+        key.update_str(&self.template_fingerprint);
+        for arg in processor_args {
+            key.update_str(arg);
+        }
+        for input in inputs {
+            key.update_str(&input.to_string_lossy());
+            key.update_path(&compiled_directory_path.join(input))?;
+        }
+        let key = key.finish();
+
+        let slot_path = cache_directory_path
+            .join(ARTIFACTS_DIRECTORY)
+            .join(&self.slot);
+        let artifact_path = slot_path.join(&key);
+        let output_name = output_path.file_name().unwrap_or_default();
+
+        if artifact_path.join(output_name).exists() {
+            info!("Inputs are unchanged since the last build, reusing the previous result.");
+            copy_path(&artifact_path.join(output_name), output_path)?;
+            return Ok(());
+        }
+
+        processor()?;
+
+        if !output_path.exists() {
+            debug!(
+                "Processor did not produce '{}', not caching the result.",
+                output_path.display()
+            );
+            return Ok(());
+        }
+
+        if slot_path.exists() {
+            dir::remove(&slot_path)?;
+        }
+        fs::create_dir_all(&artifact_path)?;
+        copy_path(output_path, &artifact_path.join(output_name))?;
+        debug!("Stored processor result in build cache under {}.", key);
+
+        Ok(())
+    }
+
+    fn chunk_path(&self, key: &Option<String>) -> Option<PathBuf> {
+        let cache_directory_path = self.cache_directory_path.as_ref()?;
+        let key = key.as_ref()?;
+
+        Some(cache_directory_path.join(CHUNKS_DIRECTORY).join(key))
+    }
+}
+
+/// Removes the build cache of a project.
+pub(crate) fn clear_build_cache(project_directory_path: &Path) -> Result<()> {
+    let cache_directory_path = project_directory_path.join(BUILD_CACHE_DIRECTORY);
+
+    if cache_directory_path.exists() {
+        debug!(
+            "Removing build cache at '{}'.",
+            cache_directory_path.display()
+        );
+        dir::remove(cache_directory_path)?;
+    }
+
+    Ok(())
+}
+
+struct CacheKey {
+    hasher: Sha256,
+}
+
+impl CacheKey {
+    fn new() -> CacheKey {
+        CacheKey {
+            hasher: Sha256::new(),
+        }
+    }
+
+    fn update_str(&mut self, value: &str) {
+        self.hasher.update(value.len().to_le_bytes());
+        self.hasher.update(value.as_bytes());
+    }
+
+    fn update_serialized<T: Serialize>(&mut self, value: &T) -> Result<()> {
+        self.update_str(&serde_json::to_string(value)?);
+        Ok(())
+    }
+
+    fn update_path(&mut self, path: &Path) -> Result<()> {
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|e| e.map(|e| e.path()))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            entries.sort();
+
+            for entry in entries {
+                self.update_str(&entry.file_name().unwrap_or_default().to_string_lossy());
+                self.update_path(&entry)?;
+            }
+        } else if path.is_file() {
+            let content = fs::read(path)?;
+            self.hasher.update(content.len().to_le_bytes());
+            self.hasher.update(&content);
+        } else {
+            self.update_str("<missing>");
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> String {
+        self.hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+fn copy_path(source: &Path, destination: &Path) -> Result<()> {
+    if source.is_dir() {
+        if destination.exists() {
+            dir::remove(destination)?;
+        }
+        fs::create_dir_all(destination)?;
+        dir::copy(
+            source,
+            destination,
+            &dir::CopyOptions::new().overwrite(true).content_only(true),
+        )?;
+    } else {
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        file::copy(
+            source,
+            destination,
+            &file::CopyOptions::new().overwrite(true),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,284 @@
+use color_eyre::eyre::Result;
+use log::info;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    conversion::ConversionTask, conversion_recorder::ConversionRecord,
+    injections::RenderingInjections, template_type::TemplateType,
+};
+
+/// The name of the build report written to the build directory.
+pub const BUILD_REPORT_FILE_NAME: &str = "build_report.json";
+
+/// A machine-readable report of a conversion run.
+///
+/// Written to the build directory as `build_report.json` after every conversion, and returned
+/// by `conversion::convert`.
+///
+/// # Fields
+///
+/// * `build_directory` - The build directory of the conversion, relative to the project.
+/// * `started_at` - The time the conversion started, in RFC 3339 format.
+/// * `duration_seconds` - The duration of the whole conversion.
+/// * `success` - Whether all conversion tasks succeeded.
+/// * `tasks` - A report for every conversion task that was run, in queue order.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BuildReport {
+    pub build_directory: PathBuf,
+    pub started_at: String,
+    pub duration_seconds: f64,
+    pub success: bool,
+    pub tasks: Vec<TaskReport>,
+}
+
+/// A report of a single conversion task.
+///
+/// All paths are relative to the build directory, except for the artifact paths, which are
+/// relative to the project directory.
+///
+/// # Fields
+///
+/// * `markdown_project` - The name of the markdown project.
+/// * `template` - The name of the template.
+/// * `template_type` - The type of the template, if the template could be resolved.
+/// * `success` - Whether the task succeeded.
+/// * `error` - The error the task failed with.
+/// * `duration_seconds` - The duration of the task.
+/// * `input_files` - The input files, in conversion order.
+/// * `injections` - The resolved header, body and footer injections.
+/// * `chunks` - The preprocessing chunks and the preprocessor chosen for each.
+/// * `commands` - The external commands that were run, with timings and exit codes.
+/// * `warnings` - Warnings emitted by the external commands or found in their logs.
+/// * `artifacts` - The files written to the output directory, with sizes and checksums.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TaskReport {
+    pub markdown_project: String,
+    pub template: String,
+    pub template_type: Option<TemplateType>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub duration_seconds: f64,
+    pub input_files: Vec<PathBuf>,
+    pub injections: InjectionsReport,
+    pub chunks: Vec<ChunkReport>,
+    pub commands: Vec<CommandReport>,
+    pub warnings: Vec<WarningReport>,
+    pub artifacts: Vec<ArtifactReport>,
+}
+
+/// The injections used by a conversion task.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct InjectionsReport {
+    pub header: Vec<PathBuf>,
+    pub body: Vec<PathBuf>,
+    pub footer: Vec<PathBuf>,
+}
+
+/// A preprocessing chunk. `cached` is set if the result was taken from the build cache.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChunkReport {
+    pub files: Vec<PathBuf>,
+    pub extension: String,
+    pub preprocessor: String,
+    pub cached: bool,
+}
+
+/// An external command run during a conversion task.
+///
+/// The exit code is missing if the command could not be started or was terminated by a signal.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommandReport {
+    pub command_line: String,
+    pub working_directory: Option<PathBuf>,
+    pub duration_seconds: Option<f64>,
+    pub exit_code: Option<i32>,
+}
+
+/// A warning emitted during a conversion task.
+///
+/// # Fields
+///
+/// * `source` - The name of the command the warning originates from.
+/// * `kind` - The kind of the warning.
+/// * `message` - The warning message.
+/// * `file` - The file the warning refers to, as reported by the command.
+/// * `line` - The line in `file` the warning refers to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WarningReport {
+    pub source: String,
+    pub kind: WarningKind,
+    pub message: String,
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+}
+
+/// The kind of a warning.
+///
+/// * `Stderr` - A line the command wrote to stderr.
+/// * `ExitCode` - A non-zero exit code that did not fail the conversion.
+/// * `UndefinedReference` - An undefined reference or citation in a LaTeX document.
+/// * `OverfullBox` - An overfull box in a LaTeX document.
+/// * `MissingFont` - A missing font, font shape or character in a LaTeX document.
+/// * `RerunLimit` - LaTeX still asked for a rerun after the maximum number of passes.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    Stderr,
+    ExitCode,
+    UndefinedReference,
+    OverfullBox,
+    MissingFont,
+    RerunLimit,
+}
+
+/// A file written to the output directory of the project.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ArtifactReport {
+    pub path: PathBuf,
+    pub size: u64,
+    pub sha256: String,
+}
+
+pub(crate) fn create_task_report(
+    project_directory_path: &Path,
+    build_directory_path: &Path,
+    conversion_task: &ConversionTask,
+    template_type: Option<TemplateType>,
+    result: &Result<()>,
+    duration: Duration,
+    record: ConversionRecord,
+) -> Result<TaskReport> {
+    let markdown_project_directory_path =
+        build_directory_path.join(&conversion_task.markdown_project.output);
+    let relative_to_build = |path: &PathBuf| {
+        get_build_relative_path(path, &markdown_project_directory_path, build_directory_path)
+    };
+    let injections = record.injections.unwrap_or(RenderingInjections::new());
+
+    Ok(TaskReport {
+        markdown_project: conversion_task.markdown_project.name.clone(),
+        template: conversion_task.template.clone(),
+        template_type,
+        success: result.is_ok(),
+        error: result.as_ref().err().map(|e| e.to_string()),
+        duration_seconds: duration.as_secs_f64(),
+        input_files: record.input_files.iter().map(relative_to_build).collect(),
+        injections: InjectionsReport {
+            header: injections
+                .header_injections
+                .iter()
+                .map(relative_to_build)
+                .collect(),
+            body: injections
+                .body_injections
+                .iter()
+                .map(relative_to_build)
+                .collect(),
+            footer: injections
+                .footer_injections
+                .iter()
+                .map(relative_to_build)
+                .collect(),
+        },
+        chunks: record
+            .chunks
+            .into_iter()
+            .map(|chunk| ChunkReport {
+                files: chunk.files.iter().map(relative_to_build).collect(),
+                extension: chunk.extension,
+                preprocessor: chunk.preprocessor,
+                cached: chunk.cached,
+            })
+            .collect(),
+        commands: record
+            .commands
+            .into_iter()
+            .map(|command| CommandReport {
+                command_line: command.command_line,
+                working_directory: command.working_directory.as_ref().map(relative_to_build),
+                duration_seconds: command.duration.map(|d| d.as_secs_f64()),
+                exit_code: command.exit_code,
+            })
+            .collect(),
+        warnings: record.warnings,
+        artifacts: record
+            .artifacts
+            .iter()
+            .map(|artifact| get_artifact_report(project_directory_path, artifact))
+            .collect::<Result<Vec<_>>>()?,
+    })
+}
+
+/// Writes the build report to the build directory and returns its path.
+pub(crate) fn write_build_report(
+    build_report: &BuildReport,
+    build_directory_path: &Path,
+) -> Result<PathBuf> {
+    let build_report_path = build_directory_path.join(BUILD_REPORT_FILE_NAME);
+
+    fs::write(
+        &build_report_path,
+        serde_json::to_string_pretty(build_report)?,
+    )?;
+
+    info!("Build report written to {}.", build_report_path.display());
+
+    Ok(build_report_path)
+}
+
+/// Makes a path recorded during conversion relative to the build directory.
+///
+/// Paths not within the build directory are relative to the markdown project directory in the
+/// build directory.
+pub(crate) fn get_build_relative_path(
+    path: &Path,
+    markdown_project_directory_path: &Path,
+    build_directory_path: &Path,
+) -> PathBuf {
+    let path = if path.starts_with(build_directory_path) {
+        path.to_path_buf()
+    } else {
+        markdown_project_directory_path.join(path)
+    };
+
+    match path.strip_prefix(build_directory_path) {
+        Ok(path) if path.as_os_str().is_empty() => PathBuf::from("."),
+        Ok(path) => without_current_dir(path),
+        Err(_) => path,
+    }
+}
+
+/// Removes `.` components from a path.
+pub(crate) fn without_current_dir(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| *c != Component::CurDir)
+        .collect()
+}
+
+fn get_artifact_report(
+    project_directory_path: &Path,
+    artifact_path: &Path,
+) -> Result<ArtifactReport> {
+    let content = fs::read(artifact_path)?;
+
+    let sha256 = Sha256::digest(&content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    Ok(ArtifactReport {
+        path: without_current_dir(
+            artifact_path
+                .strip_prefix(project_directory_path)
+                .unwrap_or(artifact_path),
+        ),
+        size: content.len() as u64,
+        sha256,
+    })
+}
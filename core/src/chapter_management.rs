@@ -0,0 +1,643 @@
+use color_eyre::eyre::{Result, eyre};
+use log::{debug, info};
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    bibliography::get_resources,
+    file_retrieval::{
+        FileOrder, ORDER_NUMBER_REGEX, get_file_order, is_order_file, retrieve_file_order_number,
+        sort_paths,
+    },
+    input_filter::InputFilter,
+    manifest_model::{Manifest, MarkdownProject},
+    project_management::{load_and_convert_manifest, save_manifest},
+};
+
+/// Represents a chapter of a markdown project.
+///
+/// A chapter consists of the files and directories of a directory sharing an order number, like
+/// `Chapter 2 - Usage.md` and the directory `Chapter 2 - Usage/`. In directories ordered by an
+/// ordering file, every listed file or directory is its own chapter.
+///
+/// # Fields
+///
+/// * `position` - The position of the chapter in its directory, starting at 1.
+/// * `order_number` - The order number of the chapter, or `None` if the directory is ordered by
+///   an ordering file.
+/// * `files` - The files of the chapter, relative to the markdown directory.
+/// * `directories` - The directories of the chapter, relative to the markdown directory.
+/// * `children` - The chapters in the directories of the chapter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chapter {
+    pub position: usize,
+    pub order_number: Option<u32>,
+    pub files: Vec<PathBuf>,
+    pub directories: Vec<PathBuf>,
+    pub children: Vec<Chapter>,
+}
+
+/// A directory of a markdown project with its chapters.
+struct ChapterDirectory {
+    markdown_project: MarkdownProject,
+    markdown_dir: PathBuf,
+    directory: PathBuf,
+    file_order: Option<FileOrder>,
+    chapters: Vec<Vec<PathBuf>>,
+}
+
+impl ChapterDirectory {
+    fn load(
+        project: &Path,
+        manifest: &Manifest,
+        name: &str,
+        directory: Option<PathBuf>,
+    ) -> Result<Self> {
+        let markdown_project = manifest
+            .markdown_projects
+            .iter()
+            .flatten()
+            .find(|p| p.name == name)
+            .ok_or_else(|| eyre!("Markdown project with name '{}' does not exist.", name))?
+            .clone();
+
+        let markdown_dir = project.join(&markdown_project.path);
+        let directory = directory.unwrap_or_default();
+
+        if directory
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return Err(eyre!(
+                "Directory '{}' must be a relative path inside the markdown directory.",
+                directory.display()
+            ));
+        }
+
+        if !markdown_dir.join(&directory).is_dir() {
+            return Err(eyre!(
+                "Directory '{}' does not exist.",
+                markdown_dir.join(&directory).display()
+            ));
+        }
+
+        let input_filter = InputFilter::new(&markdown_project, &markdown_dir)?;
+        let file_order = get_file_order(&markdown_dir, &directory)?;
+        let chapters = get_chapter_paths(
+            &markdown_dir,
+            &directory,
+            &markdown_project,
+            &input_filter,
+            file_order.as_ref(),
+        )?;
+
+        Ok(ChapterDirectory {
+            markdown_project,
+            markdown_dir,
+            directory,
+            file_order,
+            chapters,
+        })
+    }
+
+    fn ensure_renumberable(&self) -> Result<()> {
+        if let Some(file_order) = &self.file_order {
+            return Err(eyre!(
+                "The chapters of '{}' are ordered by the ordering file '{}'. Edit the ordering file instead.",
+                self.markdown_dir.join(&self.directory).display(),
+                file_order.path.display()
+            ));
+        }
+
+        if let Some(path) = self.chapters.iter().flatten().find(|path| {
+            !path
+                .file_name()
+                .is_some_and(|name| ORDER_NUMBER_REGEX.is_match(&name.to_string_lossy()))
+        }) {
+            return Err(eyre!(
+                "'{}' has no order number, so the chapters of its directory cannot be renumbered.",
+                path.display()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn get_position(&self, position: usize, max_position: usize) -> Result<usize> {
+        if position < 1 || position > max_position {
+            return Err(eyre!(
+                "Position {} is out of range. Expected a position between 1 and {}.",
+                position,
+                max_position
+            ));
+        }
+
+        Ok(position - 1)
+    }
+}
+
+/// Lists the chapters of a markdown project in the order they are converted in.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `name` - The name of the markdown project.
+/// * `directory` - The directory to list the chapters of, relative to the markdown directory.
+///   * Defaults to the markdown directory if not provided.
+///
+/// # Returns
+///
+/// A Result containing either an error or a Vec of the chapters.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::chapter_management::get_chapters;
+/// use std::path::PathBuf;
+///
+/// let chapters = get_chapters(Some(PathBuf::from("my_project")), "Markdown", None).unwrap();
+/// for chapter in chapters {
+///     println!("{}. {:?}", chapter.position, chapter.files);
+/// }
+/// ```
+pub fn get_chapters(
+    project: Option<PathBuf>,
+    name: &str,
+    directory: Option<PathBuf>,
+) -> Result<Vec<Chapter>> {
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest = load_and_convert_manifest(&project.join("manifest.toml"))?;
+
+    let chapter_directory = ChapterDirectory::load(&project, &manifest, name, directory)?;
+    let input_filter = InputFilter::new(
+        &chapter_directory.markdown_project,
+        &chapter_directory.markdown_dir,
+    )?;
+
+    let chapters = build_chapters(
+        &chapter_directory.markdown_dir,
+        &chapter_directory.markdown_project,
+        &input_filter,
+        chapter_directory.file_order.as_ref(),
+        chapter_directory.chapters,
+    )?;
+
+    debug!("chapters.get_chapters: {} chapters", chapters.len());
+    Ok(chapters)
+}
+
+/// Inserts a chapter into a markdown project and renumbers the following chapters.
+///
+/// The chapter file is created empty, unless it already exists in the directory, in which case
+/// it is moved to the position. The order number in the chapter name is replaced by the order
+/// number of the position. All renamed paths are updated in the resources and injections of the
+/// manifest.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `name` - The name of the markdown project.
+/// * `directory` - The directory to insert the chapter into, relative to the markdown directory.
+///   * Defaults to the markdown directory if not provided.
+/// * `chapter` - The file name of the chapter, containing an order number, e.g. `Chapter 4 - The Twist.md`.
+/// * `position` - The position of the new chapter, starting at 1.
+///   * Defaults to the end if not provided.
+///
+/// # Returns
+///
+/// A Result containing either an error or the path of the chapter, relative to the markdown directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::chapter_management::insert_chapter;
+/// use std::path::PathBuf;
+///
+/// insert_chapter(
+///     Some(PathBuf::from("my_project")),
+///     "Markdown",
+///     None,
+///     "Chapter 4 - The Twist.md".to_string(),
+///     Some(4),
+/// ).unwrap();
+/// ```
+pub fn insert_chapter(
+    project: Option<PathBuf>,
+    name: &str,
+    directory: Option<PathBuf>,
+    chapter: String,
+    position: Option<usize>,
+) -> Result<PathBuf> {
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest_path = project.join("manifest.toml");
+    let mut manifest = load_and_convert_manifest(&manifest_path)?;
+
+    let mut chapter_directory = ChapterDirectory::load(&project, &manifest, name, directory)?;
+
+    if chapter.is_empty() || Path::new(&chapter).components().count() != 1 {
+        return Err(eyre!("Chapter '{}' must be a file name.", chapter));
+    }
+
+    if !ORDER_NUMBER_REGEX.is_match(&chapter) {
+        return Err(eyre!(
+            "Chapter '{}' must contain an order number, e.g. 'Chapter 1 - {}'.",
+            chapter,
+            chapter
+        ));
+    }
+
+    let chapter_path = chapter_directory.directory.join(&chapter);
+    let exists = chapter_directory.markdown_dir.join(&chapter_path).exists();
+
+    if exists {
+        for paths in &mut chapter_directory.chapters {
+            paths.retain(|path| *path != chapter_path);
+        }
+        chapter_directory.chapters.retain(|paths| !paths.is_empty());
+    }
+
+    chapter_directory.ensure_renumberable()?;
+
+    let start = get_start_number(&chapter_directory.chapters);
+    let position = chapter_directory.get_position(
+        position.unwrap_or(chapter_directory.chapters.len() + 1),
+        chapter_directory.chapters.len() + 1,
+    )?;
+
+    chapter_directory
+        .chapters
+        .insert(position, vec![chapter_path.clone()]);
+
+    let mut renames = get_renames(&chapter_directory.chapters, start);
+
+    let new_chapter_path = match renames.iter().position(|(old, _)| *old == chapter_path) {
+        Some(index) if !exists => renames.remove(index).1,
+        Some(index) => renames[index].1.clone(),
+        None => chapter_path.clone(),
+    };
+
+    // Check this before renaming anything, so a failed insert leaves the directory untouched.
+    if !exists
+        && ((chapter_directory
+            .markdown_dir
+            .join(&new_chapter_path)
+            .exists()
+            && !renames.iter().any(|(old, _)| *old == new_chapter_path))
+            || renames.iter().any(|(_, new)| *new == new_chapter_path))
+    {
+        return Err(eyre!(
+            "Cannot create chapter '{}' as it already exists.",
+            new_chapter_path.display()
+        ));
+    }
+
+    apply_renames(&chapter_directory.markdown_dir, &renames)?;
+
+    if !exists {
+        fs::write(chapter_directory.markdown_dir.join(&new_chapter_path), "")?;
+        info!("Created chapter '{}'.", new_chapter_path.display());
+    }
+
+    update_manifest_references(
+        &manifest_path,
+        &mut manifest,
+        &chapter_directory.markdown_project.name,
+        &renames,
+    )?;
+
+    debug!(
+        "chapters.insert_chapter: '{}' at position {}",
+        new_chapter_path.display(),
+        position + 1
+    );
+    Ok(new_chapter_path)
+}
+
+/// Moves a chapter of a markdown project to another position and renumbers the chapters in
+/// between.
+///
+/// All renamed paths are updated in the resources and injections of the manifest.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `name` - The name of the markdown project.
+/// * `directory` - The directory containing the chapter, relative to the markdown directory.
+///   * Defaults to the markdown directory if not provided.
+/// * `from` - The current position of the chapter, starting at 1.
+/// * `to` - The new position of the chapter, starting at 1.
+///
+/// # Returns
+///
+/// A Result containing either an error or nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::chapter_management::move_chapter;
+/// use std::path::PathBuf;
+///
+/// move_chapter(Some(PathBuf::from("my_project")), "Markdown", None, 5, 2).unwrap();
+/// ```
+pub fn move_chapter(
+    project: Option<PathBuf>,
+    name: &str,
+    directory: Option<PathBuf>,
+    from: usize,
+    to: usize,
+) -> Result<()> {
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest_path = project.join("manifest.toml");
+    let mut manifest = load_and_convert_manifest(&manifest_path)?;
+
+    let mut chapter_directory = ChapterDirectory::load(&project, &manifest, name, directory)?;
+
+    chapter_directory.ensure_renumberable()?;
+
+    let chapter_count = chapter_directory.chapters.len();
+    let from = chapter_directory.get_position(from, chapter_count)?;
+    let to = chapter_directory.get_position(to, chapter_count)?;
+
+    let start = get_start_number(&chapter_directory.chapters);
+    let chapter = chapter_directory.chapters.remove(from);
+    chapter_directory.chapters.insert(to, chapter);
+
+    let renames = get_renames(&chapter_directory.chapters, start);
+
+    apply_renames(&chapter_directory.markdown_dir, &renames)?;
+
+    update_manifest_references(
+        &manifest_path,
+        &mut manifest,
+        &chapter_directory.markdown_project.name,
+        &renames,
+    )?;
+
+    debug!("chapters.move_chapter: {} -> {}", from + 1, to + 1);
+    Ok(())
+}
+
+fn get_chapter_paths(
+    markdown_dir: &Path,
+    directory: &Path,
+    markdown_project: &MarkdownProject,
+    input_filter: &InputFilter,
+    file_order: Option<&FileOrder>,
+) -> Result<Vec<Vec<PathBuf>>> {
+    let resources = get_resources(markdown_project);
+
+    let mut paths = fs::read_dir(markdown_dir.join(directory))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| !is_order_file(path, file_order))
+        .filter(|path| {
+            path.strip_prefix(markdown_dir).is_ok_and(|relative_path| {
+                !resources.iter().any(|resource| relative_path == resource)
+                    && input_filter.is_included(relative_path, path.is_dir())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    paths.sort();
+    sort_paths(&mut paths, file_order);
+
+    let mut chapters: Vec<Vec<PathBuf>> = vec![];
+
+    for path in paths {
+        let relative_path = path.strip_prefix(markdown_dir)?.to_path_buf();
+
+        match chapters.last_mut() {
+            Some(chapter)
+                if file_order.is_none()
+                    && retrieve_file_order_number(&chapter[0])
+                        == retrieve_file_order_number(&relative_path) =>
+            {
+                chapter.push(relative_path)
+            }
+            _ => chapters.push(vec![relative_path]),
+        }
+    }
+
+    Ok(chapters)
+}
+
+fn build_chapters(
+    markdown_dir: &Path,
+    markdown_project: &MarkdownProject,
+    input_filter: &InputFilter,
+    file_order: Option<&FileOrder>,
+    chapter_paths: Vec<Vec<PathBuf>>,
+) -> Result<Vec<Chapter>> {
+    chapter_paths
+        .into_iter()
+        .enumerate()
+        .map(|(i, paths)| {
+            let order_number = file_order
+                .is_none()
+                .then(|| retrieve_file_order_number(&paths[0]));
+            let (directories, files): (Vec<_>, Vec<_>) = paths
+                .into_iter()
+                .partition(|path| markdown_dir.join(path).is_dir());
+
+            let mut children = vec![];
+
+            for path in &directories {
+                let subdirectory_file_order = get_file_order(markdown_dir, path)?;
+                let subdirectory_chapters = get_chapter_paths(
+                    markdown_dir,
+                    path,
+                    markdown_project,
+                    input_filter,
+                    subdirectory_file_order.as_ref(),
+                )?;
+
+                children.append(&mut build_chapters(
+                    markdown_dir,
+                    markdown_project,
+                    input_filter,
+                    subdirectory_file_order.as_ref(),
+                    subdirectory_chapters,
+                )?);
+            }
+
+            Ok(Chapter {
+                position: i + 1,
+                order_number,
+                files,
+                directories,
+                children,
+            })
+        })
+        .collect()
+}
+
+fn get_start_number(chapters: &[Vec<PathBuf>]) -> u32 {
+    chapters
+        .iter()
+        .flatten()
+        .map(|path| retrieve_file_order_number(path))
+        .min()
+        .unwrap_or(1)
+}
+
+/// Computes the renames that number the chapters consecutively, starting at `start`.
+fn get_renames(chapters: &[Vec<PathBuf>], start: u32) -> Vec<(PathBuf, PathBuf)> {
+    chapters
+        .iter()
+        .enumerate()
+        .flat_map(|(i, paths)| {
+            paths.iter().filter_map(move |path| {
+                let file_name = path.file_name()?.to_string_lossy();
+                let new_file_name = replace_order_number(&file_name, start + i as u32);
+
+                (new_file_name != file_name)
+                    .then(|| (path.clone(), path.with_file_name(new_file_name)))
+            })
+        })
+        .collect()
+}
+
+/// Replaces the first number in a file name, keeping leading zeros.
+fn replace_order_number(file_name: &str, order_number: u32) -> String {
+    let Some(number) = ORDER_NUMBER_REGEX.find(file_name) else {
+        return file_name.to_string();
+    };
+
+    let width = if number.as_str().starts_with('0') {
+        number.len()
+    } else {
+        0
+    };
+
+    format!(
+        "{}{:0width$}{}",
+        &file_name[..number.start()],
+        order_number,
+        &file_name[number.end()..],
+        width = width
+    )
+}
+
+fn apply_renames(markdown_dir: &Path, renames: &[(PathBuf, PathBuf)]) -> Result<()> {
+    for (_, new_path) in renames {
+        if markdown_dir.join(new_path).exists() && !renames.iter().any(|(old, _)| old == new_path) {
+            return Err(eyre!(
+                "Cannot rename to '{}' as it already exists.",
+                new_path.display()
+            ));
+        }
+    }
+
+    let temporary_paths = renames
+        .iter()
+        .map(|(old_path, _)| {
+            let file_name = old_path.file_name().unwrap_or_default().to_string_lossy();
+            old_path.with_file_name(format!(".{}.tiefdown_renaming", file_name))
+        })
+        .collect::<Vec<_>>();
+
+    for ((old_path, _), temporary_path) in renames.iter().zip(&temporary_paths) {
+        fs::rename(
+            markdown_dir.join(old_path),
+            markdown_dir.join(temporary_path),
+        )?;
+    }
+
+    for ((old_path, new_path), temporary_path) in renames.iter().zip(&temporary_paths) {
+        fs::rename(
+            markdown_dir.join(temporary_path),
+            markdown_dir.join(new_path),
+        )?;
+        info!(
+            "Renamed '{}' to '{}'.",
+            old_path.display(),
+            new_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn rename_path(path: &Path, renames: &[(PathBuf, PathBuf)]) -> Option<PathBuf> {
+    renames.iter().find_map(|(old_path, new_path)| {
+        let rest = path.strip_prefix(old_path).ok()?;
+
+        if rest.as_os_str().is_empty() {
+            Some(new_path.clone())
+        } else {
+            Some(new_path.join(rest))
+        }
+    })
+}
+
+/// Updates the resources and bibliography files of the markdown project and the injection files
+/// referencing its conversion directories (`<template>_convdir/...`) to the renamed paths.
+fn update_manifest_references(
+    manifest_path: &Path,
+    manifest: &mut Manifest,
+    markdown_project_name: &str,
+    renames: &[(PathBuf, PathBuf)],
+) -> Result<()> {
+    let mut changed = false;
+
+    if let Some(markdown_project) = manifest
+        .markdown_projects
+        .iter_mut()
+        .flatten()
+        .find(|p| p.name == markdown_project_name)
+    {
+        let bibliography_paths =
+            markdown_project
+                .bibliography
+                .iter_mut()
+                .flat_map(|bibliography| {
+                    bibliography
+                        .files
+                        .iter_mut()
+                        .flatten()
+                        .chain(bibliography.csl.iter_mut())
+                });
+
+        for resource in markdown_project
+            .resources
+            .iter_mut()
+            .flatten()
+            .chain(bibliography_paths)
+        {
+            if let Some(new_resource) = rename_path(resource, renames) {
+                *resource = new_resource;
+                changed = true;
+            }
+        }
+    }
+
+    for injection in manifest.injections.iter_mut().flatten() {
+        for file in &mut injection.files {
+            let mut components = file.components();
+
+            let Some(Component::Normal(conversion_dir)) = components.next() else {
+                continue;
+            };
+
+            if !conversion_dir.to_string_lossy().ends_with("_convdir") {
+                continue;
+            }
+
+            if let Some(new_file) = rename_path(components.as_path(), renames) {
+                *file = Path::new(conversion_dir).join(new_file);
+                changed = true;
+            }
+        }
+    }
+
+    if changed {
+        save_manifest(manifest_path, manifest)?;
+        info!("Updated the references to renamed chapters in the manifest.");
+    }
+
+    Ok(())
+}
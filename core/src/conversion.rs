@@ -1,13 +1,37 @@
+use crate::bibliography::get_bibliography;
+use crate::bibliography::get_bibliography_paths;
+use crate::bibliography::get_resources;
+use crate::build_cache::{BuildCache, TaskInputs};
+use crate::build_report::BuildReport;
+use crate::build_report::create_task_report;
+use crate::build_report::get_build_relative_path;
+use crate::build_report::without_current_dir;
+use crate::build_report::write_build_report;
 use crate::conversion_decider;
+use crate::conversion_recorder::ConversionRecord;
+use crate::conversion_recorder::ConversionRecorder;
+use crate::converters::conditional_content::write_conditional_content_filter;
+use crate::converters::conversion_meta::ConversionMeta;
+use crate::converters::tex_converter::get_latex_dependencies;
+use crate::front_matter::remove_excluded_files;
+use crate::injections::RenderingInjections;
+use crate::input_filter::IGNORE_FILE_NAME;
+use crate::input_filter::InputFilter;
+use crate::input_filter::copy_filtered_files;
 use crate::manifest_model::Injection;
 use crate::manifest_model::Manifest;
 use crate::manifest_model::MarkdownProject;
 use crate::manifest_model::MetadataSettings;
+use crate::manifest_model::PreProcessor;
 use crate::manifest_model::Processors;
 use crate::manifest_model::Template;
+use crate::metadata_management::get_metadata_layers;
+use crate::metadata_management::merge_metadata_layers;
 use crate::project_management::get_missing_dependencies;
 use crate::project_management::load_and_convert_manifest;
 use crate::project_management::run_smart_clean;
+use crate::template_management::get_template_path;
+use crate::template_type::TemplateType;
 use chrono::prelude::DateTime;
 use chrono::prelude::Utc;
 use color_eyre::eyre::OptionExt;
@@ -19,15 +43,32 @@ use log::debug;
 use log::error;
 use log::info;
 use log::warn;
+use notify::Event;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use rayon::ThreadPoolBuilder;
+use rayon::iter::IntoParallelRefIterator;
+use rayon::iter::ParallelIterator;
+use std::fs;
+use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
+use std::time::Instant;
 use toml::Table;
 
 /// A task representing the conversion of a markdown project using a specific template.
-/// Contains the markdown project and the template name.
+/// Contains the markdown project, the template name and the profile the task was queued with.
+#[derive(Clone)]
 pub struct ConversionTask {
     pub markdown_project: MarkdownProject,
     pub template: String,
+    pub profile: Option<String>,
 }
 
 /// Prepares the conversion queue based on the provided arguments.
@@ -82,17 +123,7 @@ pub fn get_conversion_queue(
 
     let mut queue = vec![];
 
-    let markdown_projects = manifest
-        .markdown_projects
-        .clone()
-        .unwrap_or(vec![MarkdownProject {
-            name: "Default".to_string(),
-            path: PathBuf::from("Markdown"),
-            output: PathBuf::from("."),
-            metadata_fields: None,
-            default_profile: None,
-            resources: None,
-        }]);
+    let markdown_projects = get_markdown_projects(&manifest);
 
     let markdown_projects = if let Some(selected_markdown_projects) = selected_markdown_projects {
         markdown_projects
@@ -110,12 +141,15 @@ pub fn get_conversion_queue(
             markdown_project.clone().default_profile
         };
 
-        let templates = get_template_names(&templates, &profile, &manifest)?;
+        let template_names = get_template_names(&templates, &profile, &manifest)?;
+        // The profile only applies if it selected the templates.
+        let profile = if templates.is_none() { profile } else { None };
 
-        for template in templates {
+        for template in template_names {
             queue.push(ConversionTask {
                 markdown_project: markdown_project.clone(),
                 template,
+                profile: profile.clone(),
             });
         }
     }
@@ -123,6 +157,111 @@ pub fn get_conversion_queue(
     Ok(queue)
 }
 
+/// The resolved build plan of a single conversion task.
+///
+/// All paths are relative to the build directory of the project, except for the output path,
+/// which is relative to the project directory.
+pub struct ConversionTaskPlan {
+    pub markdown_project: String,
+    pub template: String,
+    pub converter: TemplateType,
+    pub preprocessors: Vec<PreProcessor>,
+    pub header_injections: Vec<PathBuf>,
+    pub body_injections: Vec<PathBuf>,
+    pub footer_injections: Vec<PathBuf>,
+    pub input_files: Vec<PathBuf>,
+    pub chunks: Vec<PlannedChunk>,
+    pub commands: Vec<PlannedCommand>,
+    pub output_path: Option<PathBuf>,
+}
+
+/// A chunk of input files that is passed to a single preprocessor run.
+pub struct PlannedChunk {
+    pub files: Vec<PathBuf>,
+    pub extension: String,
+    pub preprocessor: String,
+}
+
+/// An external command that would be run during conversion.
+///
+/// The working directory is relative to the build directory.
+pub struct PlannedCommand {
+    pub working_directory: Option<PathBuf>,
+    pub command_line: String,
+}
+
+/// Resolves the build plan for the conversion queue without running external tools.
+///
+/// Runs the conversion in a temporary directory, recording the chosen converter, the merged
+/// preprocessors, the input file chunks, the resolved injections, the command lines of all
+/// external tools and the output paths. Neither pandoc, nor xelatex or typst are run, and the
+/// build and output directories of the project are not touched.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `conversion_queue` - A vector of ConversionTask specifying which markdown projects to convert and which templates to use.
+/// * `metadata_overrides` - Metadata overriding all metadata defined in the manifest.
+///
+/// # Returns
+///
+/// A Result containing either an error or a vector of ConversionTaskPlan, in queue order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::conversion::{get_conversion_plan, get_conversion_queue};
+/// use std::path::PathBuf;
+///
+/// let project = Some(PathBuf::from("my_project"));
+/// let queue = get_conversion_queue(project.clone(), None, None, None).unwrap();
+/// for plan in get_conversion_plan(project, queue, None).unwrap() {
+///     for command in plan.commands {
+///         println!("{}", command.command_line);
+///     }
+/// }
+/// ```
+pub fn get_conversion_plan(
+    project: Option<PathBuf>,
+    conversion_queue: Vec<ConversionTask>,
+    metadata_overrides: Option<Table>,
+) -> Result<Vec<ConversionTaskPlan>> {
+    let project = project.unwrap_or(PathBuf::from("."));
+
+    if !project.exists() {
+        return Err(eyre!("Project path does not exist."));
+    }
+
+    let manifest_path = project.join("manifest.toml");
+    let manifest = load_and_convert_manifest(&manifest_path)?;
+
+    let plan_directory_path = create_plan_directory()?;
+
+    debug!(
+        "Resolving conversion plan in directory: {}",
+        plan_directory_path.display()
+    );
+
+    let conversion_context = ConversionContext {
+        manifest,
+        build_cache: BuildCache::disabled(),
+        compiled_directory_path: plan_directory_path.clone(),
+        metadata_overrides: metadata_overrides.unwrap_or_default(),
+    };
+
+    let plans = conversion_queue
+        .into_iter()
+        .map(|conversion_task| {
+            get_conversion_task_plan(&project, &conversion_context, conversion_task)
+        })
+        .collect::<Result<Vec<_>>>();
+
+    let _ = fs::remove_dir_all(&plan_directory_path);
+
+    plans
+}
+
 /// Converts a TiefDown project to specified templates.
 ///
 /// Runs the conversion process for all conversion tasks specified in the conversion queue.
@@ -136,10 +275,17 @@ pub fn get_conversion_queue(
 ///   * A markdown project may be converted to multiple templates.
 ///   * A template may be used for multiple markdown projects.
 ///   * If empty, no conversion will be performed.
+/// * `metadata_overrides` - Metadata overriding all metadata defined in the manifest.
+///   * Nested tables are merged with the metadata of the manifest.
+/// * `jobs` - The number of conversion tasks to run in parallel.
+///   * Defaults to 1 if not provided.
+///   * Tasks writing to the same markdown project output directory are always run one after another.
+///   * If more than one job is used, all tasks are run even if one fails, and the status of every task is printed in queue order.
 ///
 /// # Returns
 ///
-/// A Result containing either an error or nothing.
+/// A Result containing either an error or the BuildReport of the conversion.
+/// The report is also written to `build_report.json` in the build directory, even if a task failed.
 ///
 /// # Examples
 ///
@@ -149,9 +295,304 @@ pub fn get_conversion_queue(
 ///
 /// let project = Some(PathBuf::from("my_project"));
 /// let queue = get_conversion_queue(project.clone(), None, None, None).unwrap();
-/// convert(project, queue).unwrap();
+/// let report = convert(project, queue, None, None).unwrap();
+/// for task in report.tasks {
+///     println!("{} -> {}: {:.1}s", task.markdown_project, task.template, task.duration_seconds);
+/// }
+/// ```
+pub fn convert(
+    project: Option<PathBuf>,
+    conversion_queue: Vec<ConversionTask>,
+    metadata_overrides: Option<Table>,
+    jobs: Option<usize>,
+) -> Result<BuildReport> {
+    let jobs = get_jobs(jobs)?;
+
+    let project = project.unwrap_or(PathBuf::from("."));
+
+    if !project.exists() {
+        return Err(eyre!("Project path does not exist."));
+    }
+
+    check_conversion_dependencies(&project, &conversion_queue)?;
+
+    let conversion_context = prepare_conversion(&project, &metadata_overrides)?;
+
+    let started_at = Utc::now();
+    let start = Instant::now();
+
+    let results = if jobs == 1 {
+        let mut results = vec![];
+
+        for conversion_task in conversion_queue {
+            let task_result = run_conversion_task(&project, &conversion_context, conversion_task);
+            let failed = task_result.result.is_err();
+
+            results.push(task_result);
+
+            if failed {
+                break;
+            }
+        }
+
+        results
+    } else {
+        let results = run_conversion_tasks(&project, &conversion_context, &conversion_queue, jobs)?;
+
+        log_conversion_status(&results);
+
+        results
+    };
+
+    let build_report = create_build_report(
+        &project,
+        &conversion_context,
+        started_at,
+        start.elapsed(),
+        &results,
+    )?;
+    write_build_report(&build_report, &conversion_context.compiled_directory_path)?;
+
+    if let Some(e) = results
+        .into_iter()
+        .find_map(|task_result| task_result.result.err())
+    {
+        return Err(e);
+    }
+
+    Ok(build_report)
+}
+
+/// Converts a TiefDown project and converts it again whenever its inputs change.
+///
+/// Runs all conversion tasks once, then watches the markdown project directories, their
+/// resources, the template directory and the manifest. Bursts of changes are debounced, and only
+/// the conversion tasks affected by the changed files are run again. A failing conversion task is
+/// reported, but does not stop the watcher.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `conversion_queue` - A vector of ConversionTask specifying which markdown projects to convert and which templates to use.
+/// * `metadata_overrides` - Metadata overriding all metadata defined in the manifest.
+/// * `debounce` - The time to wait for further changes before starting a conversion.
+///   * Defaults to 500 milliseconds if not provided.
+/// * `jobs` - The number of conversion tasks to run in parallel.
+///   * Defaults to 1 if not provided.
+///
+/// # Returns
+///
+/// A Result containing an error if the project cannot be watched. Does not return otherwise.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::conversion::{get_conversion_queue, watch};
+/// use std::path::PathBuf;
+///
+/// let project = Some(PathBuf::from("my_project"));
+/// let queue = get_conversion_queue(project.clone(), None, None, None).unwrap();
+/// watch(project, queue, None, None, None).unwrap();
 /// ```
-pub fn convert(project: Option<PathBuf>, conversion_queue: Vec<ConversionTask>) -> Result<()> {
+pub fn watch(
+    project: Option<PathBuf>,
+    conversion_queue: Vec<ConversionTask>,
+    metadata_overrides: Option<Table>,
+    debounce: Option<Duration>,
+    jobs: Option<usize>,
+) -> Result<()> {
+    let jobs = get_jobs(jobs)?;
+
+    let project = project.unwrap_or(PathBuf::from("."));
+
+    if !project.exists() {
+        return Err(eyre!("Project path does not exist."));
+    }
+
+    check_conversion_dependencies(&project, &conversion_queue)?;
+
+    let project = project.canonicalize()?;
+    let debounce = debounce.unwrap_or(Duration::from_millis(500));
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    let mut watched_paths = vec![];
+    let mut conversion_queue = conversion_queue;
+    let mut manifest_files = get_manifest_files(&project)?;
+
+    update_watched_paths(
+        &mut watcher,
+        &mut watched_paths,
+        &conversion_queue,
+        &manifest_files,
+        &project,
+    )?;
+    run_watched_conversion(&project, &conversion_queue, &metadata_overrides, jobs);
+    let mut manifest_content = read_manifest_files(&manifest_files);
+
+    info!("Watching project for changes. Press Ctrl+C to stop.");
+
+    loop {
+        let mut changed_paths = receive_changed_paths(&receiver, debounce)?;
+
+        if changed_paths
+            .iter()
+            .any(|path| manifest_files.contains(path))
+        {
+            let new_manifest_content = read_manifest_files(&manifest_files);
+
+            if new_manifest_content == manifest_content {
+                changed_paths.retain(|path| !manifest_files.contains(path));
+            } else {
+                debug!("Manifest changed, refreshing conversion queue.");
+                manifest_content = new_manifest_content;
+
+                match refresh_conversion_queue(&project, &conversion_queue) {
+                    Ok(refreshed_queue) => conversion_queue = refreshed_queue,
+                    Err(e) => {
+                        error!("Failed to reload the manifest: {}", e);
+                        continue;
+                    }
+                }
+
+                manifest_files = get_manifest_files(&project)?;
+                manifest_content = read_manifest_files(&manifest_files);
+
+                update_watched_paths(
+                    &mut watcher,
+                    &mut watched_paths,
+                    &conversion_queue,
+                    &manifest_files,
+                    &project,
+                )?;
+            }
+        }
+
+        if changed_paths.is_empty() {
+            continue;
+        }
+
+        let affected_tasks =
+            match get_affected_conversion_tasks(&project, &conversion_queue, &changed_paths) {
+                Ok(affected_tasks) => affected_tasks,
+                Err(e) => {
+                    error!("Failed to determine the affected conversion tasks: {}", e);
+                    continue;
+                }
+            };
+
+        if affected_tasks.is_empty() {
+            debug!(
+                "{} files changed, but no conversion task is affected.",
+                changed_paths.len()
+            );
+            continue;
+        }
+
+        info!(
+            "{} files changed, converting {} affected tasks.",
+            changed_paths.len(),
+            affected_tasks.len()
+        );
+
+        run_watched_conversion(&project, &affected_tasks, &metadata_overrides, jobs);
+        manifest_content = read_manifest_files(&manifest_files);
+    }
+}
+
+/// Determines which conversion tasks are affected by changes to the given paths.
+///
+/// A conversion task is affected if a file in its markdown project directory or resources
+/// changed, or if the manifest changed. Changes to the template directory affect the tasks of
+/// the templates using the changed file as template file, filter or injection. Changes to other
+/// files in the template directory affect all tasks.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+/// * `conversion_queue` - The conversion tasks to check.
+/// * `changed_paths` - The changed paths, either absolute or relative to the project directory.
+///
+/// # Returns
+///
+/// A Result containing either an error or the affected conversion tasks in queue order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::conversion::{get_affected_conversion_tasks, get_conversion_queue};
+/// use std::path::{Path, PathBuf};
+///
+/// let project = Path::new("my_project");
+/// let queue = get_conversion_queue(Some(project.to_path_buf()), None, None, None).unwrap();
+/// let affected = get_affected_conversion_tasks(
+///     project,
+///     &queue,
+///     &[PathBuf::from("Markdown/Chapter 1.md")],
+/// ).unwrap();
+/// println!("{} tasks are affected.", affected.len());
+/// ```
+pub fn get_affected_conversion_tasks(
+    project: &Path,
+    conversion_queue: &[ConversionTask],
+    changed_paths: &[PathBuf],
+) -> Result<Vec<ConversionTask>> {
+    let project = normalize_path(project);
+    let manifest = load_and_convert_manifest(&project.join("manifest.toml"))?;
+
+    let changed_paths: Vec<PathBuf> = changed_paths
+        .iter()
+        .map(|changed_path| normalize_path(&project.join(changed_path)))
+        .collect();
+
+    let affected_tasks: Vec<ConversionTask> = conversion_queue
+        .iter()
+        .filter(|conversion_task| {
+            changed_paths.iter().any(|changed_path| {
+                is_conversion_task_affected(&project, &manifest, conversion_task, changed_path)
+            })
+        })
+        .cloned()
+        .collect();
+
+    debug!(
+        "{} of {} conversion tasks are affected by {} changed paths.",
+        affected_tasks.len(),
+        conversion_queue.len(),
+        changed_paths.len()
+    );
+
+    Ok(affected_tasks)
+}
+
+struct ConversionTaskResult {
+    conversion_task: ConversionTask,
+    result: Result<()>,
+    duration: Duration,
+    record: ConversionRecord,
+}
+
+struct ConversionContext {
+    manifest: Manifest,
+    build_cache: BuildCache,
+    compiled_directory_path: PathBuf,
+    metadata_overrides: Table,
+}
+
+fn get_jobs(jobs: Option<usize>) -> Result<usize> {
+    match jobs {
+        Some(0) => Err(eyre!("The number of jobs must be at least 1.")),
+        Some(jobs) => Ok(jobs),
+        None => Ok(1),
+    }
+}
+
+/// Checks that pandoc and the commands needed by the templates in the queue are installed.
+fn check_conversion_dependencies(
+    project: &Path,
+    conversion_queue: &[ConversionTask],
+) -> Result<()> {
     let pandoc_errors = get_missing_dependencies(vec!["pandoc"])?;
 
     if !pandoc_errors.is_empty() {
@@ -159,7 +600,29 @@ pub fn convert(project: Option<PathBuf>, conversion_queue: Vec<ConversionTask>)
         return Err(eyre!("Pandoc is not installed or not in the PATH."));
     }
 
-    let other_dependencies = get_missing_dependencies(vec!["xelatex", "typst"])?;
+    let manifest = load_and_convert_manifest(&project.join("manifest.toml"))?;
+
+    let mut dependencies = vec![];
+
+    for template in manifest.templates.iter().filter(|template| {
+        conversion_queue
+            .iter()
+            .any(|conversion_task| conversion_task.template == template.name)
+    }) {
+        let template_dependencies = match template.template_type {
+            TemplateType::Tex => get_latex_dependencies(template.latex.as_ref()),
+            TemplateType::Typst => vec!["typst"],
+            _ => vec![],
+        };
+
+        for dependency in template_dependencies {
+            if !dependencies.contains(&dependency) {
+                dependencies.push(dependency);
+            }
+        }
+    }
+
+    let other_dependencies = get_missing_dependencies(dependencies)?;
 
     if !other_dependencies.is_empty() {
         warn!("{}", other_dependencies.join("\n"));
@@ -168,107 +631,754 @@ pub fn convert(project: Option<PathBuf>, conversion_queue: Vec<ConversionTask>)
         );
     }
 
-    let project = project.unwrap_or(PathBuf::from("."));
-
-    if !project.exists() {
-        return Err(eyre!("Project path does not exist."));
-    }
+    Ok(())
+}
 
+fn prepare_conversion(
+    project: &PathBuf,
+    metadata_overrides: &Option<Table>,
+) -> Result<ConversionContext> {
     let manifest_path = project.join("manifest.toml");
     let manifest = load_and_convert_manifest(&manifest_path)?;
 
     if let Some(true) = manifest.smart_clean {
         let threshold = manifest.smart_clean_threshold.unwrap_or(5);
-        run_smart_clean(&project, threshold.saturating_sub(1))?;
+        run_smart_clean(project, threshold.saturating_sub(1))?;
     }
 
     info!("Converting project: {}", project.to_string_lossy());
 
-    let compiled_directory_path = create_build_directory(&project)?;
+    let build_cache = if let Some(true) = manifest.build_cache {
+        BuildCache::open(project)?
+    } else {
+        BuildCache::disabled()
+    };
+
+    let compiled_directory_path = create_build_directory(project)?;
 
     debug!(
         "Converting in directory: {}",
         compiled_directory_path.display()
     );
 
-    for conversion_task in conversion_queue {
-        let markdown_project = conversion_task.markdown_project;
-        let template = conversion_task.template;
+    Ok(ConversionContext {
+        manifest,
+        build_cache,
+        compiled_directory_path,
+        metadata_overrides: metadata_overrides.clone().unwrap_or_default(),
+    })
+}
 
-        info!(
-            "Converting markdown project '{}' with template '{}'.",
-            markdown_project.name, template
-        );
+fn convert_task(
+    project: &Path,
+    conversion_context: &ConversionContext,
+    conversion_task: ConversionTask,
+    recorder: &ConversionRecorder,
+) -> Result<()> {
+    let manifest = &conversion_context.manifest;
+    let markdown_project = conversion_task.markdown_project;
+    let template = conversion_task.template;
 
-        let template = get_template_mapping_from_name(&template, &manifest)?;
-        debug!("Resolved template mapping for {}.", template.name);
-        let markdown_project_compiled_directory_path =
-            compiled_directory_path.join(markdown_project.output.clone());
+    info!(
+        "Converting markdown project '{}' with template '{}'.",
+        markdown_project.name, template
+    );
 
-        dir::create_all(&markdown_project_compiled_directory_path, false)?;
-        dir::copy(
-            project.join("template/"),
-            &markdown_project_compiled_directory_path,
-            &dir::CopyOptions::new().skip_exist(true).content_only(true),
-        )?;
+    let template = get_template_mapping_from_name(&template, manifest)?;
+    debug!("Resolved template mapping for {}.", template.name);
+    let markdown_project_compiled_directory_path = get_markdown_project_compiled_directory_path(
+        &conversion_context.compiled_directory_path,
+        &markdown_project,
+    );
 
-        debug!("Copied template directory.");
+    dir::create_all(&markdown_project_compiled_directory_path, false)?;
+    dir::copy(
+        project.join("template/"),
+        &markdown_project_compiled_directory_path,
+        &dir::CopyOptions::new().skip_exist(true).content_only(true),
+    )?;
 
-        let input_dir = project.join(markdown_project.path.clone());
+    debug!("Copied template directory.");
 
-        copy_resources(
-            &markdown_project,
-            &markdown_project_compiled_directory_path,
-            &input_dir,
-        )?;
+    let input_dir = project.join(markdown_project.path.clone());
 
-        let shared_metadata = manifest.shared_metadata.clone().unwrap_or(Table::new());
-        let project_metadata = markdown_project.metadata_fields.unwrap_or(Table::new());
+    copy_resources(
+        &markdown_project,
+        &markdown_project_compiled_directory_path,
+        &input_dir,
+    )?;
 
-        let merged_metadata = merge_metadata(&shared_metadata, &project_metadata);
+    let metadata_layers = get_metadata_layers(
+        manifest,
+        &markdown_project,
+        Some(&template),
+        conversion_task.profile.as_deref(),
+        &conversion_context.metadata_overrides,
+    )?;
 
-        debug!(
-            "Merged {} metadata fields ({} shared, {} project specific).",
-            merged_metadata.len(),
-            shared_metadata.len(),
-            project_metadata.len()
-        );
+    let merged_metadata = merge_metadata_layers(&metadata_layers);
 
-        let conversion_input_dir =
-            &markdown_project_compiled_directory_path.join(template.name.clone() + "_convdir/");
-        debug!(
-            "Prepared conversion input directory: {}",
-            conversion_input_dir.display()
+    debug!(
+        "Merged {} metadata fields from {} layers.",
+        merged_metadata.len(),
+        metadata_layers.len()
+    );
+
+    let conversion_input_dir =
+        &markdown_project_compiled_directory_path.join(template.name.clone() + "_convdir/");
+    debug!(
+        "Prepared conversion input directory: {}",
+        conversion_input_dir.display()
+    );
+
+    copy_markdown_directory(
+        &input_dir,
+        &conversion_input_dir,
+        &get_resources(&markdown_project),
+        &InputFilter::new(&markdown_project, &input_dir)?,
+    )?;
+    remove_excluded_files(conversion_input_dir, &template)?;
+
+    let injections = manifest.injections.clone().unwrap_or(vec![]);
+    let resources = get_resources(&markdown_project)
+        .iter()
+        .map(|resource| input_dir.join(resource))
+        .collect();
+
+    let conversion_meta = ConversionMeta {
+        markdown_project: markdown_project.name.clone(),
+        template: template.name.clone(),
+        template_type: template.template_type,
+        profile: conversion_task.profile.clone(),
+        bibliography: get_bibliography(project, &markdown_project, &template)?,
+    };
+
+    let task_build_cache = conversion_context.build_cache.for_task(&TaskInputs {
+        conversion_meta: &conversion_meta,
+        template: &template,
+        metadata_fields: &merged_metadata,
+        metadata_settings: &manifest.metadata_settings,
+        custom_processors: &manifest.custom_processors,
+        injections: &injections,
+        template_directory_path: &project.join("template"),
+        resources: &resources,
+        conversion_input_dir,
+    })?;
+
+    convert_template(
+        &markdown_project_compiled_directory_path,
+        &template,
+        &conversion_meta,
+        project,
+        &conversion_input_dir,
+        &markdown_project.output,
+        &merged_metadata,
+        &manifest.metadata_settings,
+        &manifest.custom_processors,
+        &injections,
+        &task_build_cache,
+        recorder,
+    )
+}
+
+fn get_conversion_task_plan(
+    project: &Path,
+    conversion_context: &ConversionContext,
+    conversion_task: ConversionTask,
+) -> Result<ConversionTaskPlan> {
+    let template =
+        get_template_mapping_from_name(&conversion_task.template, &conversion_context.manifest)?;
+    conversion_decider::get_converter(&template.template_type)?;
+
+    let recorder = ConversionRecorder::new(true);
+    convert_task(
+        project,
+        conversion_context,
+        conversion_task.clone(),
+        &recorder,
+    )?;
+
+    let record = recorder.get_record();
+    let build_directory = &conversion_context.compiled_directory_path;
+    let markdown_project_directory = build_directory.join(&conversion_task.markdown_project.output);
+    let relative_to_build = |path: &PathBuf| {
+        get_build_relative_path(path, &markdown_project_directory, build_directory)
+    };
+    let injections = record.injections.unwrap_or(RenderingInjections::new());
+
+    Ok(ConversionTaskPlan {
+        markdown_project: conversion_task.markdown_project.name.clone(),
+        template: template.name.clone(),
+        converter: template.template_type,
+        preprocessors: record.preprocessors,
+        header_injections: injections
+            .header_injections
+            .iter()
+            .map(relative_to_build)
+            .collect(),
+        body_injections: injections
+            .body_injections
+            .iter()
+            .map(relative_to_build)
+            .collect(),
+        footer_injections: injections
+            .footer_injections
+            .iter()
+            .map(relative_to_build)
+            .collect(),
+        input_files: record.input_files.iter().map(relative_to_build).collect(),
+        chunks: record
+            .chunks
+            .into_iter()
+            .map(|chunk| PlannedChunk {
+                files: chunk.files.iter().map(relative_to_build).collect(),
+                extension: chunk.extension,
+                preprocessor: chunk.preprocessor,
+            })
+            .collect(),
+        commands: record
+            .commands
+            .into_iter()
+            .map(|command| PlannedCommand {
+                working_directory: command.working_directory.as_ref().map(relative_to_build),
+                command_line: command.command_line,
+            })
+            .collect(),
+        output_path: record.output_path.map(|output_path| {
+            without_current_dir(
+                &conversion_task
+                    .markdown_project
+                    .output
+                    .join(output_path.file_name().unwrap_or_default()),
+            )
+        }),
+    })
+}
+
+fn create_plan_directory() -> Result<PathBuf> {
+    let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let plan_directory_path =
+        std::env::temp_dir().join(format!("tiefdown_plan_{}_{}", std::process::id(), nanos));
+
+    dir::create_all(&plan_directory_path, true)?;
+
+    Ok(plan_directory_path)
+}
+
+fn run_watched_conversion(
+    project: &PathBuf,
+    conversion_queue: &[ConversionTask],
+    metadata_overrides: &Option<Table>,
+    jobs: usize,
+) {
+    let conversion_context = match prepare_conversion(project, metadata_overrides) {
+        Ok(conversion_context) => conversion_context,
+        Err(e) => {
+            error!("Conversion failed: {}", e);
+            return;
+        }
+    };
+
+    let started_at = Utc::now();
+    let start = Instant::now();
+
+    let results = match run_conversion_tasks(project, &conversion_context, conversion_queue, jobs) {
+        Ok(results) => results,
+        Err(e) => {
+            error!("Conversion failed: {}", e);
+            return;
+        }
+    };
+
+    log_conversion_status(&results);
+
+    if let Err(e) = create_build_report(
+        project,
+        &conversion_context,
+        started_at,
+        start.elapsed(),
+        &results,
+    )
+    .and_then(|build_report| {
+        write_build_report(&build_report, &conversion_context.compiled_directory_path)
+    }) {
+        error!("Failed to write build report: {}", e);
+    }
+}
+
+fn run_conversion_task(
+    project: &Path,
+    conversion_context: &ConversionContext,
+    conversion_task: ConversionTask,
+) -> ConversionTaskResult {
+    let recorder = ConversionRecorder::new(false);
+    let start = Instant::now();
+    let result = convert_task(
+        project,
+        conversion_context,
+        conversion_task.clone(),
+        &recorder,
+    );
+
+    ConversionTaskResult {
+        conversion_task,
+        result,
+        duration: start.elapsed(),
+        record: recorder.get_record(),
+    }
+}
+
+fn create_build_report(
+    project: &Path,
+    conversion_context: &ConversionContext,
+    started_at: DateTime<Utc>,
+    duration: Duration,
+    results: &[ConversionTaskResult],
+) -> Result<BuildReport> {
+    let build_directory_path = &conversion_context.compiled_directory_path;
+
+    let tasks = results
+        .iter()
+        .map(|task_result| {
+            let template_type = get_template_mapping_from_name(
+                &task_result.conversion_task.template,
+                &conversion_context.manifest,
+            )
+            .ok()
+            .map(|template| template.template_type);
+
+            create_task_report(
+                project,
+                build_directory_path,
+                &task_result.conversion_task,
+                template_type,
+                &task_result.result,
+                task_result.duration,
+                task_result.record.clone(),
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(BuildReport {
+        build_directory: without_current_dir(
+            build_directory_path
+                .strip_prefix(project)
+                .unwrap_or(build_directory_path),
+        ),
+        started_at: started_at.to_rfc3339(),
+        duration_seconds: duration.as_secs_f64(),
+        success: results.iter().all(|task_result| task_result.result.is_ok()),
+        tasks,
+    })
+}
+
+/// Runs the conversion tasks on up to `jobs` threads.
+///
+/// Tasks sharing a markdown project compiled directory write to the same files (the template
+/// directory, resources, metadata files and combined outputs), so they are grouped and run one
+/// after another. Only the groups run concurrently. The results are returned in queue order.
+fn run_conversion_tasks(
+    project: &Path,
+    conversion_context: &ConversionContext,
+    conversion_queue: &[ConversionTask],
+    jobs: usize,
+) -> Result<Vec<ConversionTaskResult>> {
+    let task_groups = group_conversion_tasks(
+        &conversion_context.compiled_directory_path,
+        conversion_queue,
+    );
+
+    debug!(
+        "Running {} conversion tasks in {} groups on {} threads.",
+        conversion_queue.len(),
+        task_groups.len(),
+        jobs
+    );
+
+    let thread_pool = ThreadPoolBuilder::new().num_threads(jobs).build()?;
+
+    let mut results: Vec<(usize, ConversionTaskResult)> = thread_pool.install(|| {
+        task_groups
+            .par_iter()
+            .flat_map_iter(|indices| {
+                indices.iter().map(|index| {
+                    (
+                        *index,
+                        run_conversion_task(
+                            project,
+                            conversion_context,
+                            conversion_queue[*index].clone(),
+                        ),
+                    )
+                })
+            })
+            .collect()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
+/// Groups the conversion tasks by the compiled directory of their markdown project.
+///
+/// Tasks whose compiled directories are equal or nested in one another share a group, as
+/// their outputs overlap.
+///
+/// # Arguments
+///
+/// * `compiled_directory_path` - The build directory of the project.
+/// * `conversion_queue` - The conversion tasks to group.
+///
+/// # Returns
+///
+/// The indices of the tasks in the queue, grouped in queue order.
+pub(crate) fn group_conversion_tasks(
+    compiled_directory_path: &Path,
+    conversion_queue: &[ConversionTask],
+) -> Vec<Vec<usize>> {
+    let mut task_groups: Vec<(Vec<PathBuf>, Vec<usize>)> = vec![];
+
+    for (index, conversion_task) in conversion_queue.iter().enumerate() {
+        let markdown_project_compiled_directory_path = get_markdown_project_compiled_directory_path(
+            compiled_directory_path,
+            &conversion_task.markdown_project,
         );
 
-        copy_markdown_directory(
-            &input_dir,
-            &conversion_input_dir,
-            &markdown_project.resources,
-        )?;
+        let overlapping_groups = task_groups
+            .iter()
+            .enumerate()
+            .filter(|(_, (paths, _))| {
+                paths.iter().any(|path| {
+                    path.starts_with(&markdown_project_compiled_directory_path)
+                        || markdown_project_compiled_directory_path.starts_with(path)
+                })
+            })
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+
+        let Some(&first_group) = overlapping_groups.first() else {
+            task_groups.push((vec![markdown_project_compiled_directory_path], vec![index]));
+            continue;
+        };
 
-        convert_template(
-            &markdown_project_compiled_directory_path,
-            &template,
-            &project,
-            &conversion_input_dir,
-            &markdown_project.output,
-            &merged_metadata,
-            &manifest.metadata_settings,
-            &manifest.custom_processors,
-            &manifest.injections.clone().unwrap_or(vec![]),
-        )?;
+        // A task can connect groups that didn't overlap before, so they are merged into the
+        // earliest one.
+        for &position in overlapping_groups[1..].iter().rev() {
+            let (paths, indices) = task_groups.remove(position);
+            task_groups[first_group].0.extend(paths);
+            task_groups[first_group].1.extend(indices);
+        }
+
+        let (paths, indices) = &mut task_groups[first_group];
+        paths.push(markdown_project_compiled_directory_path);
+        indices.push(index);
+        indices.sort();
+    }
+
+    task_groups
+        .into_iter()
+        .map(|(_, indices)| indices)
+        .collect()
+}
+
+fn log_conversion_status(results: &[ConversionTaskResult]) {
+    info!("Conversion status:");
+    for task_result in results {
+        match &task_result.result {
+            Ok(()) => info!(
+                "  ok      {} -> {} ({:.1}s)",
+                task_result.conversion_task.markdown_project.name,
+                task_result.conversion_task.template,
+                task_result.duration.as_secs_f32()
+            ),
+            Err(e) => error!(
+                "  failed  {} -> {} ({:.1}s): {}",
+                task_result.conversion_task.markdown_project.name,
+                task_result.conversion_task.template,
+                task_result.duration.as_secs_f32(),
+                e
+            ),
+        }
+    }
+}
+
+fn receive_changed_paths(
+    receiver: &Receiver<notify::Result<Event>>,
+    debounce: Duration,
+) -> Result<Vec<PathBuf>> {
+    let mut changed_paths = vec![];
+
+    collect_changed_paths(receiver.recv()?, &mut changed_paths);
+
+    loop {
+        match receiver.recv_timeout(debounce) {
+            Ok(event) => collect_changed_paths(event, &mut changed_paths),
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(eyre!("The file watcher stopped unexpectedly."));
+            }
+        }
+    }
+
+    changed_paths.sort();
+    changed_paths.dedup();
+
+    Ok(changed_paths)
+}
+
+fn collect_changed_paths(event: notify::Result<Event>, changed_paths: &mut Vec<PathBuf>) {
+    match event {
+        Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+            changed_paths.extend(event.paths)
+        }
+        Ok(_) => {}
+        Err(e) => warn!("File watcher error: {}", e),
+    }
+}
+
+/// Returns the manifest file and the manifest fragments it includes.
+fn get_manifest_files(project: &Path) -> Result<Vec<PathBuf>> {
+    let manifest_path = project.join("manifest.toml");
+    let manifest = load_and_convert_manifest(&manifest_path)?;
+
+    let mut manifest_files = vec![manifest_path];
+    manifest_files.extend(
+        manifest
+            .fragments
+            .iter()
+            .map(|fragment| project.join(&fragment.path)),
+    );
+
+    Ok(manifest_files)
+}
+
+/// Reads the manifest files into a single string, used to detect changes to them.
+fn read_manifest_files(manifest_files: &[PathBuf]) -> String {
+    manifest_files
+        .iter()
+        .map(|manifest_file| fs::read_to_string(manifest_file).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn update_watched_paths(
+    watcher: &mut RecommendedWatcher,
+    watched_paths: &mut Vec<PathBuf>,
+    conversion_queue: &[ConversionTask],
+    manifest_files: &[PathBuf],
+    project: &Path,
+) -> Result<()> {
+    for watched_path in watched_paths.drain(..) {
+        let _ = watcher.unwatch(&watched_path);
+    }
+
+    let mut paths = vec![
+        (project.to_path_buf(), RecursiveMode::NonRecursive),
+        (project.join("template"), RecursiveMode::Recursive),
+    ];
+
+    for manifest_file in manifest_files {
+        paths.push((manifest_file.clone(), RecursiveMode::NonRecursive));
+    }
+
+    for conversion_task in conversion_queue {
+        let markdown_project = &conversion_task.markdown_project;
+        let input_dir = project.join(&markdown_project.path);
+
+        for resource in get_resources(markdown_project) {
+            paths.push((input_dir.join(resource), RecursiveMode::Recursive));
+        }
+
+        paths.push((input_dir, RecursiveMode::Recursive));
+    }
+
+    for (path, recursive_mode) in paths {
+        if !path.exists() || watched_paths.contains(&path) {
+            continue;
+        }
+
+        watcher.watch(&path, recursive_mode)?;
+        debug!("Watching '{}'.", path.display());
+
+        watched_paths.push(path);
     }
 
     Ok(())
 }
 
+fn refresh_conversion_queue(
+    project: &Path,
+    conversion_queue: &[ConversionTask],
+) -> Result<Vec<ConversionTask>> {
+    let manifest = load_and_convert_manifest(&project.join("manifest.toml"))?;
+    let markdown_projects = get_markdown_projects(&manifest);
+
+    Ok(conversion_queue
+        .iter()
+        .filter_map(|conversion_task| {
+            let markdown_project = markdown_projects
+                .iter()
+                .find(|mp| mp.name == conversion_task.markdown_project.name);
+
+            if markdown_project.is_none() {
+                warn!(
+                    "Markdown project '{}' was removed from the manifest and will no longer be converted.",
+                    conversion_task.markdown_project.name
+                );
+            }
+
+            markdown_project.map(|markdown_project| ConversionTask {
+                markdown_project: markdown_project.clone(),
+                template: conversion_task.template.clone(),
+                profile: conversion_task.profile.clone(),
+            })
+        })
+        .collect())
+}
+
+fn is_conversion_task_affected(
+    project: &Path,
+    manifest: &Manifest,
+    conversion_task: &ConversionTask,
+    changed_path: &Path,
+) -> bool {
+    if changed_path == project.join("manifest.toml")
+        || manifest
+            .fragments
+            .iter()
+            .any(|fragment| changed_path == project.join(&fragment.path))
+    {
+        return true;
+    }
+
+    let markdown_project = &conversion_task.markdown_project;
+    let input_dir = normalize_path(&project.join(&markdown_project.path));
+
+    for resource in get_resources(markdown_project) {
+        if changed_path.starts_with(normalize_path(&input_dir.join(resource))) {
+            return true;
+        }
+    }
+
+    if let Ok(relative_path) = changed_path.strip_prefix(&input_dir) {
+        return relative_path == Path::new(IGNORE_FILE_NAME)
+            || InputFilter::new(markdown_project, &input_dir)
+                .map(|input_filter| input_filter.is_included(relative_path, changed_path.is_dir()))
+                .unwrap_or(true);
+    }
+
+    let template_directory_path = normalize_path(&project.join("template"));
+
+    if let Ok(relative_path) = changed_path.strip_prefix(&template_directory_path) {
+        let injections = manifest.injections.clone().unwrap_or(vec![]);
+        let using_templates: Vec<&Template> = manifest
+            .templates
+            .iter()
+            .filter(|template| template_uses_path(template, &injections, relative_path))
+            .collect();
+
+        return using_templates.is_empty()
+            || using_templates
+                .iter()
+                .any(|template| template.name == conversion_task.template);
+    }
+
+    false
+}
+
+fn template_uses_path(template: &Template, injections: &[Injection], relative_path: &Path) -> bool {
+    if relative_path.starts_with(get_template_path(
+        template.template_file.clone(),
+        &template.name,
+    )) {
+        return true;
+    }
+
+    if template
+        .filters
+        .clone()
+        .unwrap_or(vec![])
+        .iter()
+        .any(|filter| relative_path.starts_with(filter))
+    {
+        return true;
+    }
+
+    if get_bibliography_paths(&template.bibliography)
+        .iter()
+        .any(|file| relative_path.starts_with(file))
+    {
+        return true;
+    }
+
+    [
+        &template.header_injections,
+        &template.body_injections,
+        &template.footer_injections,
+    ]
+    .into_iter()
+    .flat_map(|names| names.clone().unwrap_or(vec![]))
+    .filter_map(|name| injections.iter().find(|injection| injection.name == name))
+    .flat_map(|injection| injection.files.iter())
+    .any(|file| relative_path.starts_with(file))
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or(path.to_path_buf())
+}
+
+/// Returns the directory a markdown project is compiled in.
+///
+/// The path is normalized lexically, so outputs like `./out`, `out` and `out/../out` share
+/// the same compiled directory, even before it is created.
+fn get_markdown_project_compiled_directory_path(
+    compiled_directory_path: &Path,
+    markdown_project: &MarkdownProject,
+) -> PathBuf {
+    let mut path = PathBuf::new();
+
+    for component in compiled_directory_path
+        .join(&markdown_project.output)
+        .components()
+    {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(path.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                path.pop();
+            }
+            component => path.push(component),
+        }
+    }
+
+    path
+}
+
+pub(crate) fn get_markdown_projects(manifest: &Manifest) -> Vec<MarkdownProject> {
+    manifest
+        .markdown_projects
+        .clone()
+        .unwrap_or(vec![MarkdownProject {
+            name: "Default".to_string(),
+            path: PathBuf::from("Markdown"),
+            output: PathBuf::from("."),
+            metadata_fields: None,
+            default_profile: None,
+            resources: None,
+            include: None,
+            exclude: None,
+            bibliography: None,
+        }])
+}
+
 fn copy_resources(
     markdown_project: &MarkdownProject,
     markdown_project_compiled_directory_path: &PathBuf,
     markdown_dir: &PathBuf,
 ) -> Result<()> {
-    for resource in markdown_project.resources.clone().unwrap_or(vec![]) {
+    for resource in get_resources(markdown_project) {
         let resource = markdown_dir.join(resource.clone());
 
         if !resource.exists() {
@@ -301,14 +1411,6 @@ fn copy_resources(
     Ok(())
 }
 
-fn merge_metadata(shared_metadata: &Table, project_metadata: &Table) -> Table {
-    let mut merged_metadata = shared_metadata.clone();
-    for (key, value) in project_metadata {
-        merged_metadata.insert(key.clone(), value.clone());
-    }
-    merged_metadata
-}
-
 fn get_template_names(
     templates: &Option<Vec<String>>,
     profile: &Option<String>,
@@ -382,7 +1484,8 @@ fn create_build_directory(project_path: &Path) -> Result<std::path::PathBuf> {
 fn copy_markdown_directory(
     markdown_dir: &Path,
     output_dir: &Path,
-    resources: &Option<Vec<PathBuf>>,
+    resources: &[PathBuf],
+    input_filter: &InputFilter,
 ) -> Result<()> {
     debug!(
         "Copying markdown directory '{}' to '{}'",
@@ -394,14 +1497,10 @@ fn copy_markdown_directory(
         dir::create_all(output_dir, false)?;
     }
 
-    dir::copy(
-        markdown_dir,
-        output_dir,
-        &dir::CopyOptions::new().overwrite(true).content_only(true),
-    )?;
+    copy_filtered_files(markdown_dir, output_dir, input_filter)?;
 
-    for resource in resources.clone().unwrap_or(vec![]) {
-        let resource = output_dir.join(resource.clone());
+    for resource in resources {
+        let resource = output_dir.join(resource);
 
         if resource.is_dir() {
             dir::remove(resource)?;
@@ -413,9 +1512,28 @@ fn copy_markdown_directory(
     Ok(())
 }
 
+fn get_artifact_files(artifact_path: &Path) -> Result<Vec<PathBuf>> {
+    if !artifact_path.is_dir() {
+        return Ok(vec![artifact_path.to_path_buf()]);
+    }
+
+    let mut entries = fs::read_dir(artifact_path)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    let mut files = vec![];
+    for entry in entries {
+        files.extend(get_artifact_files(&entry)?);
+    }
+
+    Ok(files)
+}
+
 fn convert_template(
     compiled_directory_path: &Path,
     template: &Template,
+    conversion_meta: &ConversionMeta,
     project_path: &Path,
     conversion_input_dir: &Path,
     output_dir: &Path,
@@ -423,12 +1541,16 @@ fn convert_template(
     metadata_settings: &Option<MetadataSettings>,
     custom_processors: &Processors,
     injections: &Vec<Injection>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<()> {
     debug!("Starting template conversion for '{}'.", template.name);
     debug!("  Template type: '{}'.", template.template_type);
 
     let converter = conversion_decider::get_converter(&template.template_type)?;
 
+    write_conditional_content_filter(compiled_directory_path)?;
+
     let metadata_settings = metadata_settings
         .clone()
         .unwrap_or(MetadataSettings::default());
@@ -440,19 +1562,33 @@ fn convert_template(
         compiled_directory_path,
         conversion_input_dir,
         template,
+        conversion_meta,
         metadata_fields,
         &metadata_settings,
         custom_processors,
         injections,
+        build_cache,
+        recorder,
     )?;
 
     debug!("Converter finished.");
     debug!("  Result file path: {}", result_file_path.display());
 
+    recorder.record_output(&result_file_path);
+
+    if recorder.is_dry_run() {
+        debug!("Dry run, not copying result file to output directory.");
+        return Ok(());
+    }
+
     debug!("Copying result file to output directory...");
 
     dir::create_all(project_path.join(output_dir), false)?;
 
+    let artifact_path = project_path
+        .join(output_dir)
+        .join(result_file_path.file_name().unwrap_or_default());
+
     if template.multi_file_output.unwrap_or(false) {
         dir::copy(
             &result_file_path,
@@ -465,13 +1601,15 @@ fn convert_template(
     } else {
         file::copy(
             &result_file_path,
-            project_path
-                .join(output_dir)
-                .join(result_file_path.file_name().unwrap_or_default()),
+            &artifact_path,
             &file::CopyOptions::new().overwrite(true),
         )?;
     }
 
+    for artifact in get_artifact_files(&artifact_path)? {
+        recorder.record_artifact(&artifact);
+    }
+
     debug!("Copying finished.");
 
     info!("Conversion successful.");
@@ -1,5 +1,8 @@
 use crate::{
+    build_cache::BuildCache,
+    conversion_recorder::ConversionRecorder,
     converters::{
+        conversion_meta::ConversionMeta,
         custom_preprocessor_converter::convert_custom_preprocessors,
         custom_processor_converter::convert_custom_processor, epub_converter::convert_epub,
         tex_converter::convert_latex, typst_converter::convert_typst,
@@ -17,10 +20,13 @@ type Converter = fn(
     compiled_directory_path: &Path,
     conversion_input_dir: &Path,
     template: &Template,
+    conversion_meta: &ConversionMeta,
     metadata_fields: &Table,
     metadata_settings: &MetadataSettings,
     custom_processors: &Processors,
     injections: &Vec<Injection>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<PathBuf>;
 
 pub(crate) fn get_converter(template_type: &TemplateType) -> Result<Converter> {
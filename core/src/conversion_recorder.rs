@@ -0,0 +1,262 @@
+use color_eyre::eyre::Result;
+use log::debug;
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    build_report::{WarningKind, WarningReport},
+    converters::common::{check_exit_code, run_with_logging},
+    injections::RenderingInjections,
+    manifest_model::PreProcessor,
+};
+
+/// A preprocessing chunk as recorded during a conversion.
+#[derive(Clone)]
+pub(crate) struct RecordedChunk {
+    pub(crate) order: (usize, usize),
+    pub(crate) files: Vec<PathBuf>,
+    pub(crate) extension: String,
+    pub(crate) preprocessor: String,
+    pub(crate) cached: bool,
+}
+
+/// An external command as recorded during a conversion.
+///
+/// The duration and exit code are only set if the command was run.
+#[derive(Clone)]
+pub(crate) struct RecordedCommand {
+    pub(crate) order: (usize, usize),
+    pub(crate) working_directory: Option<PathBuf>,
+    pub(crate) command_line: String,
+    pub(crate) duration: Option<Duration>,
+    pub(crate) exit_code: Option<i32>,
+}
+
+/// Everything recorded during the conversion of a single task.
+#[derive(Clone, Default)]
+pub(crate) struct ConversionRecord {
+    pub(crate) preprocessors: Vec<PreProcessor>,
+    pub(crate) injections: Option<RenderingInjections>,
+    pub(crate) input_files: Vec<PathBuf>,
+    pub(crate) chunks: Vec<RecordedChunk>,
+    pub(crate) commands: Vec<RecordedCommand>,
+    pub(crate) output_path: Option<PathBuf>,
+    pub(crate) artifacts: Vec<PathBuf>,
+    pub(crate) warnings: Vec<WarningReport>,
+}
+
+#[derive(Default)]
+struct RecorderState {
+    next_step: usize,
+    record: ConversionRecord,
+}
+
+/// Records the steps of a single conversion task and runs its external commands.
+///
+/// In dry-run mode, external commands are only recorded and never run. They return an empty
+/// output instead.
+///
+/// Preprocessing chunks may be processed in parallel, so every chunk and command is recorded with
+/// an order key consisting of a step and an index within that step. The recorded chunks and
+/// commands are sorted by this key when the record is retrieved.
+pub(crate) struct ConversionRecorder {
+    dry_run: bool,
+    state: Mutex<RecorderState>,
+}
+
+impl ConversionRecorder {
+    pub(crate) fn new(dry_run: bool) -> ConversionRecorder {
+        ConversionRecorder {
+            dry_run,
+            state: Mutex::new(RecorderState::default()),
+        }
+    }
+
+    pub(crate) fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Starts a new step, for example a preprocessing run over multiple chunks.
+    pub(crate) fn next_step(&self) -> usize {
+        let mut state = self.lock();
+        let step = state.next_step;
+        state.next_step += 1;
+        step
+    }
+
+    pub(crate) fn record_preprocessors(&self, preprocessors: &[PreProcessor]) {
+        self.lock().record.preprocessors = preprocessors.to_vec();
+    }
+
+    pub(crate) fn record_injections(&self, injections: &RenderingInjections) {
+        self.lock().record.injections = Some(injections.clone());
+    }
+
+    pub(crate) fn record_input_files(&self, input_files: &[PathBuf]) {
+        self.lock().record.input_files = input_files.to_vec();
+    }
+
+    pub(crate) fn record_chunk(
+        &self,
+        order: (usize, usize),
+        files: &[PathBuf],
+        extension: &str,
+        preprocessor: &PreProcessor,
+        cached: bool,
+    ) {
+        self.lock().record.chunks.push(RecordedChunk {
+            order,
+            files: files.to_vec(),
+            extension: extension.to_string(),
+            preprocessor: preprocessor.name.clone(),
+            cached,
+        });
+    }
+
+    pub(crate) fn record_output(&self, output_path: &Path) {
+        self.lock().record.output_path = Some(output_path.to_path_buf());
+    }
+
+    /// Records a file written to the output directory of the project.
+    pub(crate) fn record_artifact(&self, artifact_path: &Path) {
+        self.lock()
+            .record
+            .artifacts
+            .push(artifact_path.to_path_buf());
+    }
+
+    pub(crate) fn record_warning(&self, warning: WarningReport) {
+        self.lock().record.warnings.push(warning);
+    }
+
+    /// Runs a command in a new step. See [`ConversionRecorder::run_command_at`].
+    pub(crate) fn run_command(
+        &self,
+        command: Command,
+        command_name: &str,
+        supress_verbose: bool,
+    ) -> Result<String> {
+        let order = (self.next_step(), 0);
+        self.run_command_at(order, command, command_name, supress_verbose)
+    }
+
+    /// Records a command and runs it, unless this is a dry run.
+    ///
+    /// Every line the command writes to stderr is recorded as a warning, as is a non-zero exit
+    /// code that does not fail the conversion.
+    pub(crate) fn run_command_at(
+        &self,
+        order: (usize, usize),
+        command: Command,
+        command_name: &str,
+        supress_verbose: bool,
+    ) -> Result<String> {
+        let command_line = format_command(&command);
+        let mut recorded_command = RecordedCommand {
+            order,
+            working_directory: command.get_current_dir().map(|d| d.to_path_buf()),
+            command_line: command_line.clone(),
+            duration: None,
+            exit_code: None,
+        };
+
+        if self.dry_run {
+            debug!("Dry run, not executing: {}", command_line);
+            self.lock().record.commands.push(recorded_command);
+            return Ok(String::new());
+        }
+
+        let start = Instant::now();
+        let output = run_with_logging(command, command_name, supress_verbose);
+        recorded_command.duration = Some(start.elapsed());
+
+        let output = match output {
+            Ok(output) => output,
+            Err(e) => {
+                self.lock().record.commands.push(recorded_command);
+                return Err(e);
+            }
+        };
+
+        recorded_command.exit_code = output.exit_code;
+
+        {
+            let mut state = self.lock();
+            state.record.commands.push(recorded_command);
+            state.record.warnings.extend(
+                output
+                    .stderr
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(|line| WarningReport {
+                        source: command_name.to_string(),
+                        kind: WarningKind::Stderr,
+                        message: line.to_string(),
+                        file: None,
+                        line: None,
+                    }),
+            );
+        }
+
+        check_exit_code(command_name, output.exit_code)?;
+
+        if output.exit_code != Some(0) {
+            self.record_warning(WarningReport {
+                source: command_name.to_string(),
+                kind: WarningKind::ExitCode,
+                message: format!(
+                    "Exited with status code {}.",
+                    output
+                        .exit_code
+                        .map(|c| c.to_string())
+                        .unwrap_or("unknown".to_string())
+                ),
+                file: None,
+                line: None,
+            });
+        }
+
+        Ok(output.stdout)
+    }
+
+    /// Returns everything recorded so far, with chunks and commands in order.
+    pub(crate) fn get_record(&self) -> ConversionRecord {
+        let mut record = self.lock().record.clone();
+
+        record.chunks.sort_by_key(|c| c.order);
+        record.commands.sort_by_key(|c| c.order);
+
+        record
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RecorderState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Formats a command as a shell command line, quoting arguments where necessary.
+pub(crate) fn format_command(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| quote_argument(&arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn quote_argument(argument: &str) -> String {
+    let is_safe = !argument.is_empty()
+        && argument
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+
+    if is_safe {
+        argument.to_string()
+    } else {
+        format!("'{}'", argument.replace('\'', r"'\''"))
+    }
+}
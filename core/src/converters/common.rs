@@ -1,7 +1,7 @@
 use color_eyre::eyre::{Ok, Result, eyre};
 use fast_glob::glob_match;
 use log::{debug, error};
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use std::{
     fs,
     io::{BufRead, BufReader},
@@ -9,16 +9,23 @@ use std::{
     process::{Command, Stdio},
     thread,
 };
-use toml::Table;
+use toml::{Table, Value};
 
 use crate::{
+    bibliography::add_bibliography_args,
+    build_cache::BuildCache,
+    conversion_recorder::ConversionRecorder,
+    converters::conditional_content::add_conditional_content_filter,
+    converters::conversion_meta::ConversionMeta,
     file_retrieval::get_relative_path_from_compiled_dir,
+    front_matter::read_front_matter,
     injections::RenderingInjections,
     manifest_model::{
         MetaGenerationSettings, MetadataSettings, PreProcessor, PreProcessors, Template,
     },
     meta_generation_format::MetaGenerationFormat,
-    nav_meta_generation::NavMeta,
+    metadata_management::deep_merge_metadata,
+    nav_meta_generation::{NavMeta, get_breadcrumbs},
     template_type::TemplateType,
 };
 
@@ -140,7 +147,9 @@ pub(crate) fn generate_meta_file(
         fs::create_dir_all(parent)?;
     }
 
-    let meta_yaml = serde_yaml::to_string(metadata_fields)?;
+    let metadata_fields = normalize_metadata(metadata_fields);
+
+    let meta_yaml = serde_yaml::to_string(&metadata_fields)?;
     fs::write(&output, meta_yaml)?;
     debug!("Navigation metadata written to {}", output.display());
 
@@ -155,7 +164,7 @@ pub(crate) fn generate_meta_file(
 
         let output = compiled_directory_path.join(output);
 
-        let meta_json = serde_json::to_string(metadata_fields)?;
+        let meta_json = serde_json::to_string(&metadata_fields)?;
         fs::write(&output, meta_json)?;
         debug!("Navigation metadata written to {}", output.display());
     }
@@ -165,6 +174,7 @@ pub(crate) fn generate_meta_file(
 
 pub(crate) fn run_preprocessors_on_injections(
     template: &Template,
+    conversion_meta: &ConversionMeta,
     compiled_directory_path: &Path,
     metadata_fields: &Table,
     metadata_file: &Option<PathBuf>,
@@ -172,11 +182,14 @@ pub(crate) fn run_preprocessors_on_injections(
     nav_meta_data: &Option<(NavMeta, PathBuf)>,
     preprocessors: &Vec<PreProcessor>,
     input_files: &Vec<PathBuf>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<Vec<String>> {
     debug!("Running Preprocessors on injection.");
     if input_files.len() > 0 {
         run_preprocessors_on_inputs(
             template,
+            conversion_meta,
             compiled_directory_path,
             metadata_fields,
             metadata_file,
@@ -190,6 +203,8 @@ pub(crate) fn run_preprocessors_on_injections(
                         .unwrap_or(i.clone())
                 })
                 .collect(),
+            build_cache,
+            recorder,
         )
     } else {
         Ok(vec![])
@@ -198,6 +213,7 @@ pub(crate) fn run_preprocessors_on_injections(
 
 pub(crate) fn run_preprocessors_on_inputs(
     template: &Template,
+    conversion_meta: &ConversionMeta,
     compiled_directory_path: &Path,
     metadata_fields: &Table,
     metadata_file: &Option<PathBuf>,
@@ -205,27 +221,61 @@ pub(crate) fn run_preprocessors_on_inputs(
     nav_meta_data: &Option<(NavMeta, PathBuf)>,
     preprocessors: &Vec<PreProcessor>,
     input_files: &Vec<PathBuf>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<Vec<String>> {
     let processing_chunks =
         get_preprocessing_chunks(&input_files, template.multi_file_output.unwrap_or(false))?;
     debug!("Created {} preprocessing chunks.", processing_chunks.len());
 
+    let step = recorder.next_step();
+
     let results = processing_chunks
         .par_iter()
-        .map(|chunk| {
+        .enumerate()
+        .map(|(index, chunk)| {
             debug!("Processing chunk with extension {}", chunk.1);
 
             let preprocessor = choose_preprocessor(preprocessors, &chunk.1)?;
 
-            run_preprocessor(
+            let cache_key = build_cache.preprocessing_key(
+                template,
+                compiled_directory_path,
+                metadata_file,
+                nav_meta_data,
+                &preprocessor,
+                &chunk.0,
+            )?;
+
+            let cached_result = build_cache.get_chunk(&cache_key);
+            recorder.record_chunk(
+                (step, index),
+                &chunk.0,
+                &chunk.1,
+                &preprocessor,
+                cached_result.is_some(),
+            );
+
+            if let Some(result) = cached_result {
+                return Ok(result);
+            }
+
+            let result = run_preprocessor(
                 template,
+                conversion_meta,
                 compiled_directory_path,
                 metadata_fields,
                 metadata_file,
                 nav_meta_data,
                 &preprocessor,
                 &chunk.0,
-            )
+                recorder,
+                (step, index),
+            )?;
+
+            build_cache.store_chunk(&cache_key, &result)?;
+
+            Ok(result)
         })
         .collect::<Result<Vec<_>>>()?;
 
@@ -248,12 +298,15 @@ fn choose_preprocessor(preprocessors: &Vec<PreProcessor>, extension: &str) -> Re
 
 fn run_preprocessor(
     template: &Template,
+    conversion_meta: &ConversionMeta,
     compiled_directory_path: &Path,
     metadata_fields: &toml::map::Map<String, toml::Value>,
     metadata_file: &Option<PathBuf>,
     nav_meta_data: &Option<(NavMeta, PathBuf)>,
     preprocessor: &PreProcessor,
     files: &Vec<PathBuf>,
+    recorder: &ConversionRecorder,
+    order: (usize, usize),
 ) -> std::result::Result<String, color_eyre::eyre::Error> {
     debug!(
         "Running preprocessor '{}' on {} files.",
@@ -262,6 +315,8 @@ fn run_preprocessor(
     );
 
     let cli_name = preprocessor.cli.clone().unwrap_or("pandoc".to_string());
+    let metadata_fields =
+        get_file_metadata(template, compiled_directory_path, metadata_fields, files)?;
     let cli_args = preprocess_cli_args(&preprocessor.cli_args, &metadata_fields);
 
     let mut cli = Command::new(&cli_name);
@@ -272,7 +327,9 @@ fn run_preprocessor(
         && template.template_type != TemplateType::CustomProcessor
         && template.template_type != TemplateType::Epub
     {
-        add_lua_filters(template, compiled_directory_path, &mut cli)?;
+        add_lua_filters(template, conversion_meta, compiled_directory_path, &mut cli)?;
+
+        add_bibliography_args(&conversion_meta.bibliography, &mut cli);
 
         add_nav_meta(nav_meta_data, compiled_directory_path, &mut cli)?;
 
@@ -290,7 +347,30 @@ fn run_preprocessor(
             .collect::<Vec<_>>()
             .join("\" \"")
     );
-    run_with_logging(cli, &cli_name, true)
+    recorder.run_command_at(order, cli, &cli_name, true)
+}
+
+/// Merges the front matter of the file into the metadata when converting each file separately.
+fn get_file_metadata(
+    template: &Template,
+    compiled_directory_path: &Path,
+    metadata_fields: &Table,
+    files: &[PathBuf],
+) -> Result<Table> {
+    let mut metadata_fields = metadata_fields.clone();
+
+    if let (Some(true), [file]) = (template.multi_file_output, files)
+        && let Some(front_matter) = read_front_matter(&compiled_directory_path.join(file))?
+    {
+        debug!(
+            "Merging {} front matter fields of '{}'.",
+            front_matter.len(),
+            file.display()
+        );
+        deep_merge_metadata(&mut metadata_fields, &front_matter);
+    }
+
+    Ok(metadata_fields)
 }
 
 fn get_preprocessing_chunks(
@@ -361,14 +441,18 @@ fn get_preprocessing_chunks(
     Ok(chunks)
 }
 
+/// Replaces `{{key}}` placeholders in the arguments with the metadata values.
+///
+/// Nested values are referenced by their dotted path, see `flatten_metadata`.
 pub(crate) fn preprocess_cli_args(cli_args: &[String], metadata_fields: &Table) -> Vec<String> {
     let mut processed_args = Vec::new();
+    let flattened_metadata = flatten_metadata(metadata_fields, &|_, value| value.to_string());
 
     for arg in cli_args.iter() {
         let mut processed_arg = arg.clone();
-        for (metadata_key, metadata_value) in metadata_fields.iter() {
-            let value = metadata_value.as_str().unwrap_or("");
-            processed_arg = processed_arg.replace(&format!("{{{{{}}}}}", metadata_key), value);
+        for (metadata_path, metadata_value) in flattened_metadata.iter() {
+            processed_arg =
+                processed_arg.replace(&format!("{{{{{}}}}}", metadata_path), metadata_value);
         }
         processed_args.push(processed_arg);
     }
@@ -380,11 +464,119 @@ pub(crate) fn preprocess_cli_args(cli_args: &[String], metadata_fields: &Table)
     processed_args
 }
 
+/// Flattens metadata into dotted paths and their values, in order.
+///
+/// * Values in tables are referenced by `table.key`.
+/// * Items of arrays are referenced by `array.1`, `array.2` and so on, and the number of items by
+///   `array.count`. Arrays of plain values are also joined with `, ` into `array` itself.
+/// * Integers, floats, booleans and dates are formatted as in TOML.
+///
+/// `format_value` is called with the path and the plain value of every string, number, boolean
+/// and date, e.g. to escape it.
+pub(crate) fn flatten_metadata(
+    metadata_fields: &Table,
+    format_value: &dyn Fn(&str, &str) -> String,
+) -> Vec<(String, String)> {
+    let mut flattened_metadata = vec![];
+
+    for (key, value) in metadata_fields {
+        flatten_metadata_value(key, value, format_value, &mut flattened_metadata);
+    }
+
+    flattened_metadata
+}
+
+fn flatten_metadata_value(
+    path: &str,
+    value: &Value,
+    format_value: &dyn Fn(&str, &str) -> String,
+    flattened_metadata: &mut Vec<(String, String)>,
+) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                flatten_metadata_value(
+                    &format!("{}.{}", path, key),
+                    value,
+                    format_value,
+                    flattened_metadata,
+                );
+            }
+        }
+        Value::Array(array) => {
+            if array
+                .iter()
+                .all(|item| !matches!(item, Value::Table(_) | Value::Array(_)))
+            {
+                let joined = array
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| {
+                        format_value(
+                            &format!("{}.{}", path, index + 1),
+                            &format_metadata_scalar(item),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                flattened_metadata.push((path.to_string(), joined));
+            }
+
+            flattened_metadata.push((format!("{}.count", path), array.len().to_string()));
+
+            for (index, item) in array.iter().enumerate() {
+                flatten_metadata_value(
+                    &format!("{}.{}", path, index + 1),
+                    item,
+                    format_value,
+                    flattened_metadata,
+                );
+            }
+        }
+        _ => flattened_metadata.push((
+            path.to_string(),
+            format_value(path, &format_metadata_scalar(value)),
+        )),
+    }
+}
+
+fn format_metadata_scalar(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Integer(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::Boolean(value) => value.to_string(),
+        Value::Datetime(value) => value.to_string(),
+        Value::Array(_) | Value::Table(_) => String::new(),
+    }
+}
+
+/// Converts the dates in the metadata to strings, so the metadata serializes to plain YAML and
+/// JSON values.
+pub(crate) fn normalize_metadata(metadata_fields: &Table) -> Table {
+    metadata_fields
+        .iter()
+        .map(|(key, value)| (key.clone(), normalize_metadata_value(value)))
+        .collect()
+}
+
+fn normalize_metadata_value(value: &Value) -> Value {
+    match value {
+        Value::Datetime(value) => Value::String(value.to_string()),
+        Value::Array(array) => Value::Array(array.iter().map(normalize_metadata_value).collect()),
+        Value::Table(table) => Value::Table(normalize_metadata(table)),
+        _ => value.clone(),
+    }
+}
+
 pub(crate) fn add_lua_filters(
     template: &Template,
+    conversion_meta: &ConversionMeta,
     compiled_directory_path: &Path,
     pandoc: &mut Command,
 ) -> Result<()> {
+    add_conditional_content_filter(conversion_meta, pandoc);
+
     for filter in template.filters.clone().unwrap_or_default() {
         let filter = compiled_directory_path.join(&filter);
 
@@ -495,6 +687,7 @@ pub(crate) fn write_combined_output(
 
 pub(crate) fn write_multi_file_outputs(
     template: &Template,
+    conversion_meta: &ConversionMeta,
     compiled_directory_path: &Path,
     conversion_input_dir: &Path,
     output_path: &Path,
@@ -507,6 +700,8 @@ pub(crate) fn write_multi_file_outputs(
     nav_meta_data: &Option<(NavMeta, PathBuf)>,
     preprocessors: &Vec<PreProcessor>,
     results: &Vec<String>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<()> {
     debug!(
         "Writing {} files to directory: {}",
@@ -538,6 +733,7 @@ pub(crate) fn write_multi_file_outputs(
 
             let header_injections = run_preprocessors_on_injections(
                 template,
+                conversion_meta,
                 compiled_directory_path,
                 metadata_fields,
                 metadata_file,
@@ -545,9 +741,12 @@ pub(crate) fn write_multi_file_outputs(
                 &nav_meta_data,
                 &preprocessors,
                 &injections.header_injections,
+                build_cache,
+                recorder,
             )?;
             let footer_injections = run_preprocessors_on_injections(
                 template,
+                conversion_meta,
                 compiled_directory_path,
                 metadata_fields,
                 metadata_file,
@@ -555,6 +754,8 @@ pub(crate) fn write_multi_file_outputs(
                 &nav_meta_data,
                 &preprocessors,
                 &injections.footer_injections,
+                build_cache,
+                recorder,
             )?;
 
             let relative_conversion_input_dir =
@@ -611,11 +812,19 @@ fn get_current_node_nav_meta(
             let matches = relative_path.with_extension("") == n.path.with_extension("");
             matches
         });
+        let breadcrumbs = current.and_then(|current| {
+            nav_meta
+                .tree
+                .as_ref()
+                .and_then(|tree| get_breadcrumbs(tree, &current.id))
+        });
 
         Some((
             NavMeta {
                 nodes: None,
+                tree: None,
                 current: current.cloned(),
+                breadcrumbs,
             },
             nav_meta_path.clone(),
         ))
@@ -631,7 +840,9 @@ pub(crate) fn combine_pandoc_native(results: Vec<String>) -> String {
         "[\n{}\n]",
         results
             .iter()
-            .map(|r| r.trim()[1..r.trim().len() - 1].trim())
+            .map(|r| r.trim())
+            .filter(|r| r.len() >= 2)
+            .map(|r| r[1..r.len() - 1].trim())
             .filter(|r| !r.is_empty())
             .collect::<Vec<&str>>()
             .join(",\n")
@@ -652,11 +863,18 @@ pub(crate) fn write_output(
     Ok(())
 }
 
+/// The output of an external command.
+pub(crate) struct CommandOutput {
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+    pub(crate) exit_code: Option<i32>,
+}
+
 pub(crate) fn run_with_logging(
     mut command: Command,
     command_name: &str,
     supress_verbose: bool,
-) -> Result<String> {
+) -> Result<CommandOutput> {
     debug!(
         "Executing command: {} (suppress_verbose={})",
         command_name, supress_verbose
@@ -705,15 +923,21 @@ pub(crate) fn run_with_logging(
 
     let stderr_thread = thread::spawn(move || {
         let mut buffer = String::new();
+        let mut content = String::new();
+
         while let std::io::Result::Ok(bytes_read) = stderr_reader.read_line(&mut buffer) {
             if bytes_read == 0 {
                 break;
             }
 
+            content.push_str(&buffer);
+
             error!("{}", buffer);
 
             buffer.clear();
         }
+
+        content
     });
 
     let status = out.wait()?;
@@ -721,30 +945,52 @@ pub(crate) fn run_with_logging(
     let std::result::Result::Ok(stdout_str) = stdout_thread.join() else {
         return Err(eyre!("Error reading stdout thread"));
     };
-    let std::result::Result::Ok(_stderr_str) = stderr_thread.join() else {
+    let std::result::Result::Ok(stderr_str) = stderr_thread.join() else {
         return Err(eyre!("Error reading stderr thread"));
     };
 
-    if !status.success() {
-        if command_name != "xelatex" {
-            return Err(eyre!(
-                "Command {} failed with status code {}.",
-                command_name,
-                status.code().unwrap()
-            ));
+    Ok(CommandOutput {
+        stdout: stdout_str,
+        stderr: stderr_str,
+        exit_code: status.code(),
+    })
+}
+
+/// The LaTeX engines, which exit with a non-zero status code on warnings as well. latexmk is not
+/// among them, as it only fails on errors.
+const LATEX_ENGINE_COMMANDS: [&str; 3] = ["xelatex", "lualatex", "pdflatex"];
+
+/// Checks the exit code of a command, returning an error if it failed.
+///
+/// LaTeX engines exit with a non-zero status code on warnings as well, so their failures are ignored.
+/// Genuine LaTeX errors are detected by parsing the LaTeX log instead. bibtex exits with status
+/// code 1 on warnings, which is not treated as a failure either.
+pub(crate) fn check_exit_code(command_name: &str, exit_code: Option<i32>) -> Result<()> {
+    let exit_code = match (command_name, exit_code) {
+        (_, Some(0)) => {
+            debug!("Command {} completed successfully.", command_name);
+            return Ok(());
+        }
+        ("bibtex", Some(1)) => {
+            debug!("{} completed with warnings.", command_name);
+            return Ok(());
         }
+        (_, Some(exit_code)) => exit_code.to_string(),
+        (_, None) => "unknown".to_string(),
+    };
 
-        debug!(
-            "{} failed with status code {}.",
+    if !LATEX_ENGINE_COMMANDS.contains(&command_name) {
+        return Err(eyre!(
+            "Command {} failed with status code {}.",
             command_name,
-            status.code().unwrap()
-        );
-        debug!(
-            "Note: For xelatex, this is expected if there are warnings. These are ignored, but genuine errors may be present."
-        );
-    } else {
-        debug!("Command {} completed successfully.", command_name);
+            exit_code
+        ));
     }
 
-    Ok(stdout_str)
+    debug!("{} failed with status code {}.", command_name, exit_code);
+    debug!(
+        "Note: For LaTeX engines, this is expected if there are warnings. Genuine errors are detected in the LaTeX log."
+    );
+
+    Ok(())
 }
@@ -0,0 +1,50 @@
+use color_eyre::eyre::Result;
+use log::debug;
+use std::{fs, path::Path, process::Command};
+
+use crate::converters::conversion_meta::ConversionMeta;
+
+/// The bundled lua filter keeping or removing `.only` and `.exclude` divs and spans.
+pub(crate) const CONDITIONAL_CONTENT_FILTER: &str =
+    include_str!("../resources/filters/conditional_content.lua");
+
+const CONDITIONAL_CONTENT_FILTER_FILE_NAME: &str = ".tiefdown_conditional_content.lua";
+
+/// Writes the conditional content filter to the compiled directory.
+///
+/// Called once per conversion task, before the files are converted in parallel.
+///
+/// # Arguments
+///
+/// * `compiled_directory_path` - The directory pandoc runs in.
+pub(crate) fn write_conditional_content_filter(compiled_directory_path: &Path) -> Result<()> {
+    let filter_path = compiled_directory_path.join(CONDITIONAL_CONTENT_FILTER_FILE_NAME);
+
+    if fs::read_to_string(&filter_path).ok().as_deref() != Some(CONDITIONAL_CONTENT_FILTER) {
+        debug!("Writing conditional content filter.");
+        fs::write(&filter_path, CONDITIONAL_CONTENT_FILTER)?;
+    }
+
+    Ok(())
+}
+
+/// Adds the conditional content filter to the pandoc command, along with the metadata of the
+/// conversion. The filter has to be written with `write_conditional_content_filter` first.
+///
+/// # Arguments
+///
+/// * `conversion_meta` - The conversion the pandoc command runs for.
+/// * `pandoc` - The pandoc command.
+pub(crate) fn add_conditional_content_filter(
+    conversion_meta: &ConversionMeta,
+    pandoc: &mut Command,
+) {
+    debug!("Adding conditional content filter.");
+    pandoc
+        .arg("--lua-filter")
+        .arg(CONDITIONAL_CONTENT_FILTER_FILE_NAME);
+
+    for (key, value) in conversion_meta.get_metadata_fields() {
+        pandoc.arg("-M").arg(format!("{}={}", key, value));
+    }
+}
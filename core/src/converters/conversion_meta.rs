@@ -0,0 +1,35 @@
+use serde::Serialize;
+
+use crate::{bibliography::Bibliography, template_type::TemplateType};
+
+/// The conversion a converter runs for.
+///
+/// The markdown project, template, template type and profile are passed to pandoc as the
+/// metadata fields `tiefdown-markdown-project`, `tiefdown-template`, `tiefdown-template-type` and
+/// `tiefdown-profile`, which the conditional content filter (and any other lua filter) can read.
+/// The bibliography is passed to pandoc as citation arguments.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct ConversionMeta {
+    pub(crate) markdown_project: String,
+    pub(crate) template: String,
+    pub(crate) template_type: TemplateType,
+    pub(crate) profile: Option<String>,
+    pub(crate) bibliography: Option<Bibliography>,
+}
+
+impl ConversionMeta {
+    /// Returns the pandoc metadata fields of the conversion.
+    pub(crate) fn get_metadata_fields(&self) -> Vec<(&'static str, String)> {
+        let mut metadata_fields = vec![
+            ("tiefdown-markdown-project", self.markdown_project.clone()),
+            ("tiefdown-template", self.template.clone()),
+            ("tiefdown-template-type", self.template_type.to_string()),
+        ];
+
+        if let Some(profile) = &self.profile {
+            metadata_fields.push(("tiefdown-profile", profile.clone()));
+        }
+
+        metadata_fields
+    }
+}
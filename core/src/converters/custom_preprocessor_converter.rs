@@ -5,11 +5,14 @@ use log::debug;
 use toml::Table;
 
 use crate::{
+    build_cache::BuildCache,
+    conversion_recorder::ConversionRecorder,
     converters::common::{
         generate_meta_file, retrieve_combined_output, retrieve_output_extension,
         retrieve_preprocessors, run_preprocessors_on_inputs, write_combined_output,
         write_multi_file_outputs,
     },
+    converters::conversion_meta::ConversionMeta,
     file_retrieval::get_sorted_files,
     injections::retrieve_injections,
     manifest_model::{Injection, MetadataSettings, Processors, Template},
@@ -23,10 +26,13 @@ pub(crate) fn convert_custom_preprocessors(
     compiled_directory_path: &Path,
     conversion_input_dir: &Path,
     template: &Template,
+    conversion_meta: &ConversionMeta,
     metadata_fields: &Table,
     metadata_settings: &MetadataSettings,
     custom_processors: &Processors,
     injections: &Vec<Injection>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<PathBuf> {
     debug!(
         "Starting CustomPandoc conversion for template '{}'...",
@@ -78,6 +84,10 @@ pub(crate) fn convert_custom_preprocessors(
 
     debug!("Found {} input files.", input_files.len());
 
+    recorder.record_preprocessors(&preprocessors);
+    recorder.record_injections(&injections);
+    recorder.record_input_files(&input_files);
+
     debug!("Retrieving navigation metadata.");
 
     let nav_meta_data = if let Some(meta_gen) = &template.meta_gen
@@ -94,6 +104,8 @@ pub(crate) fn convert_custom_preprocessors(
             compiled_directory_path,
             conversion_input_dir,
             &output_extension,
+            meta_gen.nav_title_source.unwrap_or_default(),
+            template.multi_file_output.unwrap_or(false),
         )?;
         Some((
             nav_meta.clone(),
@@ -120,6 +132,7 @@ pub(crate) fn convert_custom_preprocessors(
     debug!("Running preprocessors on inputs...");
     let results = run_preprocessors_on_inputs(
         template,
+        conversion_meta,
         compiled_directory_path,
         metadata_fields,
         &metadata_file,
@@ -127,6 +140,8 @@ pub(crate) fn convert_custom_preprocessors(
         &nav_meta_data,
         &preprocessors,
         &input_files,
+        build_cache,
+        recorder,
     )?;
 
     let combined_output = retrieve_combined_output(template, &None)?;
@@ -136,6 +151,7 @@ pub(crate) fn convert_custom_preprocessors(
 
         write_multi_file_outputs(
             template,
+            conversion_meta,
             compiled_directory_path,
             conversion_input_dir,
             &output_path,
@@ -148,6 +164,8 @@ pub(crate) fn convert_custom_preprocessors(
             &nav_meta_data,
             &preprocessors,
             &results,
+            build_cache,
+            recorder,
         )?;
     } else if let Some(combined_output) = combined_output {
         debug!("Combined output file: {}", combined_output.display());
@@ -1,9 +1,13 @@
 use crate::{
+    bibliography::add_bibliography_args,
+    build_cache::BuildCache,
+    conversion_recorder::ConversionRecorder,
     converters::common::{
         add_lua_filters, combine_pandoc_native, generate_meta_file, merge_preprocessors,
         preprocess_cli_args, retrieve_combined_output, retrieve_preprocessors,
-        run_preprocessors_on_inputs, run_with_logging, write_output,
+        run_preprocessors_on_inputs, write_output,
     },
+    converters::conversion_meta::ConversionMeta,
     file_retrieval::get_sorted_files,
     injections::retrieve_injections,
     manifest_model::{
@@ -25,10 +29,13 @@ pub(crate) fn convert_custom_processor(
     compiled_directory_path: &Path,
     conversion_input_dir: &Path,
     template: &Template,
+    conversion_meta: &ConversionMeta,
     metadata_fields: &Table,
     metadata_settings: &MetadataSettings,
     custom_processors: &Processors,
     injections: &Vec<Injection>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<PathBuf> {
     debug!(
         "Starting Processor conversion for template '{}'...",
@@ -86,6 +93,10 @@ pub(crate) fn convert_custom_processor(
     )?;
     debug!("Found {} input files.", input_files.len());
 
+    recorder.record_preprocessors(&preprocessors);
+    recorder.record_injections(&injections);
+    recorder.record_input_files(&input_files);
+
     debug!("Retrieving navigation metadata.");
 
     let nav_meta_data = if let Some(nav_meta_gen) = &template.meta_gen
@@ -97,6 +108,8 @@ pub(crate) fn convert_custom_processor(
             compiled_directory_path,
             conversion_input_dir,
             &None,
+            nav_meta_gen.nav_title_source.unwrap_or_default(),
+            false,
         )?;
         Some((
             nav_meta.clone(),
@@ -125,6 +138,7 @@ pub(crate) fn convert_custom_processor(
     debug!("Running preprocessors on inputs...");
     let results = run_preprocessors_on_inputs(
         template,
+        conversion_meta,
         compiled_directory_path,
         metadata_fields,
         &metadata_file,
@@ -132,6 +146,8 @@ pub(crate) fn convert_custom_processor(
         &nav_meta_data,
         &preprocessors,
         &input_files,
+        build_cache,
+        recorder,
     )?;
 
     let pandoc_native = combine_pandoc_native(results);
@@ -157,17 +173,39 @@ pub(crate) fn convert_custom_processor(
 
     let mut pandoc_command = Command::new("pandoc");
 
-    add_lua_filters(template, compiled_directory_path, &mut pandoc_command)?;
+    add_lua_filters(
+        template,
+        conversion_meta,
+        compiled_directory_path,
+        &mut pandoc_command,
+    )?;
+
+    add_bibliography_args(&conversion_meta.bibliography, &mut pandoc_command);
 
     pandoc_command
         .current_dir(compiled_directory_path)
         .args(vec!["-f", "native"])
         .arg("-o")
         .arg(&output_path)
-        .args(processor_args)
+        .args(&processor_args)
         .arg(&combined_output);
 
-    run_with_logging(pandoc_command, "pandoc", false)?;
+    let mut processor_inputs = vec![combined_output.as_path()];
+    if let Some((_, nav_meta_file)) = &nav_meta_data {
+        processor_inputs.push(nav_meta_file);
+    }
+
+    build_cache.run_processor(
+        compiled_directory_path,
+        &processor_inputs,
+        &processor_args,
+        &compiled_directory_path.join(&output_path),
+        || {
+            recorder.run_command(pandoc_command, "pandoc", false)?;
+
+            Ok(())
+        },
+    )?;
 
     let output_path = compiled_directory_path.join(&output_path);
 
@@ -1,4 +1,5 @@
 use std::{
+    fs,
     path::{Path, PathBuf},
     process::Command,
 };
@@ -8,11 +9,15 @@ use log::debug;
 use toml::Table;
 
 use crate::{
+    bibliography::add_bibliography_args,
+    build_cache::BuildCache,
+    conversion_recorder::ConversionRecorder,
     converters::common::{
         add_lua_filters, combine_pandoc_native, generate_meta_file, merge_preprocessors,
-        preprocess_cli_args, retrieve_combined_output, retrieve_preprocessors,
-        run_preprocessors_on_inputs, run_with_logging, write_output,
+        normalize_metadata, preprocess_cli_args, retrieve_combined_output, retrieve_preprocessors,
+        run_preprocessors_on_inputs, write_output,
     },
+    converters::conversion_meta::ConversionMeta,
     file_retrieval::{get_relative_path_from_compiled_dir, get_sorted_files},
     injections::retrieve_injections,
     manifest_model::{
@@ -28,10 +33,13 @@ pub(crate) fn convert_epub(
     compiled_directory_path: &Path,
     conversion_input_dir: &Path,
     template: &Template,
+    conversion_meta: &ConversionMeta,
     metadata_fields: &Table,
     metadata_settings: &MetadataSettings,
     custom_processors: &Processors,
     injections: &Vec<Injection>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<PathBuf> {
     debug!("Starting EPUB conversion process.");
 
@@ -94,6 +102,10 @@ pub(crate) fn convert_epub(
     )?;
     debug!("Found {} input files.", input_files.len());
 
+    recorder.record_preprocessors(&preprocessors);
+    recorder.record_injections(&injections);
+    recorder.record_input_files(&input_files);
+
     debug!("Retrieving navigation metadata.");
 
     let nav_meta_data = if let Some(nav_meta_gen) = &template.meta_gen
@@ -105,6 +117,8 @@ pub(crate) fn convert_epub(
             compiled_directory_path,
             conversion_input_dir,
             &None,
+            nav_meta_gen.nav_title_source.unwrap_or_default(),
+            false,
         )?;
         Some((
             nav_meta.clone(),
@@ -133,6 +147,7 @@ pub(crate) fn convert_epub(
     debug!("Running preprocessors on inputs...");
     let results = run_preprocessors_on_inputs(
         template,
+        conversion_meta,
         compiled_directory_path,
         metadata_fields,
         &metadata_file,
@@ -140,6 +155,8 @@ pub(crate) fn convert_epub(
         &nav_meta_data,
         &preprocessors,
         &input_files,
+        build_cache,
+        recorder,
     )?;
 
     let pandoc_native = combine_pandoc_native(results);
@@ -171,7 +188,12 @@ pub(crate) fn convert_epub(
         .arg("-o")
         .arg(&output_path);
 
-    add_meta_args(metadata_fields, &mut pandoc)?;
+    let metadata_file = add_meta_args(
+        compiled_directory_path,
+        template,
+        metadata_fields,
+        &mut pandoc,
+    )?;
     debug!("Added metadata fields to pandoc command.");
 
     add_css_files(
@@ -188,12 +210,37 @@ pub(crate) fn convert_epub(
     )?;
     debug!("Added embedded fonts if present.");
 
-    add_lua_filters(template, compiled_directory_path, &mut pandoc)?;
+    add_lua_filters(
+        template,
+        conversion_meta,
+        compiled_directory_path,
+        &mut pandoc,
+    )?;
+
+    add_bibliography_args(&conversion_meta.bibliography, &mut pandoc);
     debug!("Added lua filters if configured.");
 
     pandoc.arg(&combined_output);
 
-    run_with_logging(pandoc, "pandoc", false)?;
+    let mut processor_inputs = vec![combined_output.as_path()];
+    if let Some(metadata_file) = &metadata_file {
+        processor_inputs.push(metadata_file);
+    }
+    if let Some((_, nav_meta_file)) = &nav_meta_data {
+        processor_inputs.push(nav_meta_file);
+    }
+
+    build_cache.run_processor(
+        compiled_directory_path,
+        &processor_inputs,
+        &processor_args,
+        &compiled_directory_path.join(&output_path),
+        || {
+            recorder.run_command(pandoc, "pandoc", false)?;
+
+            Ok(())
+        },
+    )?;
 
     let output_path = compiled_directory_path.join(output_path);
     debug!("EPUB result path: {}", output_path.display());
@@ -201,19 +248,40 @@ pub(crate) fn convert_epub(
     Ok(output_path)
 }
 
-fn add_meta_args(metadata_fields: &Table, pandoc: &mut Command) -> Result<()> {
+/// Adds the metadata to the pandoc command.
+///
+/// Strings are passed as metadata arguments. Other values are written to a YAML metadata file,
+/// whose path relative to the compiled directory is returned.
+fn add_meta_args(
+    compiled_directory_path: &Path,
+    template: &Template,
+    metadata_fields: &Table,
+    pandoc: &mut Command,
+) -> Result<Option<PathBuf>> {
+    let mut structured_metadata = Table::new();
+
     for (key, value) in metadata_fields {
         if let Some(value) = value.as_str() {
             pandoc.arg("-M").arg(format!("{}:{}", key, value));
         } else {
-            return Err(eyre!(
-                "Metadata field {} is not a string, and is not supported by TiefDownConverter.",
-                key
-            ));
+            structured_metadata.insert(key.clone(), value.clone());
         }
     }
 
-    Ok(())
+    if structured_metadata.is_empty() {
+        return Ok(None);
+    }
+
+    let metadata_file = PathBuf::from(format!(".{}_metadata.yml", template.name));
+    fs::write(
+        compiled_directory_path.join(&metadata_file),
+        serde_yaml::to_string(&normalize_metadata(&structured_metadata))?,
+    )?;
+    debug!("Structured metadata written to {}", metadata_file.display());
+
+    pandoc.arg("--metadata-file").arg(&metadata_file);
+
+    Ok(Some(metadata_file))
 }
 
 fn add_css_files(
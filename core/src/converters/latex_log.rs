@@ -0,0 +1,386 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{Result, eyre};
+use log::{debug, warn};
+use regex::Regex;
+
+use crate::{
+    build_report::{WarningKind, WarningReport},
+    conversion_recorder::ConversionRecorder,
+};
+
+/// TeX wraps log lines at this length (`max_print_line`).
+const MAX_PRINT_LINE: usize = 79;
+
+/// Messages LaTeX and common packages log when the document has to be compiled again.
+const RERUN_MARKERS: [&str; 6] = [
+    "Rerun to get cross-references right",
+    "Rerun to get citations correct",
+    "Rerun to get outlines right",
+    "Label(s) may have changed. Rerun",
+    "Please rerun LaTeX",
+    "Rerun LaTeX",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LatexIssueKind {
+    FatalError,
+    UndefinedReference,
+    OverfullBox,
+    MissingFont,
+}
+
+/// An error or warning found in a LaTeX log.
+///
+/// `file` is the file TeX was reading when the issue occurred, `line` the line in that file and
+/// `source_line` the offending TeX line, if known.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LatexIssue {
+    pub(crate) kind: LatexIssueKind,
+    pub(crate) message: String,
+    pub(crate) file: Option<PathBuf>,
+    pub(crate) line: Option<usize>,
+    pub(crate) source_line: Option<String>,
+}
+
+impl Display for LatexIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, " ({}, line {})", file.display(), line)?,
+            (None, Some(line)) => write!(f, " (line {})", line)?,
+            (Some(file), None) => write!(f, " ({})", file.display())?,
+            (None, None) => {}
+        }
+
+        if let Some(source_line) = &self.source_line {
+            write!(f, ": {}", source_line)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the log of a LaTeX run and checks it for errors.
+///
+/// Fatal errors fail the conversion. Undefined references, overfull boxes and missing fonts are
+/// logged and recorded as warnings. Issues in the combined output are mapped to the offending
+/// line of that file.
+///
+/// # Arguments
+///
+/// * `compiled_directory_path` - The directory LaTeX ran in.
+/// * `log_path` - The log file, relative to the compiled directory.
+/// * `combined_output` - The combined output file, relative to the compiled directory.
+/// * `command_name` - The name of the LaTeX command, used as the source of the warnings.
+/// * `recorder` - The recorder to record the warnings with.
+pub(crate) fn check_latex_log(
+    compiled_directory_path: &Path,
+    log_path: &Path,
+    combined_output: &Path,
+    command_name: &str,
+    recorder: &ConversionRecorder,
+) -> Result<()> {
+    let full_log_path = compiled_directory_path.join(log_path);
+
+    let Ok(log_content) = fs::read(&full_log_path) else {
+        debug!(
+            "No LaTeX log found at '{}', skipping log check.",
+            full_log_path.display()
+        );
+        return Ok(());
+    };
+    let log_content = String::from_utf8_lossy(&log_content);
+
+    let combined_output_lines = fs::read_to_string(compiled_directory_path.join(combined_output))
+        .map(|content| content.lines().map(str::to_string).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let issues = parse_latex_log(&log_content)?
+        .into_iter()
+        .map(|issue| map_to_combined_output(issue, combined_output, &combined_output_lines))
+        .collect::<Vec<_>>();
+
+    debug!(
+        "Found {} issues in LaTeX log '{}'.",
+        issues.len(),
+        log_path.display()
+    );
+
+    let mut errors = vec![];
+
+    for issue in issues {
+        let kind = match issue.kind {
+            LatexIssueKind::FatalError => {
+                errors.push(issue.to_string());
+                continue;
+            }
+            LatexIssueKind::UndefinedReference => WarningKind::UndefinedReference,
+            LatexIssueKind::OverfullBox => WarningKind::OverfullBox,
+            LatexIssueKind::MissingFont => WarningKind::MissingFont,
+        };
+
+        warn!("{}", issue);
+
+        recorder.record_warning(WarningReport {
+            source: command_name.to_string(),
+            kind,
+            message: issue.to_string(),
+            file: issue.file,
+            line: issue.line,
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(eyre!(
+            "LaTeX compilation failed with {} error(s):\n{}",
+            errors.len(),
+            errors.join("\n")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Parses a LaTeX log into its errors and warnings, in order of appearance.
+///
+/// Duplicate issues (e.g. from a second LaTeX pass writing to the same log) are reported once.
+pub(crate) fn parse_latex_log(log_content: &str) -> Result<Vec<LatexIssue>> {
+    let reference_regex = Regex::new(
+        r"Warning: (Reference|Citation) `(.*?)' on page \S+ undefined on input line (\d+)",
+    )?;
+    let overfull_regex =
+        Regex::new(r"^(Overfull \\[hv]box \(.*?\) .*?)(?: at lines? (\d+)(?:--\d+)?)?$")?;
+    let font_shape_regex = Regex::new(r"LaTeX Font Warning: (Font shape `.*?' undefined)")?;
+    let missing_character_regex = Regex::new(r"^Missing character: (.*)$")?;
+    let error_line_regex = Regex::new(r"^l\.(\d+) ?(.*)$")?;
+    let input_line_regex = Regex::new(r"on input line (\d+)")?;
+
+    let lines = unwrap_log_lines(log_content);
+
+    let mut issues: Vec<LatexIssue> = vec![];
+    let mut file_stack: Vec<Option<PathBuf>> = vec![];
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = &lines[index];
+        let current_file = file_stack.iter().rev().flatten().next().cloned();
+
+        if let Some(message) = line.strip_prefix("! ") {
+            let mut message = message.trim().to_string();
+            let mut error_line = None;
+            let mut source_line = None;
+
+            let mut next_index = index + 1;
+
+            for (context_index, context) in lines.iter().enumerate().skip(index + 1).take(19) {
+                if let Some(captures) = error_line_regex.captures(context) {
+                    error_line = captures[1].parse::<usize>().ok();
+                    let source = captures[2].trim();
+                    if !source.is_empty() {
+                        source_line = Some(source.to_string());
+                    }
+                    // The line after the error line holds the rest of the offending line.
+                    next_index = context_index + 2;
+                    break;
+                }
+
+                if context.starts_with("! ") {
+                    next_index = context_index;
+                    break;
+                }
+
+                if index + 1 == context_index
+                    && !context.trim().is_empty()
+                    && !context.starts_with('<')
+                {
+                    message.push(' ');
+                    message.push_str(context.trim());
+                }
+            }
+
+            push_issue(
+                &mut issues,
+                LatexIssue {
+                    kind: LatexIssueKind::FatalError,
+                    message,
+                    file: current_file,
+                    line: error_line,
+                    source_line,
+                },
+            );
+
+            index = next_index;
+            continue;
+        }
+
+        if let Some(captures) = reference_regex.captures(line) {
+            push_issue(
+                &mut issues,
+                LatexIssue {
+                    kind: LatexIssueKind::UndefinedReference,
+                    message: format!("{} `{}' undefined.", &captures[1], &captures[2]),
+                    file: current_file.clone(),
+                    line: captures[3].parse::<usize>().ok(),
+                    source_line: None,
+                },
+            );
+        } else if let Some(captures) = overfull_regex.captures(line) {
+            push_issue(
+                &mut issues,
+                LatexIssue {
+                    kind: LatexIssueKind::OverfullBox,
+                    message: captures[1].trim().to_string(),
+                    file: current_file.clone(),
+                    line: captures
+                        .get(2)
+                        .and_then(|l| l.as_str().parse::<usize>().ok()),
+                    source_line: None,
+                },
+            );
+        } else if let Some(captures) = font_shape_regex.captures(line) {
+            let mut message = captures[1].to_string();
+            let mut line_number = None;
+
+            // The substitution is reported on the following line.
+            if let Some(next_line) = lines.get(index + 1)
+                && next_line.starts_with("(Font)")
+            {
+                message.push_str(&format!(
+                    ", {}",
+                    next_line.trim_start_matches("(Font)").trim()
+                ));
+                line_number = input_line_regex
+                    .captures(next_line)
+                    .and_then(|c| c[1].parse::<usize>().ok());
+            }
+
+            push_issue(
+                &mut issues,
+                LatexIssue {
+                    kind: LatexIssueKind::MissingFont,
+                    message,
+                    file: current_file.clone(),
+                    line: line_number,
+                    source_line: None,
+                },
+            );
+        } else if let Some(captures) = missing_character_regex.captures(line) {
+            push_issue(
+                &mut issues,
+                LatexIssue {
+                    kind: LatexIssueKind::MissingFont,
+                    message: format!("Missing character: {}", &captures[1]),
+                    file: current_file.clone(),
+                    line: None,
+                    source_line: None,
+                },
+            );
+        }
+
+        update_file_stack(&mut file_stack, line);
+
+        index += 1;
+    }
+
+    Ok(issues)
+}
+
+/// Checks whether a LaTeX log asks for another LaTeX pass, e.g. because cross-references or
+/// citations changed.
+pub(crate) fn needs_rerun(log_content: &str) -> bool {
+    unwrap_log_lines(log_content)
+        .iter()
+        .any(|line| RERUN_MARKERS.iter().any(|marker| line.contains(marker)))
+}
+
+fn push_issue(issues: &mut Vec<LatexIssue>, issue: LatexIssue) {
+    if !issues.contains(&issue) {
+        issues.push(issue);
+    }
+}
+
+/// Joins log lines TeX wrapped at `MAX_PRINT_LINE` characters.
+fn unwrap_log_lines(log_content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut continues_previous = false;
+
+    for line in log_content.lines() {
+        if continues_previous && let Some(previous) = lines.last_mut() {
+            previous.push_str(line);
+        } else {
+            lines.push(line.to_string());
+        }
+
+        continues_previous = line.chars().count() == MAX_PRINT_LINE;
+    }
+
+    lines
+}
+
+/// Tracks the files TeX opens and closes, which it logs as `(file` and `)`.
+///
+/// Parentheses not followed by a file name are tracked as well, so that balanced parentheses in
+/// messages do not close files.
+fn update_file_stack(file_stack: &mut Vec<Option<PathBuf>>, line: &str) {
+    let mut characters = line.char_indices().peekable();
+
+    while let Some((position, character)) = characters.next() {
+        match character {
+            '(' => {
+                let rest = &line[position + 1..];
+                let file_name: String = rest
+                    .chars()
+                    .take_while(|c| !c.is_whitespace() && *c != '(' && *c != ')')
+                    .collect();
+
+                let is_file = (file_name.starts_with("./")
+                    || file_name.starts_with('/')
+                    || file_name.contains('.'))
+                    && !file_name.ends_with('.')
+                    && file_name.chars().any(|c| c.is_alphabetic());
+
+                if is_file {
+                    file_stack.push(Some(PathBuf::from(
+                        file_name.trim_start_matches("./").to_string(),
+                    )));
+                    for _ in 0..file_name.chars().count() {
+                        characters.next();
+                    }
+                } else {
+                    file_stack.push(None);
+                }
+            }
+            ')' => {
+                file_stack.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn map_to_combined_output(
+    mut issue: LatexIssue,
+    combined_output: &Path,
+    combined_output_lines: &[String],
+) -> LatexIssue {
+    let is_combined_output = issue
+        .file
+        .as_ref()
+        .is_some_and(|file| file == combined_output || file.ends_with(combined_output));
+
+    if is_combined_output
+        && let Some(line) = issue.line
+        && let Some(source_line) = combined_output_lines.get(line.wrapping_sub(1))
+    {
+        issue.file = Some(combined_output.to_path_buf());
+        issue.source_line = Some(source_line.trim().to_string());
+    }
+
+    issue
+}
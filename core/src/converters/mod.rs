@@ -1,6 +1,9 @@
 pub(crate) mod common;
+pub(crate) mod conditional_content;
+pub(crate) mod conversion_meta;
 pub(crate) mod custom_preprocessor_converter;
 pub(crate) mod custom_processor_converter;
 pub(crate) mod epub_converter;
+pub(crate) mod latex_log;
 pub(crate) mod tex_converter;
 pub(crate) mod typst_converter;
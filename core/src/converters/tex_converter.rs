@@ -6,33 +6,48 @@ use std::{
 };
 
 use color_eyre::eyre::{Result, eyre};
-use log::debug;
+use log::{debug, warn};
 use toml::Table;
 
 use crate::{
+    build_cache::BuildCache,
+    build_report::{WarningKind, WarningReport},
+    conversion_recorder::ConversionRecorder,
     converters::common::{
-        generate_meta_file, merge_preprocessors, retrieve_combined_output, retrieve_preprocessors,
-        run_preprocessors_on_inputs, run_with_logging, write_combined_output,
+        flatten_metadata, generate_meta_file, merge_preprocessors, retrieve_combined_output,
+        retrieve_preprocessors, run_preprocessors_on_inputs, write_combined_output,
     },
+    converters::conversion_meta::ConversionMeta,
+    converters::latex_log::{check_latex_log, needs_rerun},
     file_retrieval::get_sorted_files,
     injections::retrieve_injections,
     manifest_model::{
-        DEFAULT_TEX_PREPROCESSORS, Injection, MetadataSettings, Processors, Template,
+        CitationMode, DEFAULT_TEX_PREPROCESSORS, Injection, LatexEngine, LatexSettings, LatexTool,
+        MetadataSettings, Processors, Template,
     },
     meta_generation_feature::MetaGenerationFeature,
     nav_meta_generation::{generate_nav_meta_file, retrieve_nav_meta},
     template_management::{get_output_path, get_template_path},
 };
 
+/// The maximum number of LaTeX passes if the template does not configure one.
+const DEFAULT_MAX_LATEX_PASSES: u32 = 5;
+
+/// The files LaTeX writes during a pass and reads back on the next one.
+const LATEX_AUXILIARY_EXTENSIONS: [&str; 6] = ["toc", "lof", "lot", "out", "nav", "snm"];
+
 pub(crate) fn convert_latex(
     project_directory_path: &Path,
     compiled_directory_path: &Path,
     conversion_input_dir: &Path,
     template: &Template,
+    conversion_meta: &ConversionMeta,
     metadata_fields: &Table,
     metadata_settings: &MetadataSettings,
     custom_processors: &Processors,
     injections: &Vec<Injection>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<PathBuf> {
     debug!(
         "Starting LaTeX conversion for template '{}'...",
@@ -92,6 +107,10 @@ pub(crate) fn convert_latex(
     )?;
     debug!("Found {} input files.", input_files.len());
 
+    recorder.record_preprocessors(&preprocessors);
+    recorder.record_injections(&injections);
+    recorder.record_input_files(&input_files);
+
     debug!("Retrieving navigation metadata.");
 
     let nav_meta_data = if let Some(nav_meta_gen) = &template.meta_gen
@@ -103,6 +122,8 @@ pub(crate) fn convert_latex(
             compiled_directory_path,
             conversion_input_dir,
             &None,
+            nav_meta_gen.nav_title_source.unwrap_or_default(),
+            false,
         )?;
         Some((
             nav_meta.clone(),
@@ -131,6 +152,7 @@ pub(crate) fn convert_latex(
     debug!("Running preprocessors on inputs...");
     let results = run_preprocessors_on_inputs(
         template,
+        conversion_meta,
         compiled_directory_path,
         metadata_fields,
         &metadata_file,
@@ -138,6 +160,8 @@ pub(crate) fn convert_latex(
         &nav_meta_data,
         &preprocessors,
         &input_files,
+        build_cache,
+        recorder,
     )?;
 
     write_combined_output(compiled_directory_path, &combined_output, &results)?;
@@ -167,26 +191,115 @@ pub(crate) fn convert_latex(
         }
     }
 
-    debug!("Compiling LaTeX (first pass)...");
-    compile_latex(compiled_directory_path, &template_path, &processor_args)?;
-    debug!("Compiling LaTeX (second pass)...");
-    compile_latex(compiled_directory_path, &template_path, &processor_args)?;
+    let engine = template
+        .latex
+        .as_ref()
+        .and_then(|s| s.engine)
+        .unwrap_or_default();
+    let latexmk = template
+        .latex
+        .as_ref()
+        .and_then(|s| s.latexmk)
+        .unwrap_or(false);
+
+    if latexmk
+        && template
+            .latex
+            .as_ref()
+            .is_some_and(|s| s.max_passes.is_some() || s.tools.is_some())
+    {
+        warn!(
+            "Template '{}' uses latexmk, which decides on the LaTeX passes and tools itself. The configured passes and tools are ignored.",
+            template.name
+        );
+    }
 
-    let template_path = compiled_directory_path.join(template_path.with_extension("pdf"));
-    if template_path.exists() && template_path.as_os_str() != output_path.as_os_str() {
-        debug!(
-            "Copying compiled PDF from '{}' to '{}'",
-            template_path.display(),
-            output_path.display()
+    if conversion_meta
+        .bibliography
+        .as_ref()
+        .is_some_and(|b| b.mode == CitationMode::Biblatex)
+        && !latexmk
+        && !template
+            .latex
+            .as_ref()
+            .and_then(|s| s.tools.as_ref())
+            .is_some_and(|tools| {
+                tools
+                    .iter()
+                    .any(|tool| matches!(tool, LatexTool::Biber | LatexTool::Bibtex))
+            })
+    {
+        warn!(
+            "Template '{}' uses the Biblatex citation mode, but neither latexmk nor the Biber or Bibtex tool is configured. The bibliography will not be built.",
+            template.name
         );
-        fs::copy(&template_path, &output_path)?;
     }
 
+    build_cache.run_processor(
+        compiled_directory_path,
+        &[&combined_output, Path::new("metadata.tex")],
+        &processor_args,
+        &output_path,
+        || {
+            let compile_result = if latexmk {
+                compile_latex(
+                    compiled_directory_path,
+                    &template_path,
+                    &processor_args,
+                    engine,
+                    true,
+                    recorder,
+                )
+            } else {
+                compile_latex_until_stable(
+                    compiled_directory_path,
+                    &template_path,
+                    &processor_args,
+                    engine,
+                    template.latex.as_ref(),
+                    recorder,
+                )
+            };
+
+            if recorder.is_dry_run() {
+                return compile_result;
+            }
+
+            // Errors in the LaTeX log explain a failed compilation best, so they are reported first.
+            check_latex_log(
+                compiled_directory_path,
+                &template_path.with_extension("log"),
+                &combined_output,
+                get_latex_engine_command(engine),
+                recorder,
+            )?;
+            compile_result?;
+
+            let template_path = compiled_directory_path.join(template_path.with_extension("pdf"));
+            if !template_path.exists() {
+                return Err(eyre!(
+                    "LaTeX did not produce '{}'. Check the LaTeX log for details.",
+                    template_path.display()
+                ));
+            }
+            if template_path.as_os_str() != output_path.as_os_str() {
+                debug!(
+                    "Copying compiled PDF from '{}' to '{}'",
+                    template_path.display(),
+                    output_path.display()
+                );
+                fs::copy(&template_path, &output_path)?;
+            }
+
+            Ok(())
+        },
+    )?;
+
     debug!("LaTeX result path: {}", output_path.display());
     Ok(output_path)
 }
 
-fn generate_tex_metadata(
+pub(crate) fn generate_tex_metadata(
     compiled_directory_path: &Path,
     metadata_fields: &Table,
     metadata_settings: &MetadataSettings,
@@ -214,19 +327,20 @@ fn generate_tex_metadata(
 
     metadata_file_content.push_str("\n\n");
 
-    for (key, value) in metadata_fields {
-        if let Some(value) = value.as_str() {
-            metadata_file_content.push_str(&format!(
-                r"\expandafter\def\csname {}@{}\endcsname{{{}}}",
-                prefix, key, value
-            ));
-            metadata_file_content.push('\n');
+    let flattened_metadata = flatten_metadata(metadata_fields, &|path, value| {
+        if metadata_settings.is_raw_field(path) {
+            value.to_string()
         } else {
-            return Err(eyre!(
-                "Metadata field {} is not a string, and is not supported by TiefDownConverter.",
-                key
-            ));
+            escape_latex(value)
         }
+    });
+
+    for (path, value) in flattened_metadata {
+        metadata_file_content.push_str(&format!(
+            r"\expandafter\def\csname {}@{}\endcsname{{{}}}",
+            prefix, path, value
+        ));
+        metadata_file_content.push('\n');
     }
 
     metadata_file.write_all(metadata_file_content.as_bytes())?;
@@ -234,21 +348,219 @@ fn generate_tex_metadata(
     Ok(())
 }
 
+/// Escapes the LaTeX special characters in a metadata value, so it is typeset as written.
+pub(crate) fn escape_latex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for character in value.chars() {
+        match character {
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '{' | '}' | '$' | '&' | '#' | '%' | '_' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+/// Runs LaTeX until the document is stable, running the configured tools after the first pass.
+///
+/// Another pass is run if the log asks for a rerun or if one of the files LaTeX reads back on the
+/// next pass (e.g. the table of contents) changed, up to the configured maximum number of passes.
+fn compile_latex_until_stable(
+    compiled_directory_path: &Path,
+    template_path: &Path,
+    processor_args: &Vec<String>,
+    engine: LatexEngine,
+    latex_settings: Option<&LatexSettings>,
+    recorder: &ConversionRecorder,
+) -> Result<()> {
+    let tools = latex_settings
+        .and_then(|s| s.tools.clone())
+        .unwrap_or_default();
+    // The tools' results are only picked up by a pass after them.
+    let min_passes = if tools.is_empty() { 1 } else { 2 };
+    let max_passes = latex_settings
+        .and_then(|s| s.max_passes)
+        .unwrap_or(DEFAULT_MAX_LATEX_PASSES)
+        .max(min_passes);
+
+    for pass in 1..=max_passes {
+        let previous_auxiliary_files = read_auxiliary_files(compiled_directory_path, template_path);
+
+        debug!(
+            "Compiling LaTeX (pass {} of at most {})...",
+            pass, max_passes
+        );
+        compile_latex(
+            compiled_directory_path,
+            template_path,
+            processor_args,
+            engine,
+            false,
+            recorder,
+        )?;
+
+        if pass == 1 && !tools.is_empty() {
+            for tool in &tools {
+                run_latex_tool(compiled_directory_path, template_path, *tool, recorder)?;
+            }
+
+            continue;
+        }
+
+        if recorder.is_dry_run() {
+            return Ok(());
+        }
+
+        let log_content =
+            fs::read(compiled_directory_path.join(template_path.with_extension("log")))
+                .map(|content| String::from_utf8_lossy(&content).to_string())
+                .unwrap_or_default();
+
+        let rerun_requested = needs_rerun(&log_content);
+        let auxiliary_files_changed = previous_auxiliary_files
+            != read_auxiliary_files(compiled_directory_path, template_path);
+
+        if !rerun_requested && !auxiliary_files_changed {
+            debug!("LaTeX output is stable after {} pass(es).", pass);
+            return Ok(());
+        }
+
+        debug!(
+            "LaTeX needs another pass (rerun requested: {}, auxiliary files changed: {}).",
+            rerun_requested, auxiliary_files_changed
+        );
+    }
+
+    let message = format!(
+        "LaTeX output is not stable after {} passes. References may be wrong.",
+        max_passes
+    );
+    warn!("{}", message);
+    recorder.record_warning(WarningReport {
+        source: get_latex_engine_command(engine).to_string(),
+        kind: WarningKind::RerunLimit,
+        message,
+        file: None,
+        line: None,
+    });
+
+    Ok(())
+}
+
+/// Reads the auxiliary files LaTeX reads back on the next pass, to detect changes between passes.
+fn read_auxiliary_files(
+    compiled_directory_path: &Path,
+    template_path: &Path,
+) -> Vec<Option<Vec<u8>>> {
+    LATEX_AUXILIARY_EXTENSIONS
+        .iter()
+        .map(|extension| {
+            fs::read(compiled_directory_path.join(template_path.with_extension(extension))).ok()
+        })
+        .collect()
+}
+
+fn run_latex_tool(
+    compiled_directory_path: &Path,
+    template_path: &Path,
+    tool: LatexTool,
+    recorder: &ConversionRecorder,
+) -> Result<()> {
+    let command_name = get_latex_tool_command(tool);
+    let argument = match tool {
+        LatexTool::Makeindex => template_path.with_extension("idx"),
+        LatexTool::Biber | LatexTool::Bibtex | LatexTool::Makeglossaries => {
+            template_path.with_extension("")
+        }
+    };
+
+    debug!("Running {}...", command_name);
+
+    let mut tool_command = Command::new(command_name);
+    tool_command
+        .current_dir(compiled_directory_path)
+        .arg(argument);
+
+    recorder.run_command(tool_command, command_name, false)?;
+
+    Ok(())
+}
+
+/// Returns the commands needed to compile a LaTeX template with the given settings.
+pub(crate) fn get_latex_dependencies(latex_settings: Option<&LatexSettings>) -> Vec<&'static str> {
+    let engine = latex_settings.and_then(|s| s.engine).unwrap_or_default();
+    let mut dependencies = vec![get_latex_engine_command(engine)];
+
+    if latex_settings.and_then(|s| s.latexmk).unwrap_or(false) {
+        dependencies.push("latexmk");
+    } else if let Some(tools) = latex_settings.and_then(|s| s.tools.as_ref()) {
+        dependencies.extend(tools.iter().map(|tool| get_latex_tool_command(*tool)));
+    }
+
+    dependencies
+}
+
+pub(crate) fn get_latex_engine_command(engine: LatexEngine) -> &'static str {
+    match engine {
+        LatexEngine::Xelatex => "xelatex",
+        LatexEngine::Lualatex => "lualatex",
+        LatexEngine::Pdflatex => "pdflatex",
+    }
+}
+
+fn get_latexmk_engine_flag(engine: LatexEngine) -> &'static str {
+    match engine {
+        LatexEngine::Xelatex => "-xelatex",
+        LatexEngine::Lualatex => "-lualatex",
+        LatexEngine::Pdflatex => "-pdf",
+    }
+}
+
+fn get_latex_tool_command(tool: LatexTool) -> &'static str {
+    match tool {
+        LatexTool::Biber => "biber",
+        LatexTool::Bibtex => "bibtex",
+        LatexTool::Makeindex => "makeindex",
+        LatexTool::Makeglossaries => "makeglossaries",
+    }
+}
+
 fn compile_latex(
     compiled_directory_path: &Path,
     template_path: &Path,
     processor_args: &Vec<String>,
+    engine: LatexEngine,
+    latexmk: bool,
+    recorder: &ConversionRecorder,
 ) -> Result<()> {
-    let mut latex_command = Command::new("xelatex");
+    let command_name = if latexmk {
+        "latexmk"
+    } else {
+        get_latex_engine_command(engine)
+    };
+
+    let mut latex_command = Command::new(command_name);
+
+    latex_command.current_dir(compiled_directory_path);
+
+    if latexmk {
+        latex_command.arg(get_latexmk_engine_flag(engine));
+    }
 
     latex_command
-        .current_dir(compiled_directory_path)
         .arg("-interaction=nonstopmode")
         .arg("-synctex=1")
         .arg(template_path)
         .args(processor_args);
 
-    run_with_logging(latex_command, "xelatex", false)?;
+    recorder.run_command(latex_command, command_name, false)?;
 
     Ok(())
 }
@@ -7,13 +7,16 @@ use std::{
 
 use color_eyre::eyre::{Result, eyre};
 use log::debug;
-use toml::Table;
+use toml::{Table, Value, value::Datetime};
 
 use crate::{
+    build_cache::BuildCache,
+    conversion_recorder::ConversionRecorder,
     converters::common::{
         generate_meta_file, merge_preprocessors, retrieve_combined_output, retrieve_preprocessors,
-        run_preprocessors_on_inputs, run_with_logging, write_combined_output,
+        run_preprocessors_on_inputs, write_combined_output,
     },
+    converters::conversion_meta::ConversionMeta,
     file_retrieval::get_sorted_files,
     injections::retrieve_injections,
     manifest_model::{
@@ -29,10 +32,13 @@ pub(crate) fn convert_typst(
     compiled_directory_path: &Path,
     conversion_input_dir: &Path,
     template: &Template,
+    conversion_meta: &ConversionMeta,
     metadata_fields: &Table,
     metadata_settings: &MetadataSettings,
     custom_processors: &Processors,
     injections: &Vec<Injection>,
+    build_cache: &BuildCache,
+    recorder: &ConversionRecorder,
 ) -> Result<PathBuf> {
     debug!("Starting Typst conversion...");
 
@@ -89,6 +95,10 @@ pub(crate) fn convert_typst(
     )?;
     debug!("Found {} input files.", input_files.len());
 
+    recorder.record_preprocessors(&preprocessors);
+    recorder.record_injections(&injections);
+    recorder.record_input_files(&input_files);
+
     debug!("Retrieving navigation metadata.");
 
     let nav_meta_data = if let Some(nav_meta_gen) = &template.meta_gen
@@ -100,6 +110,8 @@ pub(crate) fn convert_typst(
             compiled_directory_path,
             conversion_input_dir,
             &None,
+            nav_meta_gen.nav_title_source.unwrap_or_default(),
+            false,
         )?;
         Some((
             nav_meta.clone(),
@@ -127,6 +139,7 @@ pub(crate) fn convert_typst(
 
     let results = run_preprocessors_on_inputs(
         template,
+        conversion_meta,
         compiled_directory_path,
         metadata_fields,
         &metadata_file,
@@ -134,6 +147,8 @@ pub(crate) fn convert_typst(
         &nav_meta_data,
         &preprocessors,
         &input_files,
+        build_cache,
+        recorder,
     )?;
 
     write_combined_output(compiled_directory_path, &combined_output, &results)?;
@@ -165,16 +180,31 @@ pub(crate) fn convert_typst(
         }
     }
 
-    let mut typst_command = Command::new("typst");
-
-    typst_command
-        .current_dir(compiled_directory_path)
-        .arg("compile")
-        .arg(template_path)
-        .arg(&output_path)
-        .args(processor_args);
+    let mut processor_inputs = vec![combined_output.as_path(), Path::new("metadata.typ")];
+    if let Some((_, nav_meta_file)) = &nav_meta_data {
+        processor_inputs.push(nav_meta_file);
+    }
 
-    run_with_logging(typst_command, "typst", false)?;
+    build_cache.run_processor(
+        compiled_directory_path,
+        &processor_inputs,
+        &processor_args,
+        &compiled_directory_path.join(&output_path),
+        || {
+            let mut typst_command = Command::new("typst");
+
+            typst_command
+                .current_dir(compiled_directory_path)
+                .arg("compile")
+                .arg(&template_path)
+                .arg(&output_path)
+                .args(&processor_args);
+
+            recorder.run_command(typst_command, "typst", false)?;
+
+            Ok(())
+        },
+    )?;
 
     let output_path = compiled_directory_path.join(output_path);
     debug!("Typst result path: {}", output_path.display());
@@ -182,7 +212,7 @@ pub(crate) fn convert_typst(
     Ok(output_path)
 }
 
-fn generate_typst_metadata(
+pub(crate) fn generate_typst_metadata(
     compiled_directory_path: &Path,
     metadata_fields: &Table,
     metadata_settings: &MetadataSettings,
@@ -204,15 +234,15 @@ fn generate_typst_metadata(
     metadata_file_content.push_str("\n");
 
     for (key, value) in metadata_fields.iter() {
-        if let Some(value) = value.as_str() {
-            metadata_file_content.push_str(format!(r#"  {}: "{}","#, key, value).as_str());
-            metadata_file_content.push_str("\n");
-        } else {
-            return Err(eyre!(
-                "Metadata field {} is not a string, and is not supported by TiefDownConverter.",
-                key
-            ));
-        }
+        metadata_file_content.push_str(
+            format!(
+                r#"  {}: {},"#,
+                format_typst_key(key),
+                to_typst_value(key, value, metadata_settings)
+            )
+            .as_str(),
+        );
+        metadata_file_content.push_str("\n");
     }
 
     metadata_file_content.push_str(")");
@@ -221,3 +251,107 @@ fn generate_typst_metadata(
 
     Ok(())
 }
+
+/// Converts a metadata value to the equivalent Typst value.
+///
+/// Tables become dictionaries, arrays become arrays and dates become `datetime` values. Strings
+/// are escaped, unless the field is raw, in which case the string is used as a Typst expression.
+fn to_typst_value(path: &str, value: &Value, metadata_settings: &MetadataSettings) -> String {
+    match value {
+        Value::String(value) if metadata_settings.is_raw_field(path) => value.clone(),
+        Value::String(value) => format!(r#""{}""#, escape_typst_string(value)),
+        Value::Integer(value) => value.to_string(),
+        Value::Float(value) if value.is_nan() => "calc.nan".to_string(),
+        Value::Float(value) if value.is_infinite() && value.is_sign_negative() => {
+            "-calc.inf".to_string()
+        }
+        Value::Float(value) if value.is_infinite() => "calc.inf".to_string(),
+        Value::Float(value) => format!("{:?}", value),
+        Value::Boolean(value) => value.to_string(),
+        Value::Datetime(value) => to_typst_datetime(value),
+        Value::Array(array) => {
+            let items = array
+                .iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    to_typst_value(&format!("{}.{}", path, index + 1), item, metadata_settings)
+                })
+                .collect::<Vec<_>>();
+
+            match items.len() {
+                0 => "()".to_string(),
+                // A single item needs a trailing comma to be an array rather than parentheses.
+                1 => format!("({},)", items[0]),
+                _ => format!("({})", items.join(", ")),
+            }
+        }
+        Value::Table(table) if table.is_empty() => "(:)".to_string(),
+        Value::Table(table) => format!(
+            "({})",
+            table
+                .iter()
+                .map(|(key, value)| format!(
+                    "{}: {}",
+                    format_typst_key(key),
+                    to_typst_value(&format!("{}.{}", path, key), value, metadata_settings)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Converts a TOML date, time or date-time to a Typst `datetime`. Offsets are not supported by
+/// Typst and are dropped.
+fn to_typst_datetime(datetime: &Datetime) -> String {
+    let mut arguments = vec![];
+
+    if let Some(date) = datetime.date {
+        arguments.push(format!(
+            "year: {}, month: {}, day: {}",
+            date.year, date.month, date.day
+        ));
+    }
+
+    if let Some(time) = datetime.time {
+        arguments.push(format!(
+            "hour: {}, minute: {}, second: {}",
+            time.hour, time.minute, time.second
+        ));
+    }
+
+    format!("datetime({})", arguments.join(", "))
+}
+
+/// Formats a dictionary key, quoting it if it is not a valid Typst identifier.
+fn format_typst_key(key: &str) -> String {
+    let mut characters = key.chars();
+    let is_identifier = characters
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && characters.all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+
+    if is_identifier {
+        key.to_string()
+    } else {
+        format!(r#""{}""#, escape_typst_string(key))
+    }
+}
+
+/// Escapes a metadata value for use in a Typst string literal.
+pub(crate) fn escape_typst_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for character in value.chars() {
+        match character {
+            '\\' => escaped.push_str(r"\\"),
+            '"' => escaped.push_str(r#"\""#),
+            '\n' => escaped.push_str(r"\n"),
+            '\r' => escaped.push_str(r"\r"),
+            '\t' => escaped.push_str(r"\t"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
@@ -1,8 +1,170 @@
-use color_eyre::eyre::{Result, eyre};
+use color_eyre::eyre::{Result, WrapErr, eyre};
 use log::debug;
-use std::path::{Path, PathBuf};
+use regex::Regex;
+use serde::Deserialize;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    ffi::OsStr,
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::LazyLock,
+};
 
-use crate::injections::RenderingInjections;
+use crate::{injections::RenderingInjections, input_filter::InputFilter};
+
+/// The name of the file that defines the order of the files in a directory.
+pub(crate) const ORDER_FILE_NAME: &str = "order.toml";
+
+pub(crate) static ORDER_NUMBER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d+").expect("Invalid regex"));
+static SUMMARY_LIST_ITEM_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s+(.*?)\s*$").expect("Invalid regex"));
+static SUMMARY_LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[[^\]]*\]\(\s*<?([^)>]*)>?\s*\)").expect("Invalid regex"));
+
+#[derive(Deserialize)]
+struct OrderFile {
+    order: Option<Vec<PathBuf>>,
+    summary: Option<PathBuf>,
+}
+
+/// The order of the files in a directory, as defined by an ordering file.
+///
+/// Entries are paths relative to the directory of the ordering file. Entries in subdirectories
+/// also define the order of the files in those subdirectories, unless they have their own
+/// ordering file. `summary` is the markdown summary the entries were read from, if any.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct FileOrder {
+    pub(crate) path: PathBuf,
+    pub(crate) summary: Option<PathBuf>,
+    pub(crate) entries: Vec<PathBuf>,
+}
+
+impl FileOrder {
+    fn position(&self, name: &OsStr) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.components().next() == Some(Component::Normal(name)))
+    }
+
+    pub(crate) fn for_subdirectory(&self, name: &OsStr) -> Option<FileOrder> {
+        let entries = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.strip_prefix(name).ok())
+            .filter(|entry| entry.components().next().is_some())
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+
+        (!entries.is_empty()).then(|| FileOrder {
+            path: self.path.clone(),
+            summary: self.summary.clone(),
+            entries,
+        })
+    }
+}
+
+/// Reads the ordering file of a directory, if there is one.
+///
+/// `order.toml` contains either an `order` array of paths or the path of a markdown `summary`,
+/// like a `SUMMARY.md`, with a list where each item is either a link to a file or a path.
+pub(crate) fn read_file_order(dir: &Path) -> Result<Option<FileOrder>> {
+    let path = dir.join(ORDER_FILE_NAME);
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let order_file = toml::from_str::<OrderFile>(&content)
+        .wrap_err_with(|| format!("Failed to parse ordering file '{}'.", path.display()))?;
+
+    let (summary, entries) = match (order_file.order, order_file.summary) {
+        (Some(order), None) => (None, order),
+        (None, Some(summary)) => {
+            let summary = dir.join(summary);
+            let content = fs::read_to_string(&summary).wrap_err_with(|| {
+                format!(
+                    "Failed to read the summary '{}' of ordering file '{}'.",
+                    summary.display(),
+                    path.display()
+                )
+            })?;
+
+            (Some(summary), parse_summary(&content))
+        }
+        _ => {
+            return Err(eyre!(
+                "Ordering file '{}' must define either 'order' or 'summary'.",
+                path.display()
+            ));
+        }
+    };
+
+    debug!(
+        "Read {} entries from ordering file '{}'.",
+        entries.len(),
+        path.display()
+    );
+
+    Ok(Some(FileOrder {
+        path,
+        summary,
+        entries,
+    }))
+}
+
+fn parse_summary(content: &str) -> Vec<PathBuf> {
+    content
+        .lines()
+        .filter_map(|line| SUMMARY_LIST_ITEM_REGEX.captures(line))
+        .filter_map(|captures| {
+            let item = &captures[1];
+            let entry = match SUMMARY_LINK_REGEX.captures(item) {
+                Some(link) => link[1].trim().replace("%20", " "),
+                None => item.to_string(),
+            };
+            let entry = entry.trim_start_matches("./");
+
+            (!entry.is_empty()).then(|| PathBuf::from(entry))
+        })
+        .collect()
+}
+
+/// Retrieves the file order of a directory of a markdown directory, either from its own ordering
+/// file or inherited from the ordering files of its parents.
+///
+/// # Arguments
+///
+/// * `markdown_dir` - The markdown directory.
+/// * `relative_dir` - The directory, relative to the markdown directory.
+pub(crate) fn get_file_order(
+    markdown_dir: &Path,
+    relative_dir: &Path,
+) -> Result<Option<FileOrder>> {
+    let mut dir = markdown_dir.to_path_buf();
+    let mut file_order = read_file_order(&dir)?;
+
+    for component in relative_dir.components() {
+        if let Component::Normal(name) = component {
+            let inherited_file_order = file_order
+                .as_ref()
+                .and_then(|order| order.for_subdirectory(name));
+
+            dir.push(name);
+            file_order = read_file_order(&dir)?.or(inherited_file_order);
+        }
+    }
+
+    Ok(file_order)
+}
+
+/// Checks whether a path is an ordering file or the summary the ordering file of its directory
+/// reads its entries from. Neither is converted.
+pub(crate) fn is_order_file(path: &Path, file_order: Option<&FileOrder>) -> bool {
+    path.file_name() == Some(OsStr::new(ORDER_FILE_NAME))
+        || file_order.is_some_and(|file_order| file_order.summary.as_deref() == Some(path))
+}
 
 pub(crate) fn get_sorted_files(
     input_dir: &Path,
@@ -10,8 +172,27 @@ pub(crate) fn get_sorted_files(
     compiled_directory_path: &Path,
     injections: &RenderingInjections,
     multi_file_output: bool,
+) -> Result<Vec<PathBuf>> {
+    get_sorted_files_in_order(
+        input_dir,
+        project_directory_path,
+        compiled_directory_path,
+        injections,
+        multi_file_output,
+        None,
+    )
+}
+
+fn get_sorted_files_in_order(
+    input_dir: &Path,
+    project_directory_path: &Path,
+    compiled_directory_path: &Path,
+    injections: &RenderingInjections,
+    multi_file_output: bool,
+    inherited_file_order: Option<FileOrder>,
 ) -> Result<Vec<PathBuf>> {
     let dir_content = std::fs::read_dir(input_dir)?;
+    let own_file_order = read_file_order(input_dir)?;
 
     let mut dir_content = dir_content
         .filter_map(|f| {
@@ -19,27 +200,14 @@ pub(crate) fn get_sorted_files(
 
             Some(entry.path())
         })
+        .filter(|path| !is_order_file(path, own_file_order.as_ref()))
         .collect::<Vec<_>>();
 
+    let file_order = own_file_order.or(inherited_file_order);
+
     dir_content.append(&mut injections.body_injections.clone());
 
-    dir_content.sort_by(|a, b| {
-        let a_num = retrieve_file_order_number(a);
-        let b_num = retrieve_file_order_number(b);
-
-        match a_num.cmp(&b_num) {
-            std::cmp::Ordering::Equal => {
-                let a_is_file = a.is_file();
-                let b_is_file = b.is_file();
-                match (a_is_file, b_is_file) {
-                    (true, false) => std::cmp::Ordering::Less,
-                    (false, true) => std::cmp::Ordering::Greater,
-                    _ => std::cmp::Ordering::Equal,
-                }
-            }
-            other => other,
-        }
-    });
+    sort_paths(&mut dir_content, file_order.as_ref());
 
     let injected_dir_content = if multi_file_output {
         dir_content
@@ -58,12 +226,16 @@ pub(crate) fn get_sorted_files(
             if f.is_file() {
                 return Ok(vec![f.clone()]);
             } else if f.is_dir() {
-                get_sorted_files(
+                get_sorted_files_in_order(
                     f,
                     project_directory_path,
                     compiled_directory_path,
                     &RenderingInjections::new(),
                     multi_file_output,
+                    file_order
+                        .as_ref()
+                        .zip(f.file_name())
+                        .and_then(|(order, name)| order.for_subdirectory(name)),
                 )
             } else {
                 Err(eyre!(
@@ -97,13 +269,45 @@ pub(crate) fn get_sorted_files(
     Ok(input_files)
 }
 
-fn retrieve_file_order_number(p: &Path) -> u32 {
-    let file_name_regex = regex::Regex::new(r".*?(\d+).*").unwrap();
+/// Sorts the paths of a directory. Paths listed in the file order come first in the listed
+/// order, all others are sorted by their order number, with files before directories.
+pub(crate) fn sort_paths(paths: &mut [PathBuf], file_order: Option<&FileOrder>) {
+    paths.sort_by(|a, b| {
+        let a_position = file_order.and_then(|order| order.position(a.file_name()?));
+        let b_position = file_order.and_then(|order| order.position(b.file_name()?));
+
+        match (a_position, b_position) {
+            (Some(a_position), Some(b_position)) => a_position.cmp(&b_position),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => compare_by_order_number(a, b),
+        }
+    });
+}
+
+fn compare_by_order_number(a: &Path, b: &Path) -> Ordering {
+    let a_num = retrieve_file_order_number(a);
+    let b_num = retrieve_file_order_number(b);
+
+    match a_num.cmp(&b_num) {
+        Ordering::Equal => {
+            let a_is_file = a.is_file();
+            let b_is_file = b.is_file();
+            match (a_is_file, b_is_file) {
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                _ => Ordering::Equal,
+            }
+        }
+        other => other,
+    }
+}
 
+pub(crate) fn retrieve_file_order_number(p: &Path) -> u32 {
     if let Some(order_number) = p
         .file_name()
         .and_then(|name| name.to_str().map(|s| s.to_string()))
-        .and_then(|s| file_name_regex.captures(&s).map(|cap| cap[1].to_string()))
+        .and_then(|s| ORDER_NUMBER_REGEX.find(&s).map(|m| m.as_str().to_string()))
         .and_then(|n| match n.parse::<u32>() {
             Result::Ok(n) => Some(n),
             Err(_e) => None,
@@ -115,6 +319,171 @@ fn retrieve_file_order_number(p: &Path) -> u32 {
     0
 }
 
+/// Checks the order of the files in a markdown directory and its subdirectories.
+///
+/// Directories with an ordering file are checked for unlisted files, listed files that do not
+/// exist and duplicate entries. Directories without one are checked for files without an order
+/// number, files with more than one number in their name and files sharing an order number.
+///
+/// # Arguments
+///
+/// * `markdown_dir` - The markdown directory to check.
+/// * `excluded` - Paths relative to the markdown directory that are not converted, like resources.
+/// * `input_filter` - The input filter of the markdown project.
+///
+/// # Returns
+///
+/// A Result containing either an error or a Vec of warnings.
+pub(crate) fn get_file_order_warnings(
+    markdown_dir: &Path,
+    excluded: &[PathBuf],
+    input_filter: &InputFilter,
+) -> Result<Vec<String>> {
+    let mut warnings = vec![];
+
+    collect_file_order_warnings(
+        markdown_dir,
+        markdown_dir,
+        excluded,
+        input_filter,
+        None,
+        &mut warnings,
+    )?;
+
+    Ok(warnings)
+}
+
+fn collect_file_order_warnings(
+    markdown_dir: &Path,
+    dir: &Path,
+    excluded: &[PathBuf],
+    input_filter: &InputFilter,
+    inherited_file_order: Option<FileOrder>,
+    warnings: &mut Vec<String>,
+) -> Result<()> {
+    let display = |path: &Path| {
+        path.strip_prefix(markdown_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+
+    let own_file_order = read_file_order(dir)?;
+
+    let mut dir_content = fs::read_dir(dir)?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| !is_order_file(path, own_file_order.as_ref()))
+        .filter(|path| {
+            path.strip_prefix(markdown_dir).is_ok_and(|relative_path| {
+                !excluded.iter().any(|excluded| relative_path == excluded)
+                    && input_filter.is_included(relative_path, path.is_dir())
+            })
+        })
+        .collect::<Vec<_>>();
+    dir_content.sort();
+
+    if let Some(file_order) = &own_file_order {
+        let mut seen = vec![];
+
+        for entry in &file_order.entries {
+            if seen.contains(&entry) {
+                warnings.push(format!(
+                    "'{}' is listed more than once in the ordering file '{}'.",
+                    entry.display(),
+                    display(&file_order.path)
+                ));
+            } else if !dir.join(entry).exists() {
+                warnings.push(format!(
+                    "'{}' is listed in the ordering file '{}', but does not exist.",
+                    entry.display(),
+                    display(&file_order.path)
+                ));
+            }
+
+            seen.push(entry);
+        }
+    }
+
+    let file_order = own_file_order.or(inherited_file_order);
+
+    if let Some(file_order) = &file_order {
+        for path in &dir_content {
+            if let Some(name) = path.file_name()
+                && file_order.position(name).is_none()
+            {
+                warnings.push(format!(
+                    "'{}' is not listed in the ordering file '{}'.",
+                    display(path),
+                    display(&file_order.path)
+                ));
+            }
+        }
+    } else {
+        let mut order_numbers: HashMap<(u32, bool), Vec<&PathBuf>> = HashMap::new();
+
+        for path in &dir_content {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            match ORDER_NUMBER_REGEX.find_iter(&file_name).count() {
+                0 => warnings.push(format!(
+                    "'{}' has no order number and is sorted as 0.",
+                    display(path)
+                )),
+                1 => {}
+                _ => warnings.push(format!(
+                    "'{}' contains more than one number. Its order number is {}.",
+                    display(path),
+                    retrieve_file_order_number(path)
+                )),
+            }
+
+            order_numbers
+                .entry((retrieve_file_order_number(path), path.is_file()))
+                .or_default()
+                .push(path);
+        }
+
+        let mut duplicates = order_numbers
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .collect::<Vec<_>>();
+        duplicates.sort();
+
+        for ((order_number, _), paths) in duplicates {
+            warnings.push(format!(
+                "{} share the order number {}.",
+                paths
+                    .iter()
+                    .map(|path| format!("'{}'", display(path)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                order_number
+            ));
+        }
+    }
+
+    for path in dir_content.iter().filter(|path| path.is_dir()) {
+        let subdirectory_file_order = file_order
+            .as_ref()
+            .zip(path.file_name())
+            .and_then(|(order, name)| order.for_subdirectory(name));
+
+        collect_file_order_warnings(
+            markdown_dir,
+            path,
+            excluded,
+            input_filter,
+            subdirectory_file_order,
+            warnings,
+        )?;
+    }
+
+    Ok(())
+}
+
 pub(crate) fn get_relative_path_from_compiled_dir(
     original_path: &Path,
     compiled_dir: &Path,
@@ -0,0 +1,140 @@
+use color_eyre::eyre::Result;
+use log::info;
+use std::{
+    fs,
+    io::{BufRead, BufReader, ErrorKind},
+    path::Path,
+};
+use toml::{Table, Value};
+
+use crate::{manifest_model::Template, metadata_management::yaml_to_metadata_value};
+
+/// Reads the YAML front matter of a file.
+///
+/// The front matter has to start on the first line with `---` and end with a line containing
+/// `---` or `...`. Only this leading block is read, so images and other binary files are not
+/// read in full. Files without front matter, front matter that is not valid UTF-8 and front
+/// matter that is not a YAML mapping are treated as having no front matter, just as pandoc
+/// treats them.
+pub(crate) fn read_front_matter(path: &Path) -> Result<Option<Table>> {
+    let mut reader = BufReader::new(fs::File::open(path)?);
+
+    if !reader.fill_buf()?.starts_with(b"---") {
+        return Ok(None);
+    }
+
+    let mut content = String::new();
+    loop {
+        let line_start = content.len();
+        match reader.read_line(&mut content) {
+            Ok(0) => return Ok(None),
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::InvalidData => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        let line = content[line_start..].trim_end();
+        if line_start == 0 && line != "---" {
+            return Ok(None);
+        }
+        if line_start > 0 && (line == "---" || line == "...") {
+            break;
+        }
+    }
+
+    Ok(parse_front_matter(&content))
+}
+
+pub(crate) fn parse_front_matter(content: &str) -> Option<Table> {
+    split_front_matter(content).0
+}
+
+/// Splits the content of a file into its front matter and the content following it.
+///
+/// If the file has no valid front matter, the content is returned unchanged.
+pub(crate) fn split_front_matter(content: &str) -> (Option<Table>, &str) {
+    let mut lines = content.split_inclusive('\n');
+
+    if lines.next().map(str::trim_end) != Some("---") {
+        return (None, content);
+    }
+
+    let mut offset = content.find('\n').map(|i| i + 1).unwrap_or(content.len());
+    let front_matter_start = offset;
+
+    for line in lines {
+        if line.trim_end() == "---" || line.trim_end() == "..." {
+            let front_matter = parse_front_matter_yaml(&content[front_matter_start..offset]);
+
+            return match front_matter {
+                Some(front_matter) => (Some(front_matter), &content[offset + line.len()..]),
+                None => (None, content),
+            };
+        }
+
+        offset += line.len();
+    }
+
+    (None, content)
+}
+
+fn parse_front_matter_yaml(front_matter: &str) -> Option<Table> {
+    let value: serde_yaml::Value = serde_yaml::from_str(front_matter).ok()?;
+
+    match yaml_to_metadata_value(value).ok()? {
+        Some(Value::Table(front_matter)) => Some(front_matter),
+        _ => None,
+    }
+}
+
+/// Checks whether the front matter excludes a file from the conversion with a template.
+///
+/// Files are excluded from all templates with `draft: true`, and from specific templates with
+/// `exclude_from`, which lists template names or template types, e.g. `exclude_from: [epub]`.
+pub(crate) fn is_excluded_by_front_matter(front_matter: &Table, template: &Template) -> bool {
+    if let Some(Value::Boolean(true)) = front_matter.get("draft") {
+        return true;
+    }
+
+    let exclude_from = match front_matter.get("exclude_from") {
+        Some(Value::String(exclude_from)) => vec![exclude_from.as_str()],
+        Some(Value::Array(exclude_from)) => {
+            exclude_from.iter().filter_map(|e| e.as_str()).collect()
+        }
+        _ => vec![],
+    };
+
+    exclude_from.iter().any(|exclude_from| {
+        *exclude_from == template.name
+            || exclude_from.eq_ignore_ascii_case(template.template_type.as_str())
+    })
+}
+
+/// Removes all files excluded by their front matter from the conversion input directory of a
+/// template.
+pub(crate) fn remove_excluded_files(
+    conversion_input_dir: &Path,
+    template: &Template,
+) -> Result<()> {
+    for entry in fs::read_dir(conversion_input_dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            remove_excluded_files(&path, template)?;
+            continue;
+        }
+
+        if let Some(front_matter) = read_front_matter(&path)?
+            && is_excluded_by_front_matter(&front_matter, template)
+        {
+            info!(
+                "Excluding '{}' from template '{}'.",
+                path.file_name().unwrap_or_default().to_string_lossy(),
+                template.name
+            );
+            fs::remove_file(&path)?;
+        }
+    }
+
+    Ok(())
+}
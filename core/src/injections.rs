@@ -4,9 +4,10 @@ use std::path::{Path, PathBuf};
 
 use crate::{
     manifest_model::{Injection, Template},
-    project_management::load_and_convert_manifest,
+    project_management::{load_and_convert_manifest, save_manifest},
 };
 
+#[derive(Clone)]
 pub(crate) struct RenderingInjections {
     pub header_injections: Vec<PathBuf>,
     pub body_injections: Vec<PathBuf>,
@@ -68,8 +69,7 @@ pub fn add_injection(project: Option<PathBuf>, name: String, files: Vec<PathBuf>
         manifest.injections = Some(vec![injection]);
     }
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("Injection '{}' added.", name);
 
     Ok(())
@@ -110,8 +110,7 @@ pub fn remove_injection(project: Option<PathBuf>, name: String) -> Result<()> {
         return Err(eyre!("No injections defined in the manifest."));
     }
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("Removed injection '{}'", name);
 
     Ok(())
@@ -199,8 +198,7 @@ pub fn add_files_to_injection(
         .files
         .append(&mut new_files);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("Added {} files to the injection '{}'.", files.len(), name);
 
     Ok(())
@@ -0,0 +1,216 @@
+use color_eyre::eyre::Result;
+use fast_glob::glob_match;
+use log::{debug, info};
+use std::{
+    fs,
+    path::{Component, Path},
+};
+
+use crate::manifest_model::MarkdownProject;
+
+/// The name of the file in a markdown directory containing gitignore-style exclude patterns.
+pub(crate) const IGNORE_FILE_NAME: &str = ".tiefdownignore";
+
+/// A gitignore-style pattern.
+#[derive(Clone, Debug, PartialEq)]
+struct FilterRule {
+    glob: String,
+    negated: bool,
+    directory_only: bool,
+}
+
+impl FilterRule {
+    /// Parses a gitignore-style pattern. Returns `None` for blank lines and comments.
+    fn parse(pattern: &str) -> Option<FilterRule> {
+        let pattern = pattern.trim_end();
+
+        if pattern.is_empty() || pattern.starts_with('#') {
+            return None;
+        }
+
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+        let pattern = pattern.strip_prefix('\\').unwrap_or(pattern);
+
+        let (directory_only, pattern) = match pattern.strip_suffix('/') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+
+        let glob = if let Some(pattern) = pattern.strip_prefix('/') {
+            pattern.to_string()
+        } else if pattern.contains('/') {
+            pattern.to_string()
+        } else {
+            format!("**/{}", pattern)
+        };
+
+        if glob.is_empty() {
+            return None;
+        }
+
+        Some(FilterRule {
+            glob,
+            negated,
+            directory_only,
+        })
+    }
+
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        (!self.directory_only || is_dir) && glob_match(&self.glob, path)
+    }
+}
+
+/// Decides which files of a markdown directory are converted.
+///
+/// Exclude patterns come from the `exclude` field of the markdown project, followed by the
+/// patterns in the `.tiefdownignore` file of the markdown directory. Like in gitignore, the last
+/// matching pattern wins, patterns starting with `!` re-include paths, and the contents of an
+/// excluded directory are always excluded. If the markdown project has `include` patterns, only
+/// files matching one of them are converted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct InputFilter {
+    include: Vec<FilterRule>,
+    exclude: Vec<FilterRule>,
+}
+
+impl InputFilter {
+    /// Creates the input filter of a markdown project.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown_project` - The markdown project with its include and exclude patterns.
+    /// * `markdown_dir` - The markdown directory, possibly containing a `.tiefdownignore` file.
+    pub(crate) fn new(markdown_project: &MarkdownProject, markdown_dir: &Path) -> Result<Self> {
+        let ignore_file_path = markdown_dir.join(IGNORE_FILE_NAME);
+        let ignore_file_content = if ignore_file_path.is_file() {
+            fs::read_to_string(&ignore_file_path)?
+        } else {
+            String::new()
+        };
+
+        let include = markdown_project
+            .include
+            .iter()
+            .flatten()
+            .filter_map(|pattern| FilterRule::parse(pattern))
+            .collect::<Vec<_>>();
+        let exclude = markdown_project
+            .exclude
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .chain(ignore_file_content.lines())
+            .filter_map(FilterRule::parse)
+            .collect::<Vec<_>>();
+
+        debug!(
+            "Created input filter for markdown project '{}' with {} include and {} exclude patterns.",
+            markdown_project.name,
+            include.len(),
+            exclude.len()
+        );
+
+        Ok(InputFilter { include, exclude })
+    }
+
+    /// Checks whether a path of the markdown directory is converted.
+    ///
+    /// # Arguments
+    ///
+    /// * `relative_path` - The path relative to the markdown directory.
+    /// * `is_dir` - Whether the path is a directory. Directories are not subject to `include`
+    ///   patterns, so that the files inside them can be included.
+    pub(crate) fn is_included(&self, relative_path: &Path, is_dir: bool) -> bool {
+        let components = relative_path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(component) => Some(component.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if components.len() == 1 && components[0] == IGNORE_FILE_NAME {
+            return false;
+        }
+
+        for i in 1..components.len() {
+            if matches_rules(&self.exclude, &components[..i].join("/"), true) {
+                return false;
+            }
+        }
+
+        let path = components.join("/");
+
+        if matches_rules(&self.exclude, &path, is_dir) {
+            return false;
+        }
+
+        is_dir || self.include.is_empty() || matches_rules(&self.include, &path, false)
+    }
+}
+
+fn matches_rules(rules: &[FilterRule], path: &str, is_dir: bool) -> bool {
+    rules.iter().fold(false, |matched, rule| {
+        if rule.matches(path, is_dir) {
+            !rule.negated
+        } else {
+            matched
+        }
+    })
+}
+
+/// Copies the files of the markdown directory included by the input filter to the conversion
+/// input directory. Excluded files are never written, and directories without included files
+/// are not created.
+///
+/// # Arguments
+///
+/// * `markdown_dir` - The markdown directory to copy.
+/// * `conversion_input_dir` - The conversion input directory to copy the files to.
+/// * `input_filter` - The input filter of the markdown project.
+pub(crate) fn copy_filtered_files(
+    markdown_dir: &Path,
+    conversion_input_dir: &Path,
+    input_filter: &InputFilter,
+) -> Result<()> {
+    copy_filtered_files_in(
+        markdown_dir,
+        markdown_dir,
+        conversion_input_dir,
+        input_filter,
+    )
+}
+
+fn copy_filtered_files_in(
+    markdown_dir: &Path,
+    dir: &Path,
+    conversion_input_dir: &Path,
+    input_filter: &InputFilter,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let relative_path = path.strip_prefix(markdown_dir)?;
+        let is_dir = path.is_dir();
+
+        if !input_filter.is_included(relative_path, is_dir) {
+            if relative_path == Path::new(IGNORE_FILE_NAME) {
+                debug!("Skipping '{}'.", IGNORE_FILE_NAME);
+            } else {
+                info!("Ignoring '{}'.", relative_path.display());
+            }
+        } else if is_dir {
+            copy_filtered_files_in(markdown_dir, &path, conversion_input_dir, input_filter)?;
+        } else {
+            let target_path = conversion_input_dir.join(relative_path);
+            if let Some(parent) = target_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(&path, &target_path)?;
+        }
+    }
+
+    Ok(())
+}
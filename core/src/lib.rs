@@ -1,18 +1,31 @@
+mod bibliography;
+mod build_cache;
+pub mod build_report;
+pub mod chapter_management;
 pub mod consts;
 pub mod conversion;
 mod conversion_decider;
+mod conversion_recorder;
 mod converters;
 mod file_retrieval;
+mod front_matter;
 pub mod injections;
+mod input_filter;
+mod manifest_document;
+mod manifest_includes;
 pub mod manifest_model;
+pub mod manifest_schema;
 pub mod markdown_project_management;
 pub mod meta_generation_feature;
 pub mod meta_generation_format;
+pub mod metadata_format;
 pub mod metadata_management;
 pub mod nav_meta_generation;
 pub mod project_management;
 mod template_management;
 pub mod template_type;
+mod text_diff;
+pub mod validation;
 
 #[cfg(test)]
 mod _tests;
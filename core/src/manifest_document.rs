@@ -0,0 +1,225 @@
+use color_eyre::eyre::Result;
+use log::debug;
+use serde::Serialize;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, TableLike, Value};
+
+/// Applies a serialized manifest to the existing manifest file content, keeping its
+/// formatting.
+///
+/// Only the keys that differ are changed. Comments, blank lines, key order and inline
+/// tables of the existing content are kept. Entries of arrays of tables, like templates
+/// or markdown projects, are matched by their name, so removing one entry doesn't touch
+/// the others. A key is only removed if the previous manifest defines it, so keys the
+/// manifest doesn't model, like hand-written ones, are kept.
+///
+/// # Arguments
+///
+/// * `existing_content` - The current content of the manifest file.
+/// * `previous_manifest` - The manifest as it was loaded from the existing content.
+/// * `manifest` - The manifest to write, e.g. a `Manifest` or an upgraded `toml::Table`.
+///
+/// # Returns
+///
+/// A Result containing either an error or the new content of the manifest file.
+pub(crate) fn update_manifest_document<P: Serialize, T: Serialize>(
+    existing_content: &str,
+    previous_manifest: &P,
+    manifest: &T,
+) -> Result<String> {
+    let mut document: DocumentMut = existing_content.parse()?;
+    let previous_document: DocumentMut = toml::to_string(previous_manifest)?.parse()?;
+    let updated_document: DocumentMut = toml::to_string(manifest)?.parse()?;
+
+    merge_tables(
+        document.as_table_mut(),
+        Some(previous_document.as_table()),
+        updated_document.as_table(),
+        false,
+    );
+    debug!("Applied manifest changes to the existing manifest document.");
+
+    Ok(document.to_string())
+}
+
+fn merge_tables(
+    existing: &mut dyn TableLike,
+    previous: Option<&dyn TableLike>,
+    updated: &dyn TableLike,
+    inline: bool,
+) {
+    let removed_keys = existing
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| {
+            !updated.contains_key(key)
+                && previous.is_some_and(|previous| previous.contains_key(key))
+        })
+        .collect::<Vec<_>>();
+
+    for key in removed_keys {
+        existing.remove(&key);
+    }
+
+    for (key, updated_item) in updated.iter() {
+        let previous_item = previous.and_then(|previous| previous.get(key));
+        let merged = existing
+            .get_mut(key)
+            .is_some_and(|existing_item| merge_items(existing_item, previous_item, updated_item));
+
+        // Items that changed their kind are replaced along with their key, as the key is
+        // formatted differently, e.g. as a table header.
+        if !merged {
+            existing.remove(key);
+            existing.insert(key, detach_item(updated_item, inline));
+        }
+    }
+}
+
+/// Merges two items of the same kind. Returns false if their kinds differ.
+fn merge_items(existing: &mut Item, previous: Option<&Item>, updated: &Item) -> bool {
+    let previous_table = previous.and_then(Item::as_table_like);
+
+    match (existing, updated) {
+        (Item::Table(existing), Item::Table(updated)) => {
+            merge_tables(existing, previous_table, updated, false)
+        }
+        (Item::Table(existing), Item::Value(Value::InlineTable(updated))) => {
+            merge_tables(existing, previous_table, updated, false)
+        }
+        (Item::Value(Value::InlineTable(existing)), Item::Table(updated)) => merge_tables(
+            existing,
+            previous_table,
+            &updated.clone().into_inline_table(),
+            true,
+        ),
+        (Item::Value(Value::InlineTable(existing)), Item::Value(Value::InlineTable(updated))) => {
+            merge_tables(existing, previous_table, updated, true)
+        }
+        (Item::ArrayOfTables(existing), Item::ArrayOfTables(updated)) => merge_arrays_of_tables(
+            existing,
+            previous.and_then(Item::as_array_of_tables),
+            updated,
+        ),
+        (Item::Value(existing), Item::Value(updated)) => {
+            if !is_value_equal(existing, updated) {
+                let decor = existing.decor().clone();
+                *existing = updated.clone();
+                *existing.decor_mut() = decor;
+            }
+        }
+        _ => return false,
+    }
+
+    true
+}
+
+/// Merges the tables of an array by their `name`, falling back to their position.
+fn merge_arrays_of_tables(
+    existing: &mut ArrayOfTables,
+    previous: Option<&ArrayOfTables>,
+    updated: &ArrayOfTables,
+) {
+    let mut remaining = existing.iter().cloned().map(Some).collect::<Vec<_>>();
+    let mut merged = ArrayOfTables::new();
+
+    for (index, updated_table) in updated.iter().enumerate() {
+        let name = get_table_name(updated_table);
+        let position = match name {
+            Some(name) => remaining.iter().position(|table| {
+                table
+                    .as_ref()
+                    .is_some_and(|table| get_table_name(table) == Some(name))
+            }),
+            None => (index < remaining.len() && remaining[index].is_some()).then_some(index),
+        };
+
+        match position.and_then(|position| remaining[position].take()) {
+            Some(mut table) => {
+                let previous_table = previous.and_then(|previous| match name {
+                    Some(name) => previous
+                        .iter()
+                        .find(|table| get_table_name(table) == Some(name)),
+                    None => previous.get(index),
+                });
+                merge_tables(
+                    &mut table,
+                    previous_table.map(|table| table as &dyn TableLike),
+                    updated_table,
+                    false,
+                );
+                merged.push(table);
+            }
+            None => merged.push(detach_table(updated_table)),
+        }
+    }
+
+    *existing = merged;
+}
+
+fn get_table_name(table: &Table) -> Option<&str> {
+    table.get("name").and_then(|name| name.as_str())
+}
+
+/// Copies an item of the serialized manifest, so it can be inserted into the existing
+/// document.
+///
+/// Tables are rebuilt, as their position in the serialized manifest would otherwise
+/// determine where they end up in the existing document.
+fn detach_item(item: &Item, inline: bool) -> Item {
+    let item = match item {
+        Item::Table(table) => Item::Table(detach_table(table)),
+        Item::ArrayOfTables(array) => {
+            let mut detached = ArrayOfTables::new();
+            for table in array.iter() {
+                detached.push(detach_table(table));
+            }
+            Item::ArrayOfTables(detached)
+        }
+        item => item.clone(),
+    };
+
+    if inline {
+        item.into_value().map(Item::Value).unwrap_or(Item::None)
+    } else {
+        item
+    }
+}
+
+fn detach_table(table: &Table) -> Table {
+    let mut detached = Table::new();
+    detached.set_implicit(table.is_implicit());
+
+    for (key, item) in table.iter() {
+        detached.insert(key, detach_item(item, false));
+    }
+
+    detached
+}
+
+fn is_value_equal(existing: &Value, updated: &Value) -> bool {
+    match (existing, updated) {
+        (Value::String(existing), Value::String(updated)) => existing.value() == updated.value(),
+        (Value::Integer(existing), Value::Integer(updated)) => existing.value() == updated.value(),
+        (Value::Float(existing), Value::Float(updated)) => existing.value() == updated.value(),
+        (Value::Boolean(existing), Value::Boolean(updated)) => existing.value() == updated.value(),
+        (Value::Datetime(existing), Value::Datetime(updated)) => {
+            existing.value() == updated.value()
+        }
+        (Value::Array(existing), Value::Array(updated)) => {
+            existing.len() == updated.len()
+                && existing
+                    .iter()
+                    .zip(updated.iter())
+                    .all(|(existing, updated)| is_value_equal(existing, updated))
+        }
+        (Value::InlineTable(existing), Value::InlineTable(updated)) => {
+            existing.len() == updated.len()
+                && existing.iter().all(|(key, existing)| {
+                    updated
+                        .get(key)
+                        .is_some_and(|updated| is_value_equal(existing, updated))
+                })
+        }
+        _ => false,
+    }
+}
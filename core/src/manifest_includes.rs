@@ -0,0 +1,491 @@
+use color_eyre::eyre::{Result, eyre};
+use fast_glob::glob_match;
+use log::debug;
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+use toml::{Table, Value};
+
+use crate::{build_cache::BUILD_CACHE_DIRECTORY, manifest_model::ManifestFragment};
+
+static BUILD_DIRECTORY_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d{4}-\d{2}-\d{2}_\d{2}-\d{2}-\d{2}$").expect("Invalid regex"));
+
+/// The sections of the manifest that can be defined in manifest fragments, along with the
+/// name of their entries used in error messages.
+const FRAGMENT_SECTIONS: [(&str, &str); 6] = [
+    ("templates", "Template"),
+    ("markdown_projects", "Markdown project"),
+    ("profiles", "Profile"),
+    ("injections", "Injection"),
+    ("custom_processors.preprocessors", "Preprocessor"),
+    ("custom_processors.processors", "Processor"),
+];
+
+/// Merges the manifest fragments listed in the `include` key into the manifest.
+///
+/// The entries of each fragment are appended to the sections of the manifest. An entry
+/// whose name is already defined in the manifest or another fragment is an error.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory.
+/// * `manifest` - The manifest to merge the fragments into.
+///
+/// # Returns
+///
+/// A Result containing either an error or the merged manifest fragments.
+pub(crate) fn merge_manifest_fragments(
+    project: &Path,
+    manifest: &mut Table,
+) -> Result<Vec<ManifestFragment>> {
+    let patterns = manifest
+        .get("include")
+        .and_then(|include| include.as_array())
+        .map(|include| {
+            include
+                .iter()
+                .filter_map(|pattern| pattern.as_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if patterns.is_empty() {
+        return Ok(vec![]);
+    }
+
+    check_duplicate_names(manifest)?;
+
+    let excluded_dirs = get_excluded_dirs(project, manifest);
+
+    let mut fragment_paths: Vec<PathBuf> = vec![];
+    for pattern in &patterns {
+        for fragment_path in get_fragment_paths(project, pattern, &excluded_dirs)? {
+            if !fragment_paths.contains(&fragment_path) {
+                fragment_paths.push(fragment_path);
+            }
+        }
+    }
+
+    let mut fragments: Vec<ManifestFragment> = vec![];
+
+    for fragment_path in fragment_paths {
+        let fragment_content = fs::read_to_string(project.join(&fragment_path))?;
+        let fragment_table: Table = toml::from_str(&fragment_content).map_err(|e| {
+            eyre!(
+                "Failed to parse manifest fragment '{}': {}",
+                fragment_path.display(),
+                e
+            )
+        })?;
+
+        check_fragment_keys(&fragment_path, &fragment_table)?;
+
+        let mut fragment = ManifestFragment {
+            path: fragment_path,
+            entries: vec![],
+        };
+
+        for (section, label) in FRAGMENT_SECTIONS {
+            let Some(entries) = get_section(&fragment_table, section) else {
+                continue;
+            };
+            let entries = entries.as_array().ok_or(eyre!(
+                "'{}' in manifest fragment '{}' must be a list.",
+                section,
+                fragment.path.display()
+            ))?;
+
+            for entry in entries {
+                let name = get_entry_name(entry).ok_or(eyre!(
+                    "{} without a name in manifest fragment '{}'.",
+                    label,
+                    fragment.path.display()
+                ))?;
+
+                if fragment.contains(section, name) {
+                    return Err(eyre!(
+                        "{} '{}' is defined more than once in '{}'.",
+                        label,
+                        name,
+                        fragment.path.display()
+                    ));
+                }
+
+                if let Some(defined_in) = find_entry_file(manifest, &fragments, section, name) {
+                    return Err(eyre!(
+                        "{} '{}' is defined in both '{}' and '{}'.",
+                        label,
+                        name,
+                        defined_in.display(),
+                        fragment.path.display()
+                    ));
+                }
+
+                fragment
+                    .entries
+                    .push((section.to_string(), name.to_string()));
+            }
+
+            get_or_create_section(manifest, section)?.extend(entries.iter().cloned());
+        }
+
+        debug!(
+            "Merged {} entries from manifest fragment '{}'.",
+            fragment.entries.len(),
+            fragment.path.display()
+        );
+        fragments.push(fragment);
+    }
+
+    // The manifest requires both lists of custom processors, even if the fragments only
+    // define one of them.
+    if !fragments.is_empty() {
+        for (section, _) in FRAGMENT_SECTIONS {
+            if section.starts_with("custom_processors.") {
+                get_or_create_section(manifest, section)?;
+            }
+        }
+    }
+
+    Ok(fragments)
+}
+
+/// Moves the entries of manifest fragments out of the manifest.
+///
+/// Entries are matched to the fragments they were loaded from by their section and name.
+/// Entries that are not part of any fragment, like newly added ones, stay in the manifest.
+///
+/// # Arguments
+///
+/// * `manifest` - The serialized manifest to split.
+/// * `fragments` - The manifest fragments the manifest was loaded with.
+///
+/// # Returns
+///
+/// The path and content of each fragment.
+pub(crate) fn split_manifest_fragments(
+    manifest: &mut Table,
+    fragments: &[ManifestFragment],
+) -> Vec<(PathBuf, Table)> {
+    fragments
+        .iter()
+        .map(|fragment| {
+            let mut fragment_table = Table::new();
+
+            for (section, _) in FRAGMENT_SECTIONS {
+                let Some(Value::Array(entries)) = get_section_mut(manifest, section) else {
+                    continue;
+                };
+
+                let (fragment_entries, manifest_entries) =
+                    entries.drain(..).partition::<Vec<_>, _>(|entry| {
+                        get_entry_name(entry).is_some_and(|name| fragment.contains(section, name))
+                    });
+                *entries = manifest_entries;
+
+                if !fragment_entries.is_empty() {
+                    set_section(&mut fragment_table, section, fragment_entries);
+                }
+            }
+
+            (fragment.path.clone(), fragment_table)
+        })
+        .collect()
+}
+
+/// Removes the sections emptied by `split_manifest_fragments` that the manifest file doesn't
+/// define, so splitting the manifest doesn't add empty sections to it.
+///
+/// # Arguments
+///
+/// * `manifest` - The split manifest.
+/// * `existing_manifest` - The manifest file as it is on disk.
+pub(crate) fn remove_empty_sections(manifest: &mut Table, existing_manifest: &Table) {
+    for (section, _) in FRAGMENT_SECTIONS {
+        let is_empty = get_section(manifest, section)
+            .and_then(|entries| entries.as_array())
+            .is_some_and(|entries| entries.is_empty());
+
+        if is_empty && get_section(existing_manifest, section).is_none() {
+            match section.split_once('.') {
+                Some((table, key)) => {
+                    if let Some(Value::Table(table)) = manifest.get_mut(table) {
+                        table.remove(key);
+                    }
+                }
+                None => {
+                    manifest.remove(section);
+                }
+            }
+        }
+    }
+
+    let is_custom_processors_empty = manifest
+        .get("custom_processors")
+        .and_then(|custom_processors| custom_processors.as_table())
+        .is_some_and(|custom_processors| custom_processors.is_empty());
+    if is_custom_processors_empty && !existing_manifest.contains_key("custom_processors") {
+        manifest.remove("custom_processors");
+    }
+}
+
+/// Resolves an include pattern to the paths of the fragment files, relative to the project
+/// directory.
+///
+/// Patterns without glob characters must point to an existing file. Glob patterns may match
+/// no files at all, and don't descend into the excluded directories below their base
+/// directory.
+fn get_fragment_paths(
+    project: &Path,
+    pattern: &str,
+    excluded_dirs: &[PathBuf],
+) -> Result<Vec<PathBuf>> {
+    let is_glob = |component: &str| component.contains(['*', '?', '[', '{']);
+
+    if !is_glob(pattern) {
+        if !project.join(pattern).is_file() {
+            return Err(eyre!(
+                "Included manifest fragment '{}' does not exist.",
+                pattern
+            ));
+        }
+
+        return Ok(vec![PathBuf::from(pattern)]);
+    }
+
+    let base_dir = pattern
+        .split('/')
+        .take_while(|component| !is_glob(component))
+        .collect::<PathBuf>();
+
+    let mut files = vec![];
+    collect_files(&project.join(&base_dir), excluded_dirs, &mut files)?;
+
+    let mut fragment_paths = files
+        .into_iter()
+        .filter_map(|file| file.strip_prefix(project).ok().map(Path::to_path_buf))
+        .filter(|file| file != Path::new("manifest.toml"))
+        .filter(|file| {
+            let file = file
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            glob_match(pattern, &file)
+        })
+        .collect::<Vec<_>>();
+    fragment_paths.sort();
+
+    debug!(
+        "Include pattern '{}' matched {} manifest fragments.",
+        pattern,
+        fragment_paths.len()
+    );
+
+    Ok(fragment_paths)
+}
+
+/// Returns the directories include globs don't descend into: the build directories, the
+/// build cache and the input directories of the markdown projects.
+fn get_excluded_dirs(project: &Path, manifest: &Table) -> Vec<PathBuf> {
+    let mut excluded_dirs = vec![project.join(BUILD_CACHE_DIRECTORY)];
+
+    if let Ok(entries) = fs::read_dir(project) {
+        excluded_dirs.extend(
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.is_dir()
+                        && path
+                            .file_name()
+                            .and_then(|name| name.to_str())
+                            .is_some_and(|name| BUILD_DIRECTORY_REGEX.is_match(name))
+                }),
+        );
+    }
+
+    let markdown_project_paths = manifest
+        .get("markdown_projects")
+        .and_then(|markdown_projects| markdown_projects.as_array())
+        .map(|markdown_projects| {
+            markdown_projects
+                .iter()
+                .filter_map(|markdown_project| markdown_project.get("path"))
+                .filter_map(|path| path.as_str())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if markdown_project_paths.is_empty() {
+        excluded_dirs.push(project.join("Markdown"));
+    } else {
+        excluded_dirs.extend(
+            markdown_project_paths
+                .into_iter()
+                .map(|path| project.join(path)),
+        );
+    }
+
+    excluded_dirs
+}
+
+fn collect_files(dir: &Path, excluded_dirs: &[PathBuf], files: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if excluded_dirs.contains(&path) {
+                debug!(
+                    "Skipping '{}' while resolving include patterns.",
+                    path.display()
+                );
+                continue;
+            }
+
+            collect_files(&path, excluded_dirs, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that a fragment only contains sections that can be included.
+fn check_fragment_keys(fragment_path: &Path, fragment: &Table) -> Result<()> {
+    let mut keys = vec![];
+
+    for (key, value) in fragment {
+        match (key.as_str(), value) {
+            ("custom_processors", Value::Table(custom_processors)) => keys.extend(
+                custom_processors
+                    .keys()
+                    .map(|key| format!("custom_processors.{}", key)),
+            ),
+            _ => keys.push(key.clone()),
+        }
+    }
+
+    if let Some(key) = keys
+        .iter()
+        .find(|key| !FRAGMENT_SECTIONS.iter().any(|(section, _)| section == key))
+    {
+        return Err(eyre!(
+            "Manifest fragment '{}' defines '{}', but fragments can only define templates, markdown projects, profiles, injections and custom processors.",
+            fragment_path.display(),
+            key
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that the names of the entries in each section of the manifest itself are unique.
+fn check_duplicate_names(manifest: &Table) -> Result<()> {
+    for (section, label) in FRAGMENT_SECTIONS {
+        let Some(entries) = get_section(manifest, section).and_then(|entries| entries.as_array())
+        else {
+            continue;
+        };
+
+        let mut names = vec![];
+        for name in entries.iter().filter_map(get_entry_name) {
+            if names.contains(&name) {
+                return Err(eyre!(
+                    "{} '{}' is defined more than once in 'manifest.toml'.",
+                    label,
+                    name
+                ));
+            }
+            names.push(name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds the file an entry is already defined in, if any.
+fn find_entry_file(
+    manifest: &Table,
+    fragments: &[ManifestFragment],
+    section: &str,
+    name: &str,
+) -> Option<PathBuf> {
+    if let Some(fragment) = fragments
+        .iter()
+        .find(|fragment| fragment.contains(section, name))
+    {
+        return Some(fragment.path.clone());
+    }
+
+    get_section(manifest, section)
+        .and_then(|entries| entries.as_array())
+        .is_some_and(|entries| {
+            entries
+                .iter()
+                .any(|entry| get_entry_name(entry) == Some(name))
+        })
+        .then(|| PathBuf::from("manifest.toml"))
+}
+
+fn get_entry_name(entry: &Value) -> Option<&str> {
+    entry.get("name").and_then(|name| name.as_str())
+}
+
+fn get_section<'a>(table: &'a Table, section: &str) -> Option<&'a Value> {
+    match section.split_once('.') {
+        Some((table_key, key)) => table.get(table_key)?.as_table()?.get(key),
+        None => table.get(section),
+    }
+}
+
+fn set_section(table: &mut Table, section: &str, entries: Vec<Value>) {
+    match section.split_once('.') {
+        Some((table_key, key)) => {
+            if let Value::Table(table) = table
+                .entry(table_key)
+                .or_insert_with(|| Value::Table(Table::new()))
+            {
+                table.insert(key.to_string(), Value::Array(entries));
+            }
+        }
+        None => {
+            table.insert(section.to_string(), Value::Array(entries));
+        }
+    }
+}
+
+fn get_section_mut<'a>(table: &'a mut Table, section: &str) -> Option<&'a mut Value> {
+    match section.split_once('.') {
+        Some((table_key, key)) => table.get_mut(table_key)?.as_table_mut()?.get_mut(key),
+        None => table.get_mut(section),
+    }
+}
+
+/// Returns the entries of a section, creating the section if needed.
+fn get_or_create_section<'a>(table: &'a mut Table, section: &str) -> Result<&'a mut Vec<Value>> {
+    let (table, key) = match section.split_once('.') {
+        Some((table_key, key)) => (
+            table
+                .entry(table_key)
+                .or_insert_with(|| Value::Table(Table::new()))
+                .as_table_mut()
+                .ok_or(eyre!("'{}' in the manifest must be a table.", table_key))?,
+            key,
+        ),
+        None => (table, section),
+    };
+
+    table
+        .entry(key)
+        .or_insert_with(|| Value::Array(vec![]))
+        .as_array_mut()
+        .ok_or(eyre!("'{}' in the manifest must be a list.", section))
+}
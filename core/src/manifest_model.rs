@@ -19,10 +19,15 @@ use toml::Table;
 /// * `custom_processors` - The custom processors and preprocessors available to the project.
 /// * `smart_clean` - A flag indicating whether to enable smart clean on conversion.
 /// * `smart_clean_threshold` - The threshold for smart clean.
+/// * `build_cache` - A flag indicating whether to reuse unchanged conversion results from previous builds.
 /// * `shared_metadata` - Metadata for the project shared across markdown projects.
 /// * `metadata_settings` - Metadata settings for the project.
 /// * `profiles` - A list of profiles for the project.
 /// * `injections` - A list of injections available to the conversion process that may be injected into a template.
+/// * `include` - Optional paths or glob patterns of manifest fragments, relative to the project directory.
+///   * Fragments can define templates, markdown projects, profiles, injections and custom processors.
+/// * `fragments` - The manifest fragments the manifest was loaded with.
+///   * Set by `load_and_convert_manifest`, used to write entries back to the fragment they were loaded from.
 #[derive(Deserialize, Serialize)]
 pub struct Manifest {
     pub version: u32,
@@ -31,10 +36,66 @@ pub struct Manifest {
     pub custom_processors: Processors,
     pub smart_clean: Option<bool>,
     pub smart_clean_threshold: Option<u32>,
+    pub build_cache: Option<bool>,
     pub shared_metadata: Option<Table>,
     pub metadata_settings: Option<MetadataSettings>,
     pub profiles: Option<Vec<Profile>>,
     pub injections: Option<Vec<Injection>>,
+    pub include: Option<Vec<String>>,
+    #[serde(skip)]
+    pub fragments: Vec<ManifestFragment>,
+}
+
+impl Manifest {
+    /// Returns the manifest fragment an entry of the manifest was loaded from.
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The section of the entry, e.g. `templates` or `custom_processors.preprocessors`.
+    /// * `name` - The name of the entry.
+    ///
+    /// # Returns
+    ///
+    /// The manifest fragment, or None if the entry is defined in the manifest itself.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tiefdownlib::project_management::load_and_convert_manifest;
+    /// use std::path::PathBuf;
+    ///
+    /// let manifest = load_and_convert_manifest(&PathBuf::from("my_project/manifest.toml")).unwrap();
+    /// if let Some(fragment) = manifest.get_fragment("templates", "PDF Documentation LaTeX") {
+    ///     println!("Defined in {}", fragment.path.display());
+    /// }
+    /// ```
+    pub fn get_fragment(&self, section: &str, name: &str) -> Option<&ManifestFragment> {
+        self.fragments
+            .iter()
+            .find(|fragment| fragment.contains(section, name))
+    }
+}
+
+/// Represents a manifest fragment, a TOML file pulled into the manifest by its `include` key.
+///
+/// # Fields
+///
+/// * `path` - The path to the fragment file, relative to the project directory.
+/// * `entries` - The entries defined in the fragment, as pairs of section and name.
+///   * For example `("templates", "PDF")` or `("custom_processors.processors", "Listings")`.
+#[derive(Clone, Default)]
+pub struct ManifestFragment {
+    pub path: PathBuf,
+    pub entries: Vec<(String, String)>,
+}
+
+impl ManifestFragment {
+    /// Checks whether an entry is defined in the fragment.
+    pub fn contains(&self, section: &str, name: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|(entry_section, entry_name)| entry_section == section && entry_name == name)
+    }
 }
 
 /// Represents a markdown project in a TiefDown project.
@@ -49,6 +110,9 @@ pub struct Manifest {
 /// * `metadata_fields` - Optional metadata fields specific to the markdown project.
 /// * `default_profile` - The name of the default profile to use for conversion.
 /// * `resources` - Optional resources to be copied alongside the combined markdown file.
+/// * `include` - Optional gitignore-style patterns of the files to convert. If set, only matching files are converted.
+/// * `exclude` - Optional gitignore-style patterns of the files not to convert, applied before the `.tiefdownignore` file.
+/// * `bibliography` - Optional bibliography settings. Paths are relative to the markdown directory and copied like resources.
 #[derive(Deserialize, Serialize, Clone)]
 pub struct MarkdownProject {
     pub name: String,
@@ -57,6 +121,9 @@ pub struct MarkdownProject {
     pub metadata_fields: Option<Table>,
     pub default_profile: Option<String>,
     pub resources: Option<Vec<PathBuf>>,
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+    pub bibliography: Option<BibliographySettings>,
 }
 
 /// Represents a metadata field when retrieving the metadata. This is not used for storing metadata.
@@ -218,9 +285,13 @@ pub static DEFAULT_CUSTOM_PROCESSOR_PREPROCESSORS: LazyLock<(PreProcessors, Vec<
 ///
 /// * `metadata_prefix` - The prefix to use for metadata fields.
 ///   * This is used for defining the LaTeX macro and the name of the typst object containing the metadata.
+/// * `raw_fields` - The metadata fields that contain raw LaTeX or Typst markup.
+///   * These fields are written to the generated metadata files without escaping.
+///   * Nested fields are given by their dotted path, e.g. `series.name`.
 #[derive(Deserialize, Serialize, Clone)]
 pub struct MetadataSettings {
     pub metadata_prefix: Option<String>,
+    pub raw_fields: Option<Vec<String>>,
 }
 
 impl MetadataSettings {
@@ -237,8 +308,21 @@ impl MetadataSettings {
     pub fn default() -> Self {
         Self {
             metadata_prefix: None,
+            raw_fields: None,
         }
     }
+
+    /// Checks whether a metadata field, given by its dotted path, or one of its parents is raw.
+    pub(crate) fn is_raw_field(&self, path: &str) -> bool {
+        self.raw_fields.as_ref().is_some_and(|raw_fields| {
+            raw_fields.iter().any(|field| {
+                path == field
+                    || path
+                        .strip_prefix(field.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+        })
+    }
 }
 
 /// Represents a conversion profile for the project.
@@ -249,10 +333,12 @@ impl MetadataSettings {
 ///
 /// * `name` - The name of the profile.
 /// * `templates` - A list of templates to use for conversion.
+/// * `metadata_fields` - Optional metadata fields used when converting with the profile.
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Profile {
     pub name: String,
     pub templates: Vec<String>,
+    pub metadata_fields: Option<Table>,
 }
 
 /// Represents a template in the project.
@@ -271,6 +357,10 @@ pub struct Profile {
 /// * `body_injections` - Link to the body injections of the template
 /// * `footer_injections` - Link to the footer injections of the template
 /// * `multi_file_output` - Enables multi-file generation
+/// * `latex` - Settings for LaTeX compilation, only used by LaTeX templates
+/// * `metadata_fields` - Optional metadata fields used when converting with the template.
+/// * `bibliography` - Optional bibliography settings. Paths are relative to the template directory.
+///   * Merged with the bibliography settings of the markdown project, taking precedence.
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Template {
     pub name: String,
@@ -285,6 +375,88 @@ pub struct Template {
     pub footer_injections: Option<Vec<String>>,
     pub multi_file_output: Option<bool>,
     pub meta_gen: Option<MetaGenerationSettings>,
+    pub latex: Option<LatexSettings>,
+    pub metadata_fields: Option<Table>,
+    pub bibliography: Option<BibliographySettings>,
+}
+
+/// The bibliography settings of a markdown project or template.
+///
+/// # Fields
+///
+/// * `files` - The bibliography files, e.g. BibTeX or CSL JSON files.
+/// * `csl` - The CSL style file to format citations and the bibliography with.
+///   * Only used by the `Citeproc` mode.
+/// * `mode` - How citations are processed.
+///   * Defaults to `Citeproc`.
+/// * `link_citations` - Whether citations link to their bibliography entry.
+///   * Only used by the `Citeproc` mode.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Default)]
+pub struct BibliographySettings {
+    pub files: Option<Vec<PathBuf>>,
+    pub csl: Option<PathBuf>,
+    pub mode: Option<CitationMode>,
+    pub link_citations: Option<bool>,
+}
+
+/// How citations are processed.
+///
+/// * `Citeproc` - Pandoc resolves the citations and generates the bibliography.
+/// * `Biblatex` - Pandoc writes biblatex citation commands, which are resolved when compiling
+///   the LaTeX template. Only supported by Tex templates.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CitationMode {
+    #[default]
+    Citeproc,
+    Biblatex,
+}
+
+/// The settings to use for LaTeX compilation.
+///
+/// # Fields
+///
+/// * `engine` - The LaTeX engine to compile the template with.
+///   * Defaults to `Xelatex`.
+/// * `latexmk` - Whether to run the engine through latexmk.
+///   * latexmk decides on the passes and runs the bibliography and index tools itself, so
+///     `max_passes` and `tools` are ignored.
+/// * `max_passes` - The maximum number of LaTeX passes.
+///   * LaTeX is rerun while the log asks for it or the table of contents and similar files change.
+///   * Defaults to 5.
+/// * `tools` - The tools to run between the first and second LaTeX pass, in order.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct LatexSettings {
+    pub engine: Option<LatexEngine>,
+    pub latexmk: Option<bool>,
+    pub max_passes: Option<u32>,
+    pub tools: Option<Vec<LatexTool>>,
+}
+
+/// A LaTeX engine.
+///
+/// * `Xelatex` - Compiles with XeLaTeX.
+/// * `Lualatex` - Compiles with LuaLaTeX.
+/// * `Pdflatex` - Compiles with pdfLaTeX.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LatexEngine {
+    #[default]
+    Xelatex,
+    Lualatex,
+    Pdflatex,
+}
+
+/// A tool run between LaTeX passes.
+///
+/// * `Biber` - Processes the bibliography for biblatex.
+/// * `Bibtex` - Processes the bibliography for BibTeX.
+/// * `Makeindex` - Generates the index.
+/// * `Makeglossaries` - Generates the glossaries.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LatexTool {
+    Biber,
+    Bibtex,
+    Makeindex,
+    Makeglossaries,
 }
 
 /// The settings to use for navigation metadata generation
@@ -292,6 +464,7 @@ pub struct Template {
 /// # Fields
 ///
 /// * `feature` - The navigation metadata complexity
+/// * `nav_title_source` - Where the titles of the navigation nodes are taken from
 ///
 #[derive(Deserialize, Serialize, Clone)]
 pub struct MetaGenerationSettings {
@@ -299,6 +472,20 @@ pub struct MetaGenerationSettings {
     pub format: Option<MetaGenerationFormat>,
     pub nav_output: Option<PathBuf>,
     pub metadata_output: Option<PathBuf>,
+    pub nav_title_source: Option<NavTitleSource>,
+}
+
+/// The source of the titles of navigation nodes.
+///
+/// A title in the front matter of a file always takes precedence.
+///
+/// * `Content` - The first heading of the file or, if there is none, the file stem.
+/// * `Path` - The path of the file without its extension.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum NavTitleSource {
+    #[default]
+    Content,
+    Path,
 }
 
 /// Represents an injection into the document.
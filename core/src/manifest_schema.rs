@@ -0,0 +1,362 @@
+use color_eyre::eyre::{Result, eyre};
+use log::debug;
+use serde::de::{
+    self, Deserialize, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess,
+    Visitor, value::Error,
+};
+use serde_json::{Map, Value, json};
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{consts::CURRENT_MANIFEST_VERSION, manifest_model::Manifest};
+
+/// The URI of the JSON Schema dialect of the manifest schema.
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Generates a JSON Schema of `manifest.toml` for the current manifest version.
+///
+/// The schema is derived from the manifest model, so it always lists the fields the
+/// current version of TiefDownLib reads. TOML-aware editors can use it to autocomplete and
+/// validate manifests.
+///
+/// # Returns
+///
+/// A Result containing either an error or the JSON Schema as pretty-printed JSON.
+///
+/// # Examples
+///
+/// ```
+/// use tiefdownlib::manifest_schema::get_manifest_schema;
+///
+/// let schema = get_manifest_schema().unwrap();
+/// assert!(schema.contains("\"templates\""));
+/// ```
+pub fn get_manifest_schema() -> Result<String> {
+    let mut definitions = BTreeMap::new();
+    let mut trace = Trace::default();
+
+    Manifest::deserialize(SchemaDeserializer {
+        definitions: &mut definitions,
+        trace: &mut trace,
+    })
+    .map_err(|e| eyre!("Failed to generate the manifest schema: {}", e))?;
+
+    let Some(Value::Object(mut schema)) = definitions.remove("Manifest") else {
+        return Err(eyre!(
+            "Failed to generate the manifest schema: The manifest is not a struct."
+        ));
+    };
+
+    if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+        properties.insert(
+            "version".to_string(),
+            json!({ "type": "integer", "const": CURRENT_MANIFEST_VERSION }),
+        );
+    }
+
+    let mut root = Map::new();
+    root.insert("$schema".to_string(), json!(JSON_SCHEMA_DIALECT));
+    root.insert("title".to_string(), json!("TiefDown manifest"));
+    root.insert(
+        "description".to_string(),
+        json!(format!(
+            "The manifest.toml of a TiefDown project, manifest version {}.",
+            CURRENT_MANIFEST_VERSION
+        )),
+    );
+    root.extend(schema);
+    root.insert("$defs".to_string(), json!(definitions));
+
+    debug!(
+        "Generated manifest schema with {} definitions.",
+        definitions.len()
+    );
+
+    Ok(serde_json::to_string_pretty(&Value::Object(root))? + "\n")
+}
+
+/// Writes the JSON Schema of `manifest.toml` to a file.
+///
+/// # Arguments
+///
+/// * `path` - The path to write the schema to. An existing file is overwritten.
+///
+/// # Returns
+///
+/// A Result containing either an error or nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::manifest_schema::write_manifest_schema;
+/// use std::path::Path;
+///
+/// write_manifest_schema(Path::new("manifest.schema.json")).unwrap();
+/// ```
+pub fn write_manifest_schema(path: &Path) -> Result<()> {
+    fs::write(path, get_manifest_schema()?)?;
+    debug!("Wrote manifest schema to '{}'.", path.display());
+
+    Ok(())
+}
+
+/// The schema of a single value, recorded while it is deserialized.
+#[derive(Default)]
+struct Trace {
+    schema: Option<Value>,
+    optional: bool,
+}
+
+/// A deserializer that records the schema of the type deserialized from it.
+///
+/// Every `deserialize_*` call records the JSON Schema of the requested type and hands the
+/// visitor a placeholder value. Structs and enums are recorded as definitions, so the
+/// schema follows the serde model of the manifest.
+struct SchemaDeserializer<'a> {
+    definitions: &'a mut BTreeMap<String, Value>,
+    trace: &'a mut Trace,
+}
+
+macro_rules! deserialize_primitive {
+    ($method:ident, $visit:ident, $value:expr, $schema:tt) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            self.trace.schema = Some(json!($schema));
+            visitor.$visit($value)
+        }
+    };
+}
+
+impl<'de> Deserializer<'de> for SchemaDeserializer<'_> {
+    type Error = Error;
+
+    deserialize_primitive!(deserialize_bool, visit_bool, false, { "type": "boolean" });
+    deserialize_primitive!(deserialize_i8, visit_i8, 0, { "type": "integer" });
+    deserialize_primitive!(deserialize_i16, visit_i16, 0, { "type": "integer" });
+    deserialize_primitive!(deserialize_i32, visit_i32, 0, { "type": "integer" });
+    deserialize_primitive!(deserialize_i64, visit_i64, 0, { "type": "integer" });
+    deserialize_primitive!(deserialize_u8, visit_u8, 0, { "type": "integer", "minimum": 0 });
+    deserialize_primitive!(deserialize_u16, visit_u16, 0, { "type": "integer", "minimum": 0 });
+    deserialize_primitive!(deserialize_u32, visit_u32, 0, { "type": "integer", "minimum": 0 });
+    deserialize_primitive!(deserialize_u64, visit_u64, 0, { "type": "integer", "minimum": 0 });
+    deserialize_primitive!(deserialize_f32, visit_f32, 0.0, { "type": "number" });
+    deserialize_primitive!(deserialize_f64, visit_f64, 0.0, { "type": "number" });
+    deserialize_primitive!(deserialize_char, visit_char, ' ', { "type": "string" });
+    deserialize_primitive!(deserialize_str, visit_str, "", { "type": "string" });
+    deserialize_primitive!(deserialize_string, visit_str, "", { "type": "string" });
+    deserialize_primitive!(deserialize_identifier, visit_str, "", { "type": "string" });
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.trace.schema = Some(json!({}));
+        visitor.visit_unit()
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.trace.optional = true;
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        Err(de::Error::custom(format!(
+            "{} is not supported in the manifest",
+            visitor_name(&visitor)
+        )))
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut items = Trace::default();
+        let value = visitor.visit_seq(SchemaSeqAccess {
+            definitions: self.definitions,
+            trace: Some(&mut items),
+        })?;
+
+        self.trace.schema = Some(json!({
+            "type": "array",
+            "items": items.schema.unwrap_or(json!({})),
+        }));
+
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    /// Maps are free-form tables, like metadata fields.
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.trace.schema = Some(json!({ "type": "object" }));
+        visitor.visit_map(SchemaStructAccess {
+            definitions: self.definitions,
+            fields: &[],
+            properties: Map::new(),
+            required: vec![],
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut access = SchemaStructAccess {
+            definitions: self.definitions,
+            fields,
+            properties: Map::new(),
+            required: vec![],
+        };
+        let value = visitor.visit_map(&mut access)?;
+
+        // Unknown keys are not forbidden, as the manifest loader accepts and keeps them.
+        let mut definition = json!({
+            "type": "object",
+            "properties": access.properties,
+        });
+        if !access.required.is_empty() {
+            definition["required"] = json!(access.required);
+        }
+
+        self.definitions.insert(name.to_string(), definition);
+        self.trace.schema = Some(json!({ "$ref": format!("#/$defs/{}", name) }));
+
+        Ok(value)
+    }
+
+    /// Only unit variants are supported, which are written as strings.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let Some(variant) = variants.first() else {
+            return Err(de::Error::custom(format!("enum {} has no variants", name)));
+        };
+
+        self.definitions.insert(
+            name.to_string(),
+            json!({ "type": "string", "enum": variants }),
+        );
+        self.trace.schema = Some(json!({ "$ref": format!("#/$defs/{}", name) }));
+
+        visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(*variant))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+}
+
+/// Yields a single element, recording its schema as the schema of the array items.
+struct SchemaSeqAccess<'a, 'b> {
+    definitions: &'a mut BTreeMap<String, Value>,
+    trace: Option<&'b mut Trace>,
+}
+
+impl<'de> SeqAccess<'de> for SchemaSeqAccess<'_, '_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        let Some(trace) = self.trace.take() else {
+            return Ok(None);
+        };
+
+        seed.deserialize(SchemaDeserializer {
+            definitions: self.definitions,
+            trace,
+        })
+        .map(Some)
+    }
+}
+
+/// Yields every field of a struct, recording the schema of each as a property.
+struct SchemaStructAccess<'a> {
+    definitions: &'a mut BTreeMap<String, Value>,
+    fields: &'static [&'static str],
+    properties: Map<String, Value>,
+    required: Vec<&'static str>,
+}
+
+impl<'de> MapAccess<'de> for SchemaStructAccess<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.fields.get(self.properties.len()) {
+            Some(field) => seed
+                .deserialize(IntoDeserializer::<Error>::into_deserializer(*field))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let field = self.fields[self.properties.len()];
+        let mut trace = Trace::default();
+
+        let value = seed.deserialize(SchemaDeserializer {
+            definitions: self.definitions,
+            trace: &mut trace,
+        })?;
+
+        if !trace.optional {
+            self.required.push(field);
+        }
+        self.properties
+            .insert(field.to_string(), trace.schema.unwrap_or(json!({})));
+
+        Ok(value)
+    }
+}
+
+fn visitor_name<'de, V: Visitor<'de>>(visitor: &V) -> String {
+    struct Expected<'a, V>(&'a V);
+
+    impl<'de, V: Visitor<'de>> std::fmt::Display for Expected<'_, V> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.0.expecting(f)
+        }
+    }
+
+    Expected(visitor).to_string()
+}
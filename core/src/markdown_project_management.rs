@@ -1,6 +1,10 @@
 use crate::{
+    file_retrieval::get_file_order_warnings,
+    input_filter::InputFilter,
     manifest_model::{MarkdownProject, MetadataField},
-    project_management::load_and_convert_manifest,
+    metadata_format::MetadataFormat,
+    metadata_management::{apply_metadata_import, read_metadata_file, write_metadata_file},
+    project_management::{load_and_convert_manifest, save_manifest},
 };
 use color_eyre::eyre::{Result, eyre};
 use log::debug;
@@ -16,6 +20,8 @@ use toml::{Table, Value};
 /// * `path` - The path to the markdown directory.
 /// * `output` - The path to the output directory.
 /// * `default_profile` - The name of the default profile to use for conversion.
+/// * `include` - Gitignore-style patterns of the files to convert.
+/// * `exclude` - Gitignore-style patterns of the files not to convert.
 ///
 /// # Returns
 ///
@@ -33,6 +39,8 @@ use toml::{Table, Value};
 ///     PathBuf::from("Markdown/Chapter1"),
 ///     PathBuf::from("output/chapter1"),
 ///     None,
+///     None,
+///     Some(vec!["*.swp".to_string(), "notes/".to_string()]),
 /// ).unwrap();
 /// ```
 pub fn add_markdown_project(
@@ -41,6 +49,8 @@ pub fn add_markdown_project(
     path: PathBuf,
     output: PathBuf,
     default_profile: Option<String>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
 ) -> Result<()> {
     debug!(
         "Adding markdown project '{}' (path='{}', output='{}')",
@@ -69,12 +79,14 @@ pub fn add_markdown_project(
         metadata_fields: None,
         default_profile,
         resources: None,
+        include,
+        exclude,
+        bibliography: None,
     });
 
     manifest.markdown_projects = Some(markdown_projects);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("Markdown project '{}' added.", name);
 
     Ok(())
@@ -120,8 +132,7 @@ pub fn remove_markdown_project(project: Option<PathBuf>, name: String) -> Result
 
     manifest.markdown_projects = Some(markdown_projects);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("Markdown project '{}' removed.", name);
 
     Ok(())
@@ -137,6 +148,8 @@ pub fn remove_markdown_project(project: Option<PathBuf>, name: String) -> Result
 /// * `path` - The new path to the markdown directory.
 /// * `output` - The new path to the output directory.
 /// * `default_profile` - The new name of the default profile to use for conversion.
+/// * `include` - The new gitignore-style patterns of the files to convert.
+/// * `exclude` - The new gitignore-style patterns of the files not to convert.
 ///
 /// # Returns
 ///
@@ -154,6 +167,8 @@ pub fn remove_markdown_project(project: Option<PathBuf>, name: String) -> Result
 ///     Some(PathBuf::from("Markdown/NewChapter1")),
 ///     None,
 ///     None,
+///     None,
+///     None,
 /// ).unwrap();
 /// ```
 pub fn update_markdown_project(
@@ -162,6 +177,8 @@ pub fn update_markdown_project(
     path: Option<PathBuf>,
     output: Option<PathBuf>,
     default_profile: Option<String>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
 ) -> Result<()> {
     debug!(
         "Updating markdown project '{}' (path={:?}, output={:?}, default_profile={:?})",
@@ -191,10 +208,17 @@ pub fn update_markdown_project(
         project.default_profile = Some(default_profile);
     }
 
+    if let Some(include) = include {
+        project.include = Some(include);
+    }
+
+    if let Some(exclude) = exclude {
+        project.exclude = Some(exclude);
+    }
+
     manifest.markdown_projects = Some(markdown_projects);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("Markdown project '{}' updated.", name);
 
     Ok(())
@@ -253,8 +277,7 @@ pub fn set_metadata(
 
     manifest.markdown_projects = Some(markdown_projects);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("markdown.set_metadata: updated manifest for '{}'", name);
 
     Ok(())
@@ -313,8 +336,7 @@ pub fn remove_metadata(project: Option<PathBuf>, name: String, key: String) -> R
 
     manifest.markdown_projects = Some(markdown_projects);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("markdown.remove_metadata: updated manifest for '{}'", name);
 
     Ok(())
@@ -372,6 +394,140 @@ pub fn get_metadata(project: Option<PathBuf>, name: &String) -> Result<Vec<Metad
     Ok(result)
 }
 
+/// Imports the metadata fields of a markdown project from a JSON, YAML or TOML file.
+///
+/// The file must contain a single table (or object) of metadata fields. `null` values are
+/// skipped.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `name` - The name of the markdown project.
+/// * `path` - The path to the file to import, relative to the current directory.
+/// * `format` - The format of the file.
+///   * Determined from the file extension if not provided.
+/// * `replace` - Whether to replace the metadata fields instead of merging into them.
+///   * When merging, imported fields override existing fields and nested tables are merged.
+///
+/// # Returns
+///
+/// A Result containing either an error or nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::markdown_project_management::import_metadata;
+/// use std::path::PathBuf;
+///
+/// import_metadata(
+///     Some(PathBuf::from("my_project")),
+///     "chapter1".to_string(),
+///     PathBuf::from("metadata.json"),
+///     None,
+///     false,
+/// ).unwrap();
+/// ```
+pub fn import_metadata(
+    project: Option<PathBuf>,
+    name: String,
+    path: PathBuf,
+    format: Option<MetadataFormat>,
+    replace: bool,
+) -> Result<()> {
+    debug!(
+        "markdown.import_metadata: project='{}' path='{}'",
+        name,
+        path.display()
+    );
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest_path = project.join("manifest.toml");
+
+    let mut manifest = load_and_convert_manifest(&manifest_path)?;
+
+    let mut markdown_projects = manifest.markdown_projects.unwrap_or(vec![]);
+
+    let project = markdown_projects
+        .iter_mut()
+        .find(|p| p.name == name)
+        .ok_or_else(|| eyre!("Markdown project with name '{}' does not exist.", name))?;
+
+    let imported_metadata = read_metadata_file(&path, format)?;
+
+    apply_metadata_import(
+        project.metadata_fields.get_or_insert_with(Table::new),
+        imported_metadata,
+        replace,
+    );
+
+    manifest.markdown_projects = Some(markdown_projects);
+
+    save_manifest(&manifest_path, &manifest)?;
+    debug!("markdown.import_metadata: updated manifest for '{}'", name);
+
+    Ok(())
+}
+
+/// Exports the metadata fields of a markdown project to a JSON, YAML or TOML file.
+///
+/// Dates are exported as strings to JSON and YAML files.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `name` - The name of the markdown project.
+/// * `path` - The path to the file to write, relative to the current directory.
+///   * An existing file is overwritten.
+/// * `format` - The format of the file.
+///   * Determined from the file extension if not provided.
+///
+/// # Returns
+///
+/// A Result containing either an error or nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::markdown_project_management::export_metadata;
+/// use std::path::PathBuf;
+///
+/// export_metadata(
+///     Some(PathBuf::from("my_project")),
+///     "chapter1".to_string(),
+///     PathBuf::from("metadata.yaml"),
+///     None,
+/// ).unwrap();
+/// ```
+pub fn export_metadata(
+    project: Option<PathBuf>,
+    name: String,
+    path: PathBuf,
+    format: Option<MetadataFormat>,
+) -> Result<()> {
+    debug!(
+        "markdown.export_metadata: project='{}' path='{}'",
+        name,
+        path.display()
+    );
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest_path = project.join("manifest.toml");
+
+    let manifest = load_and_convert_manifest(&manifest_path)?;
+    let markdown_projects = manifest.markdown_projects.unwrap_or(vec![]);
+
+    let project = markdown_projects
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| eyre!("Markdown project with name '{}' does not exist.", name))?;
+
+    write_metadata_file(
+        &path,
+        format,
+        &project.metadata_fields.clone().unwrap_or_default(),
+    )
+}
+
 /// Gets the markdown projects.
 ///
 /// # Arguments
@@ -465,8 +621,7 @@ pub fn add_resources(
 
     manifest.markdown_projects = Some(markdown_projects);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("markdown.add_resources: updated manifest for '{}'", name);
 
     Ok(())
@@ -529,8 +684,7 @@ pub fn remove_resource(project: Option<PathBuf>, name: String, resource: PathBuf
 
     manifest.markdown_projects = Some(markdown_projects);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("markdown.remove_resource: updated manifest for '{}'", name);
 
     Ok(())
@@ -577,3 +731,63 @@ pub fn get_resources(project: Option<PathBuf>, name: &String) -> Result<Vec<Path
     debug!("markdown.get_resources: {} entries", res.len());
     Ok(res)
 }
+
+/// Checks the order of the files of a markdown project.
+///
+/// Directories with an ordering file (`order.toml`) are checked for unlisted files, listed files
+/// that do not exist and duplicate entries. Directories without one are checked for files without
+/// an order number, files with more than one number in their name and files sharing an order
+/// number. Resources and files excluded by the input filter are not checked.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `name` - The name of the markdown project to check.
+///
+/// # Returns
+///
+/// A Result containing either an error or a Vec of warnings.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::markdown_project_management::lint_file_order;
+/// use std::path::PathBuf;
+///
+/// let warnings = lint_file_order(Some(PathBuf::from("my_project")), "Markdown").unwrap();
+/// for warning in warnings {
+///     println!("{}", warning);
+/// }
+/// ```
+pub fn lint_file_order(project: Option<PathBuf>, name: &str) -> Result<Vec<String>> {
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest_path = project.join("manifest.toml");
+
+    let manifest = load_and_convert_manifest(&manifest_path)?;
+
+    let markdown_projects = manifest.markdown_projects.unwrap_or(vec![]);
+
+    let markdown_project = markdown_projects
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| eyre!("Markdown project with name '{}' does not exist.", name))?;
+
+    let markdown_dir = project.join(&markdown_project.path);
+
+    if !markdown_dir.is_dir() {
+        return Err(eyre!(
+            "Markdown directory '{}' does not exist.",
+            markdown_dir.display()
+        ));
+    }
+
+    let warnings = get_file_order_warnings(
+        &markdown_dir,
+        &markdown_project.resources.clone().unwrap_or(vec![]),
+        &InputFilter::new(markdown_project, &markdown_dir)?,
+    )?;
+
+    debug!("markdown.lint_file_order: {} warnings", warnings.len());
+    Ok(warnings)
+}
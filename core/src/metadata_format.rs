@@ -0,0 +1,109 @@
+use color_eyre::eyre::{self, Result, eyre};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+    str::FromStr,
+};
+
+/// File format for importing and exporting metadata
+///
+/// # Examples
+///
+/// ```
+/// use tiefdownlib::metadata_format::MetadataFormat;
+/// use std::str::FromStr;
+///
+/// let f = MetadataFormat::from_str("yaml").unwrap();
+/// assert_eq!(f, MetadataFormat::Yaml);
+/// assert_eq!(f.to_string(), "Yaml");
+/// ```
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetadataFormat {
+    Json = 0,
+    Yaml = 1,
+    Toml = 2,
+}
+
+impl FromStr for MetadataFormat {
+    type Err = eyre::Report;
+
+    /// Parses a string slice into a `MetadataFormat`.
+    ///
+    /// Case-insensitive. `yml` is accepted as an alias for `yaml`. Returns an error for
+    /// unrecognized values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tiefdownlib::metadata_format::MetadataFormat;
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(MetadataFormat::from_str("yml").unwrap(), MetadataFormat::Yaml);
+    /// assert!(MetadataFormat::from_str("xml").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(MetadataFormat::Json),
+            "yaml" | "yml" => Ok(MetadataFormat::Yaml),
+            "toml" => Ok(MetadataFormat::Toml),
+            _ => Err(eyre!("Invalid metadata format: {}", s)),
+        }
+    }
+}
+
+impl MetadataFormat {
+    /// Determines the metadata format from the extension of a file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tiefdownlib::metadata_format::MetadataFormat;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(
+    ///     MetadataFormat::from_path(Path::new("metadata.json")).unwrap(),
+    ///     MetadataFormat::Json
+    /// );
+    /// assert!(MetadataFormat::from_path(Path::new("metadata.csv")).is_err());
+    /// ```
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .ok_or(eyre!(
+                "Cannot determine the metadata format of '{}'. Please specify the format.",
+                path.display()
+            ))?;
+
+        extension.parse().map_err(|_| {
+            eyre!(
+                "Unknown metadata file extension '{}'. Please specify the format.",
+                extension
+            )
+        })
+    }
+
+    /// Returns the canonical string name of this format variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tiefdownlib::metadata_format::MetadataFormat;
+    ///
+    /// assert_eq!(MetadataFormat::Toml.as_str(), "Toml");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetadataFormat::Json => "Json",
+            MetadataFormat::Yaml => "Yaml",
+            MetadataFormat::Toml => "Toml",
+        }
+    }
+}
+
+impl Display for MetadataFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
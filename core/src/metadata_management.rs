@@ -1,10 +1,62 @@
-use std::path::PathBuf;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
-use crate::{manifest_model::MetadataField, project_management::load_and_convert_manifest};
+use crate::{
+    conversion::get_markdown_projects,
+    converters::common::normalize_metadata,
+    manifest_model::{Manifest, MarkdownProject, MetadataField, Template},
+    metadata_format::MetadataFormat,
+    project_management::{load_and_convert_manifest, save_manifest},
+};
 use color_eyre::eyre::{Result, eyre};
 use log::debug;
 use toml::{Table, Value};
 
+/// A layer of metadata. When resolving metadata, the layers are merged in the order of this
+/// enum, each layer overriding the ones before it.
+///
+/// * `Shared` - The shared metadata of the project.
+/// * `Profile` - The metadata of the conversion profile.
+/// * `Template` - The metadata of the template.
+/// * `MarkdownProject` - The metadata of the markdown project.
+/// * `Override` - Metadata overrides given on the command line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MetadataLayer {
+    Shared,
+    Profile(String),
+    Template(String),
+    MarkdownProject(String),
+    Override,
+}
+
+impl Display for MetadataLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetadataLayer::Shared => write!(f, "shared metadata"),
+            MetadataLayer::Profile(name) => write!(f, "profile '{}'", name),
+            MetadataLayer::Template(name) => write!(f, "template '{}'", name),
+            MetadataLayer::MarkdownProject(name) => write!(f, "markdown project '{}'", name),
+            MetadataLayer::Override => write!(f, "override"),
+        }
+    }
+}
+
+/// A metadata field after resolving all metadata layers.
+///
+/// # Fields
+///
+/// * `key` - The dotted path of the field, e.g. `series.name` for nested tables.
+/// * `value` - The effective value of the field.
+/// * `layer` - The layer the effective value comes from.
+#[derive(Clone, Debug)]
+pub struct ResolvedMetadataField {
+    pub key: String,
+    pub value: Value,
+    pub layer: MetadataLayer,
+}
+
 /// Sets the shared metadata fields for a TiefDown project.
 ///
 /// # Arguments
@@ -42,8 +94,7 @@ pub fn set_metadata(project: Option<PathBuf>, key: String, value: String) -> Res
         .get_or_insert_with(&mut || Table::new())
         .insert(key, Value::String(value));
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!(
         "metadata.set: updated manifest at '{}'",
         manifest_path.display()
@@ -90,8 +141,7 @@ pub fn remove_metadata(project: Option<PathBuf>, key: String) -> Result<()> {
 
     shared_metadata.remove(&key);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!(
         "metadata.remove: updated manifest at '{}'",
         manifest_path.display()
@@ -139,3 +189,517 @@ pub fn get_metadata(project: Option<PathBuf>) -> Result<Vec<MetadataField>> {
         })
         .collect())
 }
+
+/// Imports the shared metadata of a TiefDown project from a JSON, YAML or TOML file.
+///
+/// The file must contain a single table (or object) of metadata fields. `null` values are
+/// skipped.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `path` - The path to the file to import, relative to the current directory.
+/// * `format` - The format of the file.
+///   * Determined from the file extension if not provided.
+/// * `replace` - Whether to replace the shared metadata instead of merging into it.
+///   * When merging, imported fields override existing fields and nested tables are merged.
+///
+/// # Returns
+///
+/// A Result containing either an error or nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::metadata_management::import_metadata;
+/// use std::path::PathBuf;
+///
+/// import_metadata(
+///     Some(PathBuf::from("my_project")),
+///     PathBuf::from("metadata.json"),
+///     None,
+///     false,
+/// ).unwrap();
+/// ```
+pub fn import_metadata(
+    project: Option<PathBuf>,
+    path: PathBuf,
+    format: Option<MetadataFormat>,
+    replace: bool,
+) -> Result<()> {
+    debug!("metadata.import: path='{}'", path.display());
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest_path = project.join("manifest.toml");
+
+    let mut manifest = load_and_convert_manifest(&manifest_path)?;
+
+    let imported_metadata = read_metadata_file(&path, format)?;
+    let imported_fields = imported_metadata.len();
+
+    apply_metadata_import(
+        manifest.shared_metadata.get_or_insert_with(Table::new),
+        imported_metadata,
+        replace,
+    );
+
+    save_manifest(&manifest_path, &manifest)?;
+    debug!(
+        "metadata.import: imported {} fields into manifest at '{}'",
+        imported_fields,
+        manifest_path.display()
+    );
+
+    Ok(())
+}
+
+/// Exports the shared metadata of a TiefDown project to a JSON, YAML or TOML file.
+///
+/// Dates are exported as strings to JSON and YAML files.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `path` - The path to the file to write, relative to the current directory.
+///   * An existing file is overwritten.
+/// * `format` - The format of the file.
+///   * Determined from the file extension if not provided.
+///
+/// # Returns
+///
+/// A Result containing either an error or nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::metadata_management::export_metadata;
+/// use std::path::PathBuf;
+///
+/// export_metadata(
+///     Some(PathBuf::from("my_project")),
+///     PathBuf::from("metadata.yaml"),
+///     None,
+/// ).unwrap();
+/// ```
+pub fn export_metadata(
+    project: Option<PathBuf>,
+    path: PathBuf,
+    format: Option<MetadataFormat>,
+) -> Result<()> {
+    debug!("metadata.export: path='{}'", path.display());
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest_path = project.join("manifest.toml");
+
+    let manifest = load_and_convert_manifest(&manifest_path)?;
+
+    write_metadata_file(&path, format, &manifest.shared_metadata.unwrap_or_default())
+}
+
+/// Reads a metadata table from a JSON, YAML or TOML file.
+pub(crate) fn read_metadata_file(path: &Path, format: Option<MetadataFormat>) -> Result<Table> {
+    let format = match format {
+        Some(format) => format,
+        None => MetadataFormat::from_path(path)?,
+    };
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("Failed to read metadata file '{}': {}", path.display(), e))?;
+
+    let metadata = match format {
+        MetadataFormat::Json => {
+            let value: serde_json::Value = serde_json::from_str(&content)?;
+            json_to_metadata_value(value)?
+        }
+        MetadataFormat::Yaml => {
+            let value: serde_yaml::Value = serde_yaml::from_str(&content)?;
+            yaml_to_metadata_value(value)?
+        }
+        MetadataFormat::Toml => Some(Value::Table(toml::from_str(&content)?)),
+    };
+
+    match metadata {
+        Some(Value::Table(metadata)) => Ok(metadata),
+        None => Ok(Table::new()),
+        Some(_) => Err(eyre!(
+            "Metadata file '{}' must contain a table of metadata fields.",
+            path.display()
+        )),
+    }
+}
+
+/// Writes a metadata table to a JSON, YAML or TOML file.
+pub(crate) fn write_metadata_file(
+    path: &Path,
+    format: Option<MetadataFormat>,
+    metadata: &Table,
+) -> Result<()> {
+    let format = match format {
+        Some(format) => format,
+        None => MetadataFormat::from_path(path)?,
+    };
+
+    let content = match format {
+        MetadataFormat::Json => serde_json::to_string_pretty(&normalize_metadata(metadata))? + "\n",
+        MetadataFormat::Yaml => serde_yaml::to_string(&normalize_metadata(metadata))?,
+        MetadataFormat::Toml => toml::to_string(metadata)?,
+    };
+
+    std::fs::write(path, content)
+        .map_err(|e| eyre!("Failed to write metadata file '{}': {}", path.display(), e))?;
+    debug!(
+        "metadata.export: wrote {} fields to '{}'",
+        metadata.len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Merges imported metadata into existing metadata, or replaces it.
+pub(crate) fn apply_metadata_import(metadata: &mut Table, imported_metadata: Table, replace: bool) {
+    if replace {
+        *metadata = imported_metadata;
+    } else {
+        deep_merge_metadata(metadata, &imported_metadata);
+    }
+}
+
+fn json_to_metadata_value(value: serde_json::Value) -> Result<Option<Value>> {
+    Ok(match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::Bool(value) => Some(Value::Boolean(value)),
+        serde_json::Value::Number(number) => Some(number_to_metadata_value(
+            number.as_i64(),
+            number.as_f64(),
+            &number,
+        )?),
+        serde_json::Value::String(value) => Some(Value::String(value)),
+        serde_json::Value::Array(array) => Some(Value::Array(
+            array
+                .into_iter()
+                .filter_map(|value| json_to_metadata_value(value).transpose())
+                .collect::<Result<_>>()?,
+        )),
+        serde_json::Value::Object(object) => {
+            let mut table = Table::new();
+            for (key, value) in object {
+                if let Some(value) = json_to_metadata_value(value)? {
+                    table.insert(key, value);
+                }
+            }
+            Some(Value::Table(table))
+        }
+    })
+}
+
+pub(crate) fn yaml_to_metadata_value(value: serde_yaml::Value) -> Result<Option<Value>> {
+    Ok(match value {
+        serde_yaml::Value::Null => None,
+        serde_yaml::Value::Bool(value) => Some(Value::Boolean(value)),
+        serde_yaml::Value::Number(number) => Some(number_to_metadata_value(
+            number.as_i64(),
+            number.as_f64(),
+            &number,
+        )?),
+        serde_yaml::Value::String(value) => Some(Value::String(value)),
+        serde_yaml::Value::Sequence(sequence) => Some(Value::Array(
+            sequence
+                .into_iter()
+                .filter_map(|value| yaml_to_metadata_value(value).transpose())
+                .collect::<Result<_>>()?,
+        )),
+        serde_yaml::Value::Mapping(mapping) => {
+            let mut table = Table::new();
+            for (key, value) in mapping {
+                let key = match key {
+                    serde_yaml::Value::String(key) => key,
+                    serde_yaml::Value::Bool(key) => key.to_string(),
+                    serde_yaml::Value::Number(key) => key.to_string(),
+                    key => return Err(eyre!("Unsupported metadata key: {:?}", key)),
+                };
+
+                if let Some(value) = yaml_to_metadata_value(value)? {
+                    table.insert(key, value);
+                }
+            }
+            Some(Value::Table(table))
+        }
+        serde_yaml::Value::Tagged(tagged) => yaml_to_metadata_value(tagged.value)?,
+    })
+}
+
+fn number_to_metadata_value(
+    integer: Option<i64>,
+    float: Option<f64>,
+    number: &dyn Display,
+) -> Result<Value> {
+    match (integer, float) {
+        (Some(integer), _) => Ok(Value::Integer(integer)),
+        (None, Some(float)) => Ok(Value::Float(float)),
+        (None, None) => Err(eyre!("Unsupported metadata number: {}", number)),
+    }
+}
+
+/// Parses metadata overrides in the form `key=value` into a metadata table.
+///
+/// Keys may be dotted paths to set fields in nested tables, e.g. `series.name=Dreams`. Values
+/// are always strings.
+///
+/// # Arguments
+///
+/// * `overrides` - The overrides to parse.
+///
+/// # Returns
+///
+/// A Result containing either an error or the overrides as a metadata table.
+///
+/// # Examples
+///
+/// ```
+/// use tiefdownlib::metadata_management::parse_metadata_overrides;
+///
+/// let overrides = parse_metadata_overrides(&["series.name=Dreams".to_string()]).unwrap();
+/// assert_eq!(overrides["series"]["name"].as_str(), Some("Dreams"));
+/// ```
+pub fn parse_metadata_overrides(overrides: &[String]) -> Result<Table> {
+    let mut metadata = Table::new();
+
+    for metadata_override in overrides {
+        let (key, value) = metadata_override.split_once('=').ok_or(eyre!(
+            "Invalid metadata override '{}'. Expected the form key=value.",
+            metadata_override
+        ))?;
+
+        let path = key.split('.').map(str::trim).collect::<Vec<_>>();
+        if path.iter().any(|segment| segment.is_empty()) {
+            return Err(eyre!(
+                "Invalid metadata override '{}'. The key must not be empty.",
+                metadata_override
+            ));
+        }
+
+        let mut table = &mut metadata;
+        for segment in &path[..path.len() - 1] {
+            let entry = table
+                .entry(segment.to_string())
+                .or_insert_with(|| Value::Table(Table::new()));
+            if !entry.is_table() {
+                *entry = Value::Table(Table::new());
+            }
+            table = entry.as_table_mut().expect("Entry was just made a table");
+        }
+
+        table.insert(
+            path[path.len() - 1].to_string(),
+            Value::String(value.to_string()),
+        );
+    }
+
+    Ok(metadata)
+}
+
+/// Resolves the effective metadata of a markdown project and shows which layer each field comes
+/// from.
+///
+/// The layers are merged in this order, each overriding the ones before it: shared metadata,
+/// profile metadata, template metadata, markdown project metadata and the overrides. Nested
+/// tables are merged, all other values are replaced.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `markdown_project` - The name of the markdown project.
+/// * `template` - The name of the template to include the metadata of.
+/// * `profile` - The name of the profile to include the metadata of.
+///   * Defaults to the default profile of the markdown project if not provided.
+/// * `metadata_overrides` - Metadata overrides, e.g. from `parse_metadata_overrides`.
+///
+/// # Returns
+///
+/// A Result containing either an error or the resolved fields, ordered by key.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::metadata_management::resolve_metadata;
+/// use std::path::PathBuf;
+///
+/// let fields = resolve_metadata(
+///     Some(PathBuf::from("my_project")),
+///     "Book".to_string(),
+///     Some("pdf".to_string()),
+///     None,
+///     None,
+/// ).unwrap();
+/// for field in fields {
+///     println!("{} = {} ({})", field.key, field.value, field.layer);
+/// }
+/// ```
+pub fn resolve_metadata(
+    project: Option<PathBuf>,
+    markdown_project: String,
+    template: Option<String>,
+    profile: Option<String>,
+    metadata_overrides: Option<Table>,
+) -> Result<Vec<ResolvedMetadataField>> {
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest_path = project.join("manifest.toml");
+
+    let manifest = load_and_convert_manifest(&manifest_path)?;
+
+    let markdown_projects = get_markdown_projects(&manifest);
+    let markdown_project = markdown_projects
+        .iter()
+        .find(|mp| mp.name == markdown_project)
+        .ok_or(eyre!("Markdown project '{}' not found.", markdown_project))?;
+
+    let template = template
+        .map(|template| {
+            manifest
+                .templates
+                .iter()
+                .find(|t| t.name == template)
+                .ok_or(eyre!("Template '{}' not found.", template))
+        })
+        .transpose()?;
+
+    let profile = profile.or(markdown_project.default_profile.clone());
+
+    let layers = get_metadata_layers(
+        &manifest,
+        markdown_project,
+        template,
+        profile.as_deref(),
+        &metadata_overrides.unwrap_or_default(),
+    )?;
+
+    let metadata = merge_metadata_layers(&layers);
+    debug!("Resolved metadata from {} layers.", layers.len());
+
+    Ok(get_metadata_provenance(&layers, &metadata))
+}
+
+/// Collects the metadata layers for a conversion, lowest precedence first.
+pub(crate) fn get_metadata_layers(
+    manifest: &Manifest,
+    markdown_project: &MarkdownProject,
+    template: Option<&Template>,
+    profile: Option<&str>,
+    metadata_overrides: &Table,
+) -> Result<Vec<(MetadataLayer, Table)>> {
+    let mut layers = vec![(
+        MetadataLayer::Shared,
+        manifest.shared_metadata.clone().unwrap_or_default(),
+    )];
+
+    if let Some(profile) = profile {
+        let profile = manifest
+            .profiles
+            .as_ref()
+            .and_then(|profiles| profiles.iter().find(|p| p.name == profile))
+            .ok_or(eyre!("Profile '{}' not found.", profile))?;
+
+        layers.push((
+            MetadataLayer::Profile(profile.name.clone()),
+            profile.metadata_fields.clone().unwrap_or_default(),
+        ));
+    }
+
+    if let Some(template) = template {
+        layers.push((
+            MetadataLayer::Template(template.name.clone()),
+            template.metadata_fields.clone().unwrap_or_default(),
+        ));
+    }
+
+    layers.push((
+        MetadataLayer::MarkdownProject(markdown_project.name.clone()),
+        markdown_project.metadata_fields.clone().unwrap_or_default(),
+    ));
+    layers.push((MetadataLayer::Override, metadata_overrides.clone()));
+
+    Ok(layers)
+}
+
+/// Deep merges metadata layers, lowest precedence first. Nested tables are merged, all other
+/// values are replaced.
+pub(crate) fn merge_metadata_layers(layers: &[(MetadataLayer, Table)]) -> Table {
+    let mut metadata = Table::new();
+
+    for (_, layer) in layers {
+        deep_merge_metadata(&mut metadata, layer);
+    }
+
+    metadata
+}
+
+pub(crate) fn deep_merge_metadata(metadata: &mut Table, layer: &Table) {
+    for (key, value) in layer {
+        match (metadata.get_mut(key), value) {
+            (Some(Value::Table(existing)), Value::Table(table)) => {
+                deep_merge_metadata(existing, table)
+            }
+            _ => {
+                metadata.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Finds the layer each field of the merged metadata comes from. Arrays and empty tables are
+/// reported as a whole.
+pub(crate) fn get_metadata_provenance(
+    layers: &[(MetadataLayer, Table)],
+    metadata: &Table,
+) -> Vec<ResolvedMetadataField> {
+    let mut fields = vec![];
+    collect_metadata_provenance(layers, metadata, &mut vec![], &mut fields);
+    fields
+}
+
+fn collect_metadata_provenance(
+    layers: &[(MetadataLayer, Table)],
+    metadata: &Table,
+    path: &mut Vec<String>,
+    fields: &mut Vec<ResolvedMetadataField>,
+) {
+    for (key, value) in metadata {
+        path.push(key.clone());
+
+        match value {
+            Value::Table(table) if !table.is_empty() => {
+                collect_metadata_provenance(layers, table, path, fields)
+            }
+            _ => {
+                // The last layer defining a field is the one it was taken from.
+                let layer = layers
+                    .iter()
+                    .rev()
+                    .find(|(_, layer)| get_metadata_value(layer, path).is_some())
+                    .map(|(layer, _)| layer.clone())
+                    .unwrap_or(MetadataLayer::Shared);
+
+                fields.push(ResolvedMetadataField {
+                    key: path.join("."),
+                    value: value.clone(),
+                    layer,
+                });
+            }
+        }
+
+        path.pop();
+    }
+}
+
+fn get_metadata_value<'a>(metadata: &'a Table, path: &[String]) -> Option<&'a Value> {
+    let (first, rest) = path.split_first()?;
+    let value = metadata.get(first)?;
+
+    if rest.is_empty() {
+        Some(value)
+    } else {
+        get_metadata_value(value.as_table()?, rest)
+    }
+}
@@ -1,21 +1,72 @@
 use color_eyre::eyre::Result;
 use log::debug;
+use regex::Regex;
 use serde::Serialize;
 use std::{
+    collections::HashSet,
     fs,
     path::{Path, PathBuf},
+    sync::LazyLock,
 };
+use toml::{Table, Value};
 
-use crate::{manifest_model::MetaGenerationSettings, meta_generation_format::MetaGenerationFormat};
+use crate::{
+    converters::common::normalize_metadata,
+    front_matter::split_front_matter,
+    manifest_model::{MetaGenerationSettings, NavTitleSource},
+    meta_generation_format::MetaGenerationFormat,
+};
 
 const DEFAULT_NAV_META_YML_FILE_PATH: &str = ".meta_nav.yml";
 
+static MARKDOWN_ATX_HEADING_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^ {0,3}(#{1,6})[ \t]+(.*?)(?:[ \t]+#+)?[ \t]*$").expect("Invalid regex")
+});
+static MARKDOWN_SETEXT_UNDERLINE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ {0,3}(=+|-+)[ \t]*$").expect("Invalid regex"));
+static MARKDOWN_FENCE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ {0,3}(`{3,}|~{3,})").expect("Invalid regex"));
+static PANDOC_ATTRIBUTES_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s*\{([^}]*)\}\s*$").expect("Invalid regex"));
+static PANDOC_IDENTIFIER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|\s)#([^\s}]+)").expect("Invalid regex"));
+static MARKDOWN_LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"!?\[([^\]]*)\](?:\([^)]*\)|\[[^\]]*\])").expect("Invalid regex"));
+static MARKDOWN_CODE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"`+([^`]*)`+").expect("Invalid regex"));
+static MARKDOWN_STAR_EMPHASIS_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*{1,3}([^*\s](?:[^*]*[^*\s])?)\*{1,3}").expect("Invalid regex"));
+static MARKDOWN_UNDERSCORE_EMPHASIS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(^|[^\w])_{1,3}([^_\s](?:[^_]*[^_\s])?)_{1,3}([^\w]|$)").expect("Invalid regex")
+});
+static MARKDOWN_STRIKEOUT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"~~([^~]+)~~").expect("Invalid regex"));
+static HTML_TITLE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").expect("Invalid regex"));
+static HTML_HEADING_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<h([1-6])(\s[^>]*)?>(.*?)</h[1-6]>").expect("Invalid regex")
+});
+static HTML_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\bid\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("Invalid regex")
+});
+static HTML_TAG_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<[^>]*>").expect("Invalid regex"));
+static TYPST_HEADING_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(=+)[ \t]+(.*?)(?:[ \t]*<([^>]*)>)?[ \t]*$").expect("Invalid regex")
+});
+
 #[derive(Serialize, Clone)]
 pub struct NavMeta {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nodes: Option<Vec<NavMetaNode>>,
+    /// The nodes nested by their directories, with the sections of each file as children.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree: Option<Vec<NavTreeNode>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub current: Option<NavMetaNode>,
+    /// The directories leading to the current node, followed by the current node itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub breadcrumbs: Option<Vec<NavBreadcrumb>>,
 }
 
 #[derive(Serialize, Clone)]
@@ -26,28 +77,91 @@ pub struct NavMetaNode {
     pub prev: Option<NavMetaNodeId>,
     pub next: Option<NavMetaNodeId>,
     pub depth: usize,
+    /// The fields of the front matter of the file, except for the title.
+    #[serde(skip_serializing_if = "Table::is_empty")]
+    pub metadata: Table,
+    /// The level 2 and 3 headings of the file, with level 3 headings nested in level 2 headings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<NavTreeNode>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
 pub struct NavMetaNodeId {
     pub value: String,
 }
 
+/// The kind of a node in the navigation tree.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavTreeNodeKind {
+    Directory,
+    File,
+    Section,
+}
+
+/// A node in the navigation tree.
+///
+/// # Fields
+///
+/// * `kind` - Whether the node is a directory, a file or a section of a file
+/// * `title` - The title of the node, the directory name for directories
+/// * `path` - The path of the directory or file, relative to the conversion input directory
+/// * `id` - The id of the corresponding `NavMetaNode` for files
+/// * `anchor` - The anchor of the heading for sections
+/// * `children` - The files and directories of a directory or the sections of a file
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct NavTreeNode {
+    pub kind: NavTreeNodeKind,
+    pub title: String,
+    pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<NavMetaNodeId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<NavTreeNode>,
+}
+
+/// An entry in the breadcrumbs of the current node.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct NavBreadcrumb {
+    pub title: String,
+    pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<NavMetaNodeId>,
+}
+
 #[derive(Clone)]
 struct PreNavNode {
     id: NavMetaNodeId,
     path: PathBuf,
     title: String,
+    metadata: Table,
+    sections: Vec<NavTreeNode>,
+}
+
+/// A heading of a file.
+#[derive(Clone, Debug, PartialEq)]
+struct Heading {
+    level: usize,
+    title: String,
+    id: Option<String>,
 }
 
+/// Retrieves the navigation metadata of the input files.
+///
+/// Section anchors are unique within each file for multi file outputs and unique across all
+/// files otherwise, as all files end up in the same document.
 pub(crate) fn retrieve_nav_meta(
     input_files: &Vec<PathBuf>,
     compiled_directory_path: &Path,
     conversion_input_dir: &Path,
     output_extension: &Option<String>,
+    nav_title_source: NavTitleSource,
+    multi_file_output: bool,
 ) -> Result<NavMeta> {
     let canon_compiled_directory_path = &compiled_directory_path.canonicalize()?;
     let canon_conversion_input_dir = &conversion_input_dir.canonicalize()?;
+    let mut used_anchors = HashSet::new();
 
     let pre_nodes: Vec<PreNavNode> = input_files
         .iter()
@@ -62,6 +176,12 @@ pub(crate) fn retrieve_nav_meta(
             );
 
             let canon_file_path = canon_compiled_directory_path.join(f).canonicalize()?;
+            let content = String::from_utf8(fs::read(&canon_file_path)?).unwrap_or_default();
+            let (front_matter, body) = split_front_matter(&content);
+            let mut metadata = front_matter
+                .map(|front_matter| normalize_metadata(&front_matter))
+                .unwrap_or_default();
+
             let path = canon_file_path
                 .strip_prefix(canon_conversion_input_dir)?
                 .to_path_buf();
@@ -72,13 +192,35 @@ pub(crate) fn retrieve_nav_meta(
                 path
             };
 
-            let title = path.with_extension("").to_string_lossy().to_string();
+            let front_matter_title = match metadata.remove("title") {
+                Some(Value::String(title)) => Some(get_markdown_text(&title)),
+                Some(title) => Some(title.to_string()),
+                None => None,
+            };
+
+            let headings = get_headings(&canon_file_path, body);
+
+            let title = front_matter_title.unwrap_or_else(|| match nav_title_source {
+                NavTitleSource::Content => get_content_title(&canon_file_path, body, &headings)
+                    .unwrap_or_else(|| {
+                        path.file_stem()
+                            .map(|s| s.to_string_lossy().to_string())
+                            .unwrap_or_default()
+                    }),
+                NavTitleSource::Path => path.with_extension("").to_string_lossy().to_string(),
+            });
             let nav_id = NavMetaNodeId { value: id };
+            if multi_file_output {
+                used_anchors.clear();
+            }
+            let sections = get_sections(&path, &headings, &mut used_anchors);
 
             Ok(PreNavNode {
                 id: nav_id,
                 path,
                 title,
+                metadata,
+                sections,
             })
         })
         .collect::<Result<Vec<_>>>()?;
@@ -106,18 +248,372 @@ pub(crate) fn retrieve_nav_meta(
                 prev,
                 next,
                 depth,
+                metadata: p.metadata.clone(),
+                sections: p.sections.clone(),
             }
         })
         .collect::<Vec<_>>();
 
+    let tree = build_nav_tree(&nodes);
+
     debug!("Built navigation metadata tree.");
 
     Ok(NavMeta {
         nodes: Some(nodes),
+        tree: Some(tree),
         current: None,
+        breadcrumbs: None,
     })
 }
 
+/// Nests the nodes by their directories. Consecutive files in the same directory share a
+/// directory node, so the tree keeps the order of the input files.
+fn build_nav_tree(nodes: &[NavMetaNode]) -> Vec<NavTreeNode> {
+    let mut tree: Vec<NavTreeNode> = vec![];
+
+    for node in nodes {
+        let mut children = &mut tree;
+        let mut directory_path = PathBuf::new();
+
+        for component in node.path.parent().into_iter().flatten() {
+            directory_path.push(component);
+
+            let is_current_directory = children.last().is_some_and(|last| {
+                last.kind == NavTreeNodeKind::Directory && last.path == directory_path
+            });
+
+            if !is_current_directory {
+                children.push(NavTreeNode {
+                    kind: NavTreeNodeKind::Directory,
+                    title: component.to_string_lossy().to_string(),
+                    path: directory_path.clone(),
+                    id: None,
+                    anchor: None,
+                    children: vec![],
+                });
+            }
+
+            children = &mut children
+                .last_mut()
+                .expect("Directory node should exist")
+                .children;
+        }
+
+        children.push(NavTreeNode {
+            kind: NavTreeNodeKind::File,
+            title: node.title.clone(),
+            path: node.path.clone(),
+            id: Some(node.id.clone()),
+            anchor: None,
+            children: node.sections.clone(),
+        });
+    }
+
+    tree
+}
+
+/// Retrieves the breadcrumbs leading to the node with the given id in the navigation tree.
+///
+/// # Arguments
+///
+/// * `tree` - The navigation tree
+/// * `id` - The id of the node
+///
+/// # Returns
+///
+/// The directories containing the node followed by the node itself, or `None` if the node is
+/// not part of the tree.
+pub(crate) fn get_breadcrumbs(
+    tree: &[NavTreeNode],
+    id: &NavMetaNodeId,
+) -> Option<Vec<NavBreadcrumb>> {
+    tree.iter().find_map(|node| {
+        let breadcrumb = NavBreadcrumb {
+            title: node.title.clone(),
+            path: node.path.clone(),
+            id: node.id.clone(),
+        };
+
+        match node.kind {
+            NavTreeNodeKind::File if node.id.as_ref() == Some(id) => Some(vec![breadcrumb]),
+            NavTreeNodeKind::Directory => {
+                get_breadcrumbs(&node.children, id).map(|mut breadcrumbs| {
+                    breadcrumbs.insert(0, breadcrumb);
+                    breadcrumbs
+                })
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Builds the sections of a file from its level 2 and 3 headings. Headings without an explicit
+/// identifier get a slugified anchor, made unique among the `used_anchors` like pandoc does.
+fn get_sections(
+    path: &Path,
+    headings: &[Heading],
+    used_anchors: &mut HashSet<String>,
+) -> Vec<NavTreeNode> {
+    let mut sections: Vec<NavTreeNode> = vec![];
+    let mut in_level_2_section = false;
+
+    for heading in headings {
+        let anchor = match &heading.id {
+            Some(id) => id.clone(),
+            None => get_unique_slug(&heading.title, used_anchors),
+        };
+        used_anchors.insert(anchor.clone());
+
+        if heading.level < 2 {
+            in_level_2_section = false;
+            continue;
+        }
+
+        if heading.level > 3 {
+            continue;
+        }
+
+        let section = NavTreeNode {
+            kind: NavTreeNodeKind::Section,
+            title: heading.title.clone(),
+            path: path.to_path_buf(),
+            id: None,
+            anchor: Some(anchor),
+            children: vec![],
+        };
+
+        match sections.last_mut() {
+            Some(parent) if heading.level == 3 && in_level_2_section => {
+                parent.children.push(section)
+            }
+            _ => {
+                in_level_2_section = heading.level == 2;
+                sections.push(section)
+            }
+        }
+    }
+
+    sections
+}
+
+fn get_unique_slug(title: &str, used_anchors: &HashSet<String>) -> String {
+    let slug = slugify(title);
+
+    if !used_anchors.contains(&slug) {
+        return slug;
+    }
+
+    (1..)
+        .map(|i| format!("{}-{}", slug, i))
+        .find(|slug| !used_anchors.contains(slug))
+        .expect("There should always be an unused slug")
+}
+
+/// Converts a heading into an anchor the way pandoc generates identifiers: Punctuation except
+/// underscores, hyphens and periods is removed, whitespace becomes hyphens, everything is
+/// lowercased and everything before the first letter is dropped.
+pub(crate) fn slugify(title: &str) -> String {
+    let slug = title
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    let slug = slug.trim_start_matches(|c: char| !c.is_alphabetic());
+
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Retrieves the title of a file from its content. Markdown files use their first heading, HTML
+/// files their `<title>` or first `<h1>` and Typst files their first heading.
+fn get_content_title(path: &Path, content: &str, headings: &[Heading]) -> Option<String> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "html" | "htm" | "xhtml" => HTML_TITLE_REGEX
+            .captures(content)
+            .map(|captures| get_html_text(&captures[1]))
+            .filter(|title| !title.is_empty())
+            .or_else(|| {
+                headings
+                    .iter()
+                    .find(|heading| heading.level == 1)
+                    .map(|heading| heading.title.clone())
+            }),
+        _ => headings.first().map(|heading| heading.title.clone()),
+    }
+}
+
+/// Retrieves the headings of a file. Supports Markdown, HTML and Typst files.
+fn get_headings(path: &Path, content: &str) -> Vec<Heading> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "md" | "markdown" => get_markdown_headings(content),
+        "html" | "htm" | "xhtml" => get_html_headings(content),
+        "typ" => get_typst_headings(content),
+        _ => vec![],
+    }
+}
+
+fn get_markdown_headings(content: &str) -> Vec<Heading> {
+    let mut headings = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut fence: Option<String> = None;
+
+    for line in content.lines() {
+        if let Some(fence_captures) = MARKDOWN_FENCE_REGEX.captures(line) {
+            let marker = &fence_captures[1];
+
+            match &fence {
+                Some(open_fence)
+                    if marker.starts_with(&open_fence[..1]) && marker.len() >= open_fence.len() =>
+                {
+                    fence = None
+                }
+                Some(_) => {}
+                None => fence = Some(marker.to_string()),
+            }
+
+            paragraph.clear();
+            continue;
+        }
+
+        if fence.is_some() {
+            continue;
+        }
+
+        if let Some(captures) = MARKDOWN_ATX_HEADING_REGEX.captures(line) {
+            let heading = get_markdown_heading(captures[1].len(), &captures[2]);
+            if !heading.title.is_empty() {
+                headings.push(heading);
+            }
+            paragraph.clear();
+            continue;
+        }
+
+        if !paragraph.is_empty()
+            && let Some(captures) = MARKDOWN_SETEXT_UNDERLINE_REGEX.captures(line)
+        {
+            let level = if captures[1].starts_with('=') { 1 } else { 2 };
+            headings.push(get_markdown_heading(level, &paragraph.join(" ")));
+            paragraph.clear();
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            paragraph.clear();
+        } else {
+            paragraph.push(line.trim());
+        }
+    }
+
+    headings
+}
+
+fn get_markdown_heading(level: usize, heading: &str) -> Heading {
+    let id = PANDOC_ATTRIBUTES_REGEX
+        .captures(heading)
+        .and_then(|attributes| {
+            PANDOC_IDENTIFIER_REGEX
+                .captures(attributes.get(1)?.as_str())
+                .map(|captures| captures[1].to_string())
+        });
+
+    Heading {
+        level,
+        title: get_markdown_text(&PANDOC_ATTRIBUTES_REGEX.replace(heading, "")),
+        id,
+    }
+}
+
+/// Removes the inline formatting of markdown text, keeping the text of links, images, code
+/// spans, emphasis and strikeouts.
+fn get_markdown_text(markdown: &str) -> String {
+    let text = MARKDOWN_LINK_REGEX.replace_all(markdown, "$1");
+    let text = MARKDOWN_CODE_REGEX.replace_all(&text, "$1");
+    let text = MARKDOWN_STAR_EMPHASIS_REGEX.replace_all(&text, "$1");
+    let text = MARKDOWN_UNDERSCORE_EMPHASIS_REGEX.replace_all(&text, "$1$2$3");
+    let text = MARKDOWN_STRIKEOUT_REGEX.replace_all(&text, "$1");
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn get_html_headings(content: &str) -> Vec<Heading> {
+    HTML_HEADING_REGEX
+        .captures_iter(content)
+        .filter_map(|captures| {
+            let level = captures[1].parse().ok()?;
+            let title = get_html_text(&captures[3]);
+            let id = captures
+                .get(2)
+                .and_then(|attributes| HTML_ID_REGEX.captures(attributes.as_str()))
+                .and_then(|id| id.get(1).or(id.get(2)))
+                .map(|id| id.as_str().to_string());
+
+            (!title.is_empty()).then_some(Heading { level, title, id })
+        })
+        .collect()
+}
+
+fn get_html_text(html: &str) -> String {
+    let text = HTML_TAG_REGEX.replace_all(html, "");
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn get_typst_headings(content: &str) -> Vec<Heading> {
+    let mut headings = vec![];
+    let mut in_raw_block = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_raw_block = !in_raw_block;
+            continue;
+        }
+
+        if in_raw_block {
+            continue;
+        }
+
+        if let Some(captures) = TYPST_HEADING_REGEX.captures(line) {
+            let title = captures[2].trim().to_string();
+            if !title.is_empty() {
+                headings.push(Heading {
+                    level: captures[1].len(),
+                    title,
+                    id: captures.get(3).map(|label| label.as_str().to_string()),
+                });
+            }
+        }
+    }
+
+    headings
+}
+
 pub(crate) fn generate_nav_meta_file(
     meta_gen: &MetaGenerationSettings,
     nav_meta: &NavMeta,
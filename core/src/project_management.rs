@@ -1,18 +1,29 @@
 use crate::{
+    build_cache::clear_build_cache,
     consts::CURRENT_MANIFEST_VERSION,
+    manifest_document::update_manifest_document,
+    manifest_includes::{
+        merge_manifest_fragments, remove_empty_sections, split_manifest_fragments,
+    },
     manifest_model::{
-        Manifest, MarkdownProject, MetaGenerationSettings, PreProcessor, PreProcessors, Processor,
-        Processors, Profile, Template, upgrade_manifest,
+        Manifest, ManifestFragment, MarkdownProject, MetaGenerationSettings, NavTitleSource,
+        PreProcessor, PreProcessors, Processor, Processors, Profile, Template, upgrade_manifest,
     },
     meta_generation_feature::MetaGenerationFeature,
     meta_generation_format::MetaGenerationFormat,
     template_management::{self, add_lix_filters, get_template_path, get_template_type_from_path},
     template_type::TemplateType,
+    text_diff::get_unified_diff,
 };
 use color_eyre::eyre::{Result, eyre};
 use fs_extra::dir;
 use log::{debug, error, info};
-use std::{env::current_dir, fs, path::PathBuf, process::Command};
+use std::{
+    env::current_dir,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
 use toml::{Table, Value};
 
 /// Initializes a new TiefDown project.
@@ -127,6 +138,9 @@ This is a simple test document for you to edit or overwrite."#,
             metadata_fields: None,
             default_profile: None,
             resources: None,
+            include: None,
+            exclude: None,
+            bibliography: None,
         }]),
         templates: templates.clone(),
         custom_processors: Processors {
@@ -135,10 +149,13 @@ This is a simple test document for you to edit or overwrite."#,
         },
         smart_clean: smart_clean_value,
         smart_clean_threshold,
+        build_cache: None,
         shared_metadata: None,
         metadata_settings: None,
         profiles: None,
         injections: None,
+        include: None,
+        fragments: vec![],
     };
 
     std::fs::write(manifest_path.clone(), toml::to_string(&manifest)?)?;
@@ -162,6 +179,9 @@ fn get_template_mapping_for_preset(template: &String) -> Result<Template> {
         footer_injections: None,
         multi_file_output: None,
         meta_gen: None,
+        latex: None,
+        metadata_fields: None,
+        bibliography: None,
     };
 
     add_lix_filters(&mut template);
@@ -192,6 +212,7 @@ fn get_template_mapping_for_preset(template: &String) -> Result<Template> {
 /// * `nav_meta_gen_output` - Output path of IR for navigation meta generation
 /// * `metadata_meta_gen_output` - Output path of IR for metadata meta generation
 /// * `meta_gen_format` - Output format of IR for meta generation
+/// * `nav_title_source` - Where the titles of the navigation metadata are taken from
 ///
 /// # Returns
 ///
@@ -210,7 +231,7 @@ fn get_template_mapping_for_preset(template: &String) -> Result<Template> {
 ///     Some(PathBuf::from("template.tex")),
 ///     Some(PathBuf::from("output.pdf")),
 ///     None, None, None, None, None, None, None,
-///     false, None, None, None, None, None,
+///     false, None, None, None, None, None, None,
 /// ).unwrap();
 /// ```
 pub fn add_template(
@@ -232,6 +253,7 @@ pub fn add_template(
     nav_meta_gen_output: Option<PathBuf>,
     metadata_meta_gen_output: Option<PathBuf>,
     meta_gen_format: Option<MetaGenerationFormat>,
+    nav_title_source: Option<NavTitleSource>,
 ) -> Result<()> {
     debug!(
         "Adding template '{}' (type: {:?})...",
@@ -295,6 +317,7 @@ pub fn add_template(
             nav_output: nav_meta_gen_output,
             metadata_output: metadata_meta_gen_output,
             format: meta_gen_format,
+            nav_title_source,
         })
     } else {
         None
@@ -313,6 +336,9 @@ pub fn add_template(
         footer_injections,
         multi_file_output: if multi_file_output { Some(true) } else { None },
         meta_gen,
+        latex: None,
+        metadata_fields: None,
+        bibliography: None,
     };
 
     create_templates(&project, &vec![template.clone()])?;
@@ -320,8 +346,7 @@ pub fn add_template(
 
     manifest.templates.extend([template.clone()]);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("Template '{}' added and manifest updated.", template_name);
 
     Ok(())
@@ -361,8 +386,7 @@ pub fn remove_template(project: Option<PathBuf>, template_name: String) -> Resul
     {
         let removed_template = manifest.templates.swap_remove(pos);
 
-        let manifest_content = toml::to_string(&manifest)?;
-        std::fs::write(&manifest_path, manifest_content)?;
+        save_manifest(&manifest_path, &manifest)?;
 
         let template_dir = project.join("template");
         let template_path = template_dir.join(
@@ -416,6 +440,7 @@ pub fn remove_template(project: Option<PathBuf>, template_name: String) -> Resul
 /// * `nav_meta_gen_output` - Output path of IR for navigation meta generation
 /// * `metadata_meta_gen_output` - Output path of IR for metadata meta generation
 /// * `meta_gen_format` - Output format of IR for meta generation
+/// * `nav_title_source` - Where the titles of the navigation metadata are taken from
 ///
 /// # Returns
 ///
@@ -434,7 +459,7 @@ pub fn remove_template(project: Option<PathBuf>, template_name: String) -> Resul
 ///     None, None,
 ///     Some(PathBuf::from("new_output.pdf")),
 ///     None, None, None, None, None, None, None, None, None, None, None,
-///     Some(false), None, None, None, None, None,
+///     Some(false), None, None, None, None, None, None,
 /// ).unwrap();
 /// ```
 pub fn update_template(
@@ -460,6 +485,7 @@ pub fn update_template(
     nav_meta_gen_output: Option<PathBuf>,
     metadata_meta_gen_output: Option<PathBuf>,
     meta_gen_format: Option<MetaGenerationFormat>,
+    nav_title_source: Option<NavTitleSource>,
 ) -> Result<()> {
     debug!(
         "Updating template '{}' (fields provided: type={:?}, file={:?}, output={:?})",
@@ -614,6 +640,7 @@ pub fn update_template(
             || nav_meta_gen_output.is_some()
             || metadata_meta_gen_output.is_some()
             || meta_gen_format.is_some()
+            || nav_title_source.is_some()
         {
             if let Some(meta_gen) = &mut template.meta_gen {
                 meta_gen.feature = meta_gen_feature.unwrap_or(meta_gen.feature);
@@ -621,6 +648,7 @@ pub fn update_template(
                 meta_gen.metadata_output =
                     metadata_meta_gen_output.or(meta_gen.metadata_output.clone());
                 meta_gen.format = meta_gen_format.or(meta_gen.format.clone());
+                meta_gen.nav_title_source = nav_title_source.or(meta_gen.nav_title_source);
             } else {
                 if let Some(meta_gen_feature) = meta_gen_feature {
                     template.meta_gen = Some(MetaGenerationSettings {
@@ -628,6 +656,7 @@ pub fn update_template(
                         nav_output: nav_meta_gen_output,
                         metadata_output: metadata_meta_gen_output,
                         format: meta_gen_format,
+                        nav_title_source,
                     })
                 } else {
                     return Err(eyre!(
@@ -643,8 +672,7 @@ pub fn update_template(
         ));
     }
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
     debug!("Template '{}' updated and manifest saved.", template_name);
 
     Ok(())
@@ -658,6 +686,7 @@ pub fn update_template(
 ///   * Defaults to the current directory if not provided.
 /// * `smart_clean` - Whether to enable smart cleaning.
 /// * `smart_clean_threshold` - The threshold for smart cleaning.
+/// * `build_cache` - Whether to reuse unchanged conversion results from previous builds.
 ///
 /// # Returns
 ///
@@ -670,12 +699,13 @@ pub fn update_template(
 /// use std::path::PathBuf;
 ///
 /// // Enable smart clean with a threshold of 3 builds
-/// update_settings(Some(PathBuf::from("my_project")), Some(true), Some(3)).unwrap();
+/// update_settings(Some(PathBuf::from("my_project")), Some(true), Some(3), None).unwrap();
 /// ```
 pub fn update_settings(
     project: Option<PathBuf>,
     smart_clean: Option<bool>,
     smart_clean_threshold: Option<u32>,
+    build_cache: Option<bool>,
 ) -> Result<()> {
     let project = project.unwrap_or(PathBuf::from("."));
     let manifest_path = project.join("manifest.toml");
@@ -691,8 +721,12 @@ pub fn update_settings(
         manifest.smart_clean_threshold = Some(smart_clean_threshold);
     }
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    if let Some(build_cache_value) = build_cache {
+        let build_cache_value = if build_cache_value { Some(true) } else { None };
+        manifest.build_cache = build_cache_value;
+    }
+
+    save_manifest(&manifest_path, &manifest)?;
 
     Ok(())
 }
@@ -747,8 +781,7 @@ pub fn add_preprocessor(
     };
     manifest.custom_processors.preprocessors.push(preprocessor);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
 
     Ok(())
 }
@@ -790,8 +823,7 @@ pub fn remove_preprocessor(project: Option<PathBuf>, name: String) -> Result<()>
         return Err(eyre!("Preprocessor with name '{}' does not exist.", name));
     }
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
 
     Ok(())
 }
@@ -837,8 +869,7 @@ pub fn add_processor(
     };
     manifest.custom_processors.processors.push(processor);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
 
     Ok(())
 }
@@ -880,8 +911,7 @@ pub fn remove_processor(project: Option<PathBuf>, name: String) -> Result<()> {
         return Err(eyre!("Processor with name '{}' does not exist.", name));
     }
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
 
     Ok(())
 }
@@ -948,7 +978,11 @@ pub fn add_profile(project: Option<PathBuf>, name: String, templates: Vec<String
 
     let mut manifest = load_and_convert_manifest(&manifest_path)?;
 
-    let profile = Profile { name, templates };
+    let profile = Profile {
+        name,
+        templates,
+        metadata_fields: None,
+    };
 
     if manifest.profiles.is_none() {
         manifest.profiles = Some(vec![]);
@@ -956,8 +990,7 @@ pub fn add_profile(project: Option<PathBuf>, name: String, templates: Vec<String
 
     manifest.profiles.as_mut().unwrap().push(profile);
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
 
     Ok(())
 }
@@ -998,8 +1031,7 @@ pub fn remove_profile(project: Option<PathBuf>, name: String) -> Result<()> {
         return Err(eyre!("Profile with name '{}' does not exist.", name));
     }
 
-    let manifest_content = toml::to_string(&manifest)?;
-    std::fs::write(&manifest_path, manifest_content)?;
+    save_manifest(&manifest_path, &manifest)?;
 
     Ok(())
 }
@@ -1099,7 +1131,7 @@ pub fn get_preprocessors(project: Option<PathBuf>) -> Result<Vec<PreProcessor>>
     Ok(manifest.custom_processors.preprocessors)
 }
 
-/// Cleans the project's output directories.
+/// Cleans the project's output directories and removes the build cache.
 ///
 /// # Arguments
 ///
@@ -1124,6 +1156,7 @@ pub fn clean(project: Option<PathBuf>) -> Result<()> {
     let _ = load_and_convert_manifest(&manifest_path)?;
 
     run_smart_clean(&project, 0)?;
+    clear_build_cache(&project)?;
 
     Ok(())
 }
@@ -1246,9 +1279,9 @@ pub(crate) fn get_missing_dependencies(dependencies: Vec<&str>) -> Result<Vec<St
 ///
 /// This function reads `manifest.toml`, checks its version against
 /// `CURRENT_MANIFEST_VERSION`, and applies `upgrade_manifest` when the file is
-/// older. The manifest is written back to disk after this step to persist any
-/// upgrades or normalization. If the file is from a newer version of the
-/// program, an error is returned.
+/// older. The upgrade only happens in memory, the file itself is left untouched.
+/// Use `upgrade_manifest_file` to write the upgrade to disk. If the file is from a
+/// newer version of the program, an error is returned.
 ///
 /// # Arguments
 ///
@@ -1267,7 +1300,269 @@ pub(crate) fn get_missing_dependencies(dependencies: Vec<&str>) -> Result<Vec<St
 /// let manifest = load_and_convert_manifest(&PathBuf::from("my_project/manifest.toml")).unwrap();
 /// println!("Manifest version: {}", manifest.version);
 /// ```
-pub fn load_and_convert_manifest(manifest_path: &std::path::PathBuf) -> Result<Manifest> {
+pub fn load_and_convert_manifest(manifest_path: &Path) -> Result<Manifest> {
+    let (_, manifest, manifest_version, fragments) = load_manifest_table(manifest_path)?;
+
+    if manifest_version < CURRENT_MANIFEST_VERSION {
+        info!(
+            "The manifest is at version {} and was upgraded to version {} for this run. Run `project upgrade-manifest` to update the file.",
+            manifest_version, CURRENT_MANIFEST_VERSION
+        );
+    }
+
+    let mut manifest: Manifest = Value::Table(manifest).try_into()?;
+    manifest.fragments = fragments;
+
+    Ok(manifest)
+}
+
+/// Writes a manifest to disk.
+///
+/// Only the changed keys of an existing manifest file are written, keeping its comments
+/// and formatting. Keys the manifest doesn't model are kept as well. If the manifest file
+/// on disk is from an older version, it is upgraded with `upgrade_manifest_file` first,
+/// as writing the manifest persists the upgrade.
+///
+/// # Arguments
+///
+/// * `manifest_path` - The path to the `manifest.toml` file.
+/// * `manifest` - The manifest to write.
+///
+/// # Returns
+///
+/// A Result containing either an error or nothing.
+pub(crate) fn save_manifest(manifest_path: &Path, manifest: &Manifest) -> Result<()> {
+    if manifest_path.exists()
+        && get_manifest_version(&toml::from_str(&fs::read_to_string(manifest_path)?)?)?
+            < CURRENT_MANIFEST_VERSION
+    {
+        let project = manifest_path.parent().unwrap_or(Path::new("."));
+        upgrade_manifest_file(Some(project.to_path_buf()), false)?;
+    }
+
+    if !manifest.fragments.is_empty() {
+        return save_split_manifest(manifest_path, manifest);
+    }
+
+    let manifest_content = if manifest_path.exists() {
+        let existing_content = fs::read_to_string(manifest_path)?;
+        let previous_manifest: Manifest = toml::from_str(&existing_content)?;
+
+        update_manifest_document(&existing_content, &previous_manifest, manifest)?
+    } else {
+        toml::to_string(manifest)?
+    };
+
+    fs::write(manifest_path, &manifest_content)?;
+    debug!(
+        "Wrote manifest to '{}' ({} bytes).",
+        manifest_path.display(),
+        manifest_content.len()
+    );
+
+    Ok(())
+}
+
+/// Writes a manifest loaded with manifest fragments to disk.
+///
+/// Entries loaded from a fragment are written back to that fragment, everything else is
+/// written to the manifest file. Fragments are only written if they changed.
+fn save_split_manifest(manifest_path: &Path, manifest: &Manifest) -> Result<()> {
+    let Value::Table(mut manifest_table) = Value::try_from(manifest)? else {
+        return Err(eyre!("Failed to serialize the manifest."));
+    };
+    let Value::Table(mut previous_manifest_table) =
+        Value::try_from(load_and_convert_manifest(manifest_path)?)?
+    else {
+        return Err(eyre!("Failed to serialize the manifest."));
+    };
+
+    let project = manifest_path.parent().unwrap_or(Path::new("."));
+
+    let previous_fragments =
+        split_manifest_fragments(&mut previous_manifest_table, &manifest.fragments);
+    for ((fragment_path, fragment), (_, previous_fragment)) in
+        split_manifest_fragments(&mut manifest_table, &manifest.fragments)
+            .into_iter()
+            .zip(previous_fragments)
+    {
+        let existing_content = fs::read_to_string(project.join(&fragment_path))?;
+        let fragment_content =
+            update_manifest_document(&existing_content, &previous_fragment, &fragment)?;
+
+        if fragment_content != existing_content {
+            fs::write(project.join(&fragment_path), &fragment_content)?;
+            debug!(
+                "Wrote manifest fragment '{}' ({} bytes).",
+                fragment_path.display(),
+                fragment_content.len()
+            );
+        }
+    }
+
+    let existing_content = fs::read_to_string(manifest_path)?;
+    let existing_manifest: Table = toml::from_str(&existing_content)?;
+
+    remove_empty_sections(&mut manifest_table, &existing_manifest);
+    let manifest_content =
+        update_manifest_document(&existing_content, &previous_manifest_table, &manifest_table)?;
+
+    fs::write(manifest_path, &manifest_content)?;
+    debug!(
+        "Wrote manifest to '{}' ({} bytes).",
+        manifest_path.display(),
+        manifest_content.len()
+    );
+
+    Ok(())
+}
+
+/// The result of upgrading a manifest file to the current manifest version.
+///
+/// # Fields
+///
+/// * `from_version` - The version of the manifest file before the upgrade.
+/// * `to_version` - The version of the manifest file after the upgrade.
+/// * `diff` - A unified diff of the manifest file and the manifest fragments it includes.
+///   * Empty if the manifest is already up to date.
+/// * `backup_path` - The path the original manifest file was backed up to.
+///   * Only set if the manifest file was written.
+/// * `fragment_backup_paths` - The paths the changed manifest fragments were backed up to.
+///   * Empty if no manifest fragment was written.
+pub struct ManifestUpgrade {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub diff: String,
+    pub backup_path: Option<PathBuf>,
+    pub fragment_backup_paths: Vec<PathBuf>,
+}
+
+/// Upgrades the manifest file of a project to the current manifest version.
+///
+/// The manifest fragments the manifest includes are upgraded along with it. Each file is
+/// backed up next to itself with a `.bak` extension before it is overwritten. Nothing is
+/// written if the manifest is already up to date.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+/// * `dry_run` - Whether to only compute the upgrade without writing it.
+///
+/// # Returns
+///
+/// A Result containing either an error or the upgrade of the manifest file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::project_management::upgrade_manifest_file;
+/// use std::path::PathBuf;
+///
+/// let upgrade = upgrade_manifest_file(Some(PathBuf::from("my_project")), true).unwrap();
+/// println!("{}", upgrade.diff);
+/// ```
+pub fn upgrade_manifest_file(project: Option<PathBuf>, dry_run: bool) -> Result<ManifestUpgrade> {
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest_path = project.join("manifest.toml");
+
+    let (manifest_content, mut manifest, manifest_version, fragments) =
+        load_manifest_table(&manifest_path)?;
+
+    let mut upgrade = ManifestUpgrade {
+        from_version: manifest_version,
+        to_version: CURRENT_MANIFEST_VERSION,
+        diff: String::new(),
+        backup_path: None,
+        fragment_backup_paths: vec![],
+    };
+
+    if manifest_version == CURRENT_MANIFEST_VERSION {
+        debug!("Manifest is already at version {}.", manifest_version);
+        return Ok(upgrade);
+    }
+
+    let mut upgraded_files = vec![];
+
+    for (fragment_path, fragment) in split_manifest_fragments(&mut manifest, &fragments) {
+        let fragment_content = fs::read_to_string(project.join(&fragment_path))?;
+        let upgraded_content = update_manifest_document(
+            &fragment_content,
+            &toml::from_str::<Table>(&fragment_content)?,
+            &fragment,
+        )?;
+
+        if upgraded_content != fragment_content {
+            upgraded_files.push((fragment_path, fragment_content, upgraded_content));
+        }
+    }
+
+    if !fragments.is_empty() {
+        // Only keep the sections emptied by splitting off the fragments if the manifest
+        // defines them on its own.
+        let mut unmerged_manifest: Table = toml::from_str(&manifest_content)?;
+        upgrade_manifest(&mut unmerged_manifest, manifest_version)?;
+        remove_empty_sections(&mut manifest, &unmerged_manifest);
+    }
+
+    let upgraded_content = update_manifest_document(
+        &manifest_content,
+        &toml::from_str::<Table>(&manifest_content)?,
+        &manifest,
+    )?;
+    upgrade.diff = get_unified_diff(
+        &format!("manifest.toml (v{})", manifest_version),
+        &format!("manifest.toml (v{})", CURRENT_MANIFEST_VERSION),
+        &manifest_content,
+        &upgraded_content,
+    );
+
+    for (fragment_path, fragment_content, upgraded_fragment_content) in &upgraded_files {
+        upgrade.diff.push_str(&get_unified_diff(
+            &format!("{} (v{})", fragment_path.display(), manifest_version),
+            &format!(
+                "{} (v{})",
+                fragment_path.display(),
+                CURRENT_MANIFEST_VERSION
+            ),
+            fragment_content,
+            upgraded_fragment_content,
+        ));
+    }
+
+    if dry_run {
+        return Ok(upgrade);
+    }
+
+    for (fragment_path, _, upgraded_fragment_content) in upgraded_files {
+        let fragment_path = project.join(fragment_path);
+        upgrade
+            .fragment_backup_paths
+            .push(back_up_manifest(&fragment_path)?);
+        fs::write(&fragment_path, upgraded_fragment_content)?;
+    }
+
+    upgrade.backup_path = Some(back_up_manifest(&manifest_path)?);
+    fs::write(&manifest_path, upgraded_content)?;
+    debug!(
+        "Upgraded manifest from version {} to {}.",
+        manifest_version, CURRENT_MANIFEST_VERSION
+    );
+
+    Ok(upgrade)
+}
+
+/// Reads the manifest file, merges the manifest fragments it includes and upgrades the
+/// result to the current version in memory.
+///
+/// Fragments are not versioned on their own, they are merged before the upgrade and are
+/// upgraded from the version of the manifest file.
+///
+/// Returns the original file content, the upgraded manifest, the version of the file and
+/// the merged manifest fragments.
+fn load_manifest_table(
+    manifest_path: &Path,
+) -> Result<(String, Table, u32, Vec<ManifestFragment>)> {
     if !manifest_path.exists() {
         return Err(eyre!(
             "Manifest file does not exist. Please initialize a project before editing it."
@@ -1282,33 +1577,45 @@ pub fn load_and_convert_manifest(manifest_path: &std::path::PathBuf) -> Result<M
     );
 
     let mut manifest: Table = toml::from_str(&manifest_content)?;
+    let manifest_version = get_manifest_version(&manifest)?;
 
-    let current_manifest_version: u32 = manifest
-        .get("version")
-        .unwrap_or(&Value::Integer(0))
-        .as_integer()
-        .unwrap_or(0)
-        .try_into()?;
-
-    if current_manifest_version < CURRENT_MANIFEST_VERSION {
-        upgrade_manifest(&mut manifest, current_manifest_version)?;
-        debug!("Manifest upgraded to version {}.", CURRENT_MANIFEST_VERSION,);
-    } else if current_manifest_version > CURRENT_MANIFEST_VERSION {
+    if manifest_version > CURRENT_MANIFEST_VERSION {
         return Err(eyre!(
             "Manifest file is from a newer version of the program. Please update the program."
         ));
     }
 
-    let manifest = &toml::to_string(&manifest)?;
-    fs::write(manifest_path, manifest)?;
-    debug!(
-        "Manifest written back after potential upgrade ({} bytes).",
-        manifest.len()
-    );
+    let project = manifest_path.parent().unwrap_or(Path::new("."));
+    let fragments = merge_manifest_fragments(project, &mut manifest)?;
+
+    if manifest_version < CURRENT_MANIFEST_VERSION {
+        upgrade_manifest(&mut manifest, manifest_version)?;
+        debug!("Manifest upgraded to version {}.", CURRENT_MANIFEST_VERSION,);
+    }
 
-    let manifest: Manifest = toml::from_str(manifest)?;
+    Ok((manifest_content, manifest, manifest_version, fragments))
+}
 
-    Ok(manifest)
+fn get_manifest_version(manifest: &Table) -> Result<u32> {
+    Ok(manifest
+        .get("version")
+        .unwrap_or(&Value::Integer(0))
+        .as_integer()
+        .unwrap_or(0)
+        .try_into()?)
+}
+
+/// Copies a manifest file or manifest fragment to a `.bak` file next to it.
+fn back_up_manifest(manifest_path: &Path) -> Result<PathBuf> {
+    let backup_path = manifest_path.with_extension("toml.bak");
+    fs::copy(manifest_path, &backup_path)?;
+    info!(
+        "Backed up '{}' to '{}' before upgrading it.",
+        manifest_path.display(),
+        backup_path.display()
+    );
+
+    Ok(backup_path)
 }
 
 fn create_templates(project: &std::path::Path, templates: &Vec<Template>) -> Result<()> {
@@ -0,0 +1,103 @@
+-- Conditional content for TiefDown.
+--
+-- Divs and spans with the class "only" are kept only if all their conditions match the current
+-- conversion, divs and spans with the class "exclude" are removed if all their conditions match:
+--
+--   ::: {.only template="epub"}
+--   ::: {.exclude profile="print,proof"}
+--   [ebook only]{.only template-type="Epub"}
+--
+-- Conditions are the attributes template, template-type, profile and markdown-project. Each
+-- takes a comma separated list of values. The current conversion is read from the metadata
+-- fields tiefdown-template, tiefdown-template-type, tiefdown-profile and
+-- tiefdown-markdown-project.
+
+local conditions = {
+  ["template"] = { key = "tiefdown-template" },
+  ["template-type"] = { key = "tiefdown-template-type", case_insensitive = true },
+  ["profile"] = { key = "tiefdown-profile" },
+  ["markdown-project"] = { key = "tiefdown-markdown-project" },
+}
+
+local current = {}
+
+local function normalize(value, condition)
+  value = value:match("^%s*(.-)%s*$")
+  if condition.case_insensitive then
+    return value:lower()
+  end
+  return value
+end
+
+local function matches(attribute, condition)
+  local active = current[condition.key]
+  if active == nil then
+    return false
+  end
+
+  for value in attribute:gmatch("[^,]+") do
+    if normalize(value, condition) == normalize(active, condition) then
+      return true
+    end
+  end
+
+  return false
+end
+
+-- Returns true if all conditions of the element match, false if one does not and nil if the
+-- element has no conditions.
+local function evaluate(elem)
+  local result = nil
+
+  for name, condition in pairs(conditions) do
+    local attribute = elem.attributes[name]
+    if attribute ~= nil then
+      result = (result ~= false) and matches(attribute, condition)
+    end
+  end
+
+  return result
+end
+
+local function has_class(elem, class)
+  for _, c in ipairs(elem.classes) do
+    if c == class then
+      return true
+    end
+  end
+  return false
+end
+
+-- Returns the content to replace the element with, or nil to keep the element unchanged.
+local function filter(elem)
+  local only = has_class(elem, "only")
+  local exclude = has_class(elem, "exclude")
+
+  if not only and not exclude then
+    return nil
+  end
+
+  local result = evaluate(elem)
+  if result == nil then
+    return nil
+  end
+
+  if (only and not result) or (exclude and result) then
+    return {}
+  end
+
+  return elem.content
+end
+
+local function read_meta(meta)
+  for _, condition in pairs(conditions) do
+    if meta[condition.key] ~= nil then
+      current[condition.key] = pandoc.utils.stringify(meta[condition.key])
+    end
+  end
+end
+
+return {
+  { Meta = read_meta },
+  { Div = filter, Span = filter },
+}
@@ -0,0 +1,138 @@
+/// The number of unchanged lines shown around each change.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DiffLine<'a> {
+    Unchanged(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Creates a unified diff between two texts.
+///
+/// # Arguments
+///
+/// * `old_name` - The name of the old text, shown in the `---` header.
+/// * `new_name` - The name of the new text, shown in the `+++` header.
+/// * `old` - The old text.
+/// * `new` - The new text.
+///
+/// # Returns
+///
+/// The unified diff, or an empty string if the texts are equal.
+pub(crate) fn get_unified_diff(old_name: &str, new_name: &str, old: &str, new: &str) -> String {
+    let lines = get_diff_lines(
+        &old.lines().collect::<Vec<_>>(),
+        &new.lines().collect::<Vec<_>>(),
+    );
+
+    if lines
+        .iter()
+        .all(|line| matches!(line, DiffLine::Unchanged(_)))
+    {
+        return String::new();
+    }
+
+    let mut diff = format!("--- {}\n+++ {}\n", old_name, new_name);
+
+    for (start, end) in get_hunks(&lines) {
+        let old_start = lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_start = lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count();
+        let old_count = lines[start..end]
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_count = lines[start..end]
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count();
+
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            get_hunk_range(old_start, old_count),
+            get_hunk_range(new_start, new_count)
+        ));
+
+        for line in &lines[start..end] {
+            match line {
+                DiffLine::Unchanged(line) => diff.push_str(&format!(" {}\n", line)),
+                DiffLine::Removed(line) => diff.push_str(&format!("-{}\n", line)),
+                DiffLine::Added(line) => diff.push_str(&format!("+{}\n", line)),
+            }
+        }
+    }
+
+    diff
+}
+
+/// Computes the line changes via the longest common subsequence of both texts.
+fn get_diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Unchanged(old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+
+    lines.extend(old[i..].iter().map(|line| DiffLine::Removed(line)));
+    lines.extend(new[j..].iter().map(|line| DiffLine::Added(line)));
+
+    lines
+}
+
+/// Groups the changed lines with their context into hunks, merging overlapping ones.
+fn get_hunks(lines: &[DiffLine]) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = vec![];
+
+    for (index, line) in lines.iter().enumerate() {
+        if matches!(line, DiffLine::Unchanged(_)) {
+            continue;
+        }
+
+        let start = index.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (index + 1 + DIFF_CONTEXT_LINES).min(lines.len());
+
+        match hunks.last_mut() {
+            Some(hunk) if hunk.1 >= start => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    hunks
+}
+
+fn get_hunk_range(start: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, count)
+    }
+}
@@ -0,0 +1,462 @@
+use color_eyre::eyre::Result;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    bibliography::get_resources,
+    conversion::get_markdown_projects,
+    manifest_model::{Manifest, MarkdownProject, Template},
+    project_management::load_and_convert_manifest,
+    template_type::TemplateType,
+};
+
+/// A problem found in the manifest of a project.
+///
+/// # Fields
+///
+/// * `severity` - How severe the problem is.
+/// * `kind` - The kind of the problem.
+/// * `location` - The manifest entry the problem was found in, e.g. `template 'PDF'`.
+/// * `message` - A description of the problem.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    pub location: String,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// The severity of a diagnostic.
+///
+/// * `Error` - The conversion fails or doesn't behave as configured.
+/// * `Warning` - The manifest works, but likely contains a mistake.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// The kind of a diagnostic.
+///
+/// * `DuplicateName` - Two entries of the same list share a name.
+/// * `MissingPreprocessor` - A template references a preprocessor that doesn't exist.
+/// * `MissingProcessor` - A template references a processor that doesn't exist.
+/// * `MissingInjection` - A template references an injection that doesn't exist.
+/// * `MissingTemplate` - A profile references a template that doesn't exist.
+/// * `MissingProfile` - A markdown project's default profile doesn't exist.
+/// * `MissingFilter` - A lua filter of a template doesn't exist in the template directory.
+/// * `MissingInjectionFile` - A file of an injection doesn't exist.
+/// * `MissingResource` - A resource of a markdown project doesn't exist.
+/// * `InvalidMultiFileOutput` - A template's multi-file output conflicts with its settings.
+/// * `UnusedPreprocessor` - A preprocessor isn't used by any template.
+/// * `UnusedProcessor` - A processor isn't used by any template.
+/// * `UnusedInjection` - An injection isn't used by any template.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    DuplicateName,
+    MissingPreprocessor,
+    MissingProcessor,
+    MissingInjection,
+    MissingTemplate,
+    MissingProfile,
+    MissingFilter,
+    MissingInjectionFile,
+    MissingResource,
+    InvalidMultiFileOutput,
+    UnusedPreprocessor,
+    UnusedProcessor,
+    UnusedInjection,
+}
+
+/// Validates the manifest of a project without converting it.
+///
+/// Checks that the references between the entries of the manifest resolve, that the
+/// referenced files exist and that the template settings are compatible with each other.
+///
+/// # Arguments
+///
+/// * `project` - The path to the project directory (relative or absolute).
+///   * Defaults to the current directory if not provided.
+///
+/// # Returns
+///
+/// A Result containing either an error, if the manifest could not be loaded, or the
+/// diagnostics found in the manifest.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tiefdownlib::validation::{Severity, validate};
+/// use std::path::PathBuf;
+///
+/// let diagnostics = validate(Some(PathBuf::from("my_project"))).unwrap();
+/// for diagnostic in &diagnostics {
+///     println!("{}", diagnostic);
+/// }
+///
+/// let valid = diagnostics.iter().all(|d| d.severity != Severity::Error);
+/// ```
+pub fn validate(project: Option<PathBuf>) -> Result<Vec<Diagnostic>> {
+    let project = project.unwrap_or(PathBuf::from("."));
+    let manifest = load_and_convert_manifest(&project.join("manifest.toml"))?;
+
+    let diagnostics = validate_manifest(&project, &manifest);
+    debug!("Found {} diagnostics in the manifest.", diagnostics.len());
+
+    Ok(diagnostics)
+}
+
+fn validate_manifest(project: &Path, manifest: &Manifest) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let markdown_projects = get_markdown_projects(manifest);
+    let template_dir = project.join("template");
+
+    check_duplicate_names(
+        &mut diagnostics,
+        "markdown project",
+        markdown_projects.iter().map(|p| &p.name),
+    );
+    check_duplicate_names(
+        &mut diagnostics,
+        "template",
+        manifest.templates.iter().map(|t| &t.name),
+    );
+    check_duplicate_names(
+        &mut diagnostics,
+        "preprocessor",
+        manifest
+            .custom_processors
+            .preprocessors
+            .iter()
+            .map(|p| &p.name),
+    );
+    check_duplicate_names(
+        &mut diagnostics,
+        "processor",
+        manifest
+            .custom_processors
+            .processors
+            .iter()
+            .map(|p| &p.name),
+    );
+    check_duplicate_names(
+        &mut diagnostics,
+        "profile",
+        manifest.profiles.iter().flatten().map(|p| &p.name),
+    );
+    check_duplicate_names(
+        &mut diagnostics,
+        "injection",
+        manifest.injections.iter().flatten().map(|i| &i.name),
+    );
+
+    for template in &manifest.templates {
+        check_template(&mut diagnostics, manifest, template, &template_dir);
+    }
+
+    for profile in manifest.profiles.iter().flatten() {
+        for template in &profile.templates {
+            if !manifest.templates.iter().any(|t| t.name == *template) {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    kind: DiagnosticKind::MissingTemplate,
+                    location: format!("profile '{}'", profile.name),
+                    message: format!("Template '{}' does not exist.", template),
+                });
+            }
+        }
+    }
+
+    for markdown_project in &markdown_projects {
+        check_markdown_project(&mut diagnostics, project, manifest, markdown_project);
+    }
+
+    for injection in manifest.injections.iter().flatten() {
+        for file in &injection.files {
+            if !injection_file_exists(project, &markdown_projects, file) {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    kind: DiagnosticKind::MissingInjectionFile,
+                    location: format!("injection '{}'", injection.name),
+                    message: format!(
+                        "File '{}' does not exist in the template directory or the markdown projects.",
+                        file.display()
+                    ),
+                });
+            }
+        }
+    }
+
+    check_unused_entries(&mut diagnostics, manifest);
+
+    diagnostics
+}
+
+fn check_duplicate_names<'a>(
+    diagnostics: &mut Vec<Diagnostic>,
+    entry: &str,
+    names: impl Iterator<Item = &'a String>,
+) {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+
+    for name in names {
+        if !seen.insert(name) && reported.insert(name) {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                kind: DiagnosticKind::DuplicateName,
+                location: format!("{} '{}'", entry, name),
+                message: format!("There are multiple {}s with this name.", entry),
+            });
+        }
+    }
+}
+
+fn check_template(
+    diagnostics: &mut Vec<Diagnostic>,
+    manifest: &Manifest,
+    template: &Template,
+    template_dir: &Path,
+) {
+    let location = format!("template '{}'", template.name);
+    let mut push = |severity, kind, message| {
+        diagnostics.push(Diagnostic {
+            severity,
+            kind,
+            location: location.clone(),
+            message,
+        })
+    };
+
+    for preprocessor in template.preprocessors.iter().flat_map(|p| &p.preprocessors) {
+        if !manifest
+            .custom_processors
+            .preprocessors
+            .iter()
+            .any(|p| p.name == *preprocessor)
+        {
+            push(
+                Severity::Error,
+                DiagnosticKind::MissingPreprocessor,
+                format!("Preprocessor '{}' does not exist.", preprocessor),
+            );
+        }
+    }
+
+    if let Some(processor) = &template.processor
+        && !manifest
+            .custom_processors
+            .processors
+            .iter()
+            .any(|p| p.name == *processor)
+    {
+        push(
+            Severity::Error,
+            DiagnosticKind::MissingProcessor,
+            format!("Processor '{}' does not exist.", processor),
+        );
+    }
+
+    for injection in get_template_injections(template) {
+        if !manifest
+            .injections
+            .iter()
+            .flatten()
+            .any(|i| i.name == *injection)
+        {
+            push(
+                Severity::Error,
+                DiagnosticKind::MissingInjection,
+                format!("Injection '{}' does not exist.", injection),
+            );
+        }
+    }
+
+    for filter in template.filters.iter().flatten() {
+        if !template_dir.join(filter).exists() {
+            push(
+                Severity::Error,
+                DiagnosticKind::MissingFilter,
+                format!(
+                    "Filter '{}' does not exist in the template directory.",
+                    filter
+                ),
+            );
+        }
+    }
+
+    if template.multi_file_output.unwrap_or(false) {
+        if template.template_type != TemplateType::CustomPreprocessors {
+            push(
+                Severity::Error,
+                DiagnosticKind::InvalidMultiFileOutput,
+                format!(
+                    "Multi-file output is not supported for template type '{}'.",
+                    template.template_type
+                ),
+            );
+        }
+
+        if template
+            .preprocessors
+            .as_ref()
+            .is_some_and(|p| p.combined_output.is_some())
+        {
+            push(
+                Severity::Error,
+                DiagnosticKind::InvalidMultiFileOutput,
+                "Multi-file output cannot be combined with a combined output.".to_string(),
+            );
+        }
+
+        if template
+            .preprocessors
+            .as_ref()
+            .is_none_or(|p| p.output_extension.is_none())
+        {
+            push(
+                Severity::Error,
+                DiagnosticKind::InvalidMultiFileOutput,
+                "Multi-file output requires an output extension.".to_string(),
+            );
+        }
+    }
+}
+
+fn check_markdown_project(
+    diagnostics: &mut Vec<Diagnostic>,
+    project: &Path,
+    manifest: &Manifest,
+    markdown_project: &MarkdownProject,
+) {
+    let location = format!("markdown project '{}'", markdown_project.name);
+
+    if let Some(default_profile) = &markdown_project.default_profile
+        && !manifest
+            .profiles
+            .iter()
+            .flatten()
+            .any(|p| p.name == *default_profile)
+    {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            kind: DiagnosticKind::MissingProfile,
+            location: location.clone(),
+            message: format!("Default profile '{}' does not exist.", default_profile),
+        });
+    }
+
+    let markdown_dir = project.join(&markdown_project.path);
+    for resource in get_resources(markdown_project) {
+        if !markdown_dir.join(&resource).exists() {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                kind: DiagnosticKind::MissingResource,
+                location: location.clone(),
+                message: format!(
+                    "Resource '{}' does not exist in '{}'.",
+                    resource.display(),
+                    markdown_project.path.display()
+                ),
+            });
+        }
+    }
+}
+
+fn check_unused_entries(diagnostics: &mut Vec<Diagnostic>, manifest: &Manifest) {
+    for preprocessor in &manifest.custom_processors.preprocessors {
+        if !manifest.templates.iter().any(|t| {
+            t.preprocessors
+                .as_ref()
+                .is_some_and(|p| p.preprocessors.contains(&preprocessor.name))
+        }) {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                kind: DiagnosticKind::UnusedPreprocessor,
+                location: format!("preprocessor '{}'", preprocessor.name),
+                message: "The preprocessor is not used by any template.".to_string(),
+            });
+        }
+    }
+
+    for processor in &manifest.custom_processors.processors {
+        if !manifest
+            .templates
+            .iter()
+            .any(|t| t.processor.as_ref() == Some(&processor.name))
+        {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                kind: DiagnosticKind::UnusedProcessor,
+                location: format!("processor '{}'", processor.name),
+                message: "The processor is not used by any template.".to_string(),
+            });
+        }
+    }
+
+    for injection in manifest.injections.iter().flatten() {
+        if !manifest
+            .templates
+            .iter()
+            .any(|t| get_template_injections(t).any(|i| *i == injection.name))
+        {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                kind: DiagnosticKind::UnusedInjection,
+                location: format!("injection '{}'", injection.name),
+                message: "The injection is not used by any template.".to_string(),
+            });
+        }
+    }
+}
+
+fn get_template_injections(template: &Template) -> impl Iterator<Item = &String> {
+    template
+        .header_injections
+        .iter()
+        .chain(template.body_injections.iter())
+        .chain(template.footer_injections.iter())
+        .flatten()
+}
+
+/// Injection files are resolved in the compiled directory, which contains the template
+/// directory, the resources of the markdown project and its conversion directories.
+fn injection_file_exists(
+    project: &Path,
+    markdown_projects: &[MarkdownProject],
+    file: &Path,
+) -> bool {
+    if project.join("template").join(file).exists() {
+        return true;
+    }
+
+    markdown_projects.iter().any(|markdown_project| {
+        let markdown_dir = project.join(&markdown_project.path);
+
+        let is_resource = get_resources(markdown_project).iter().any(|resource| {
+            resource.file_name() == Some(file.as_os_str()) && markdown_dir.join(resource).exists()
+        });
+
+        let mut components = file.components();
+        let is_input_file = matches!(
+            components.next(),
+            Some(Component::Normal(conversion_dir))
+                if conversion_dir.to_string_lossy().ends_with("_convdir")
+        ) && markdown_dir.join(components.as_path()).exists();
+
+        is_resource || is_input_file
+    })
+}